@@ -1,15 +1,38 @@
 ﻿#include "BanetteGenerator.h"
+#include "BanetteGenerator/generator/bindings.h"
 
 #define LOCTEXT_NAMESPACE "FBanetteGeneratorModule"
 
+DEFINE_LOG_CATEGORY_STATIC(LogBanetteGenerator, Log, All);
+
+static void ForwardGeneratorLog(const int32_t Level, const char* Message)
+{
+    const FString Text = UTF8_TO_TCHAR(Message);
+    switch (static_cast<LogLevel>(Level))
+    {
+    case LogLevel::Error:
+        UE_LOG(LogBanetteGenerator, Error, TEXT("%s"), *Text);
+        break;
+    case LogLevel::Warning:
+        UE_LOG(LogBanetteGenerator, Warning, TEXT("%s"), *Text);
+        break;
+    case LogLevel::Debug:
+        UE_LOG(LogBanetteGenerator, Verbose, TEXT("%s"), *Text);
+        break;
+    default:
+        UE_LOG(LogBanetteGenerator, Log, TEXT("%s"), *Text);
+        break;
+    }
+}
+
 void FBanetteGeneratorModule::StartupModule()
 {
-    
+    banette::ffi::generator::banette_set_log_callback(&ForwardGeneratorLog);
 }
 
 void FBanetteGeneratorModule::ShutdownModule()
 {
-    
+    banette::ffi::generator::banette_set_log_callback(nullptr);
 }
 
 #undef LOCTEXT_NAMESPACE
@@ -4,15 +4,40 @@
 #include "BanetteGeneratorLibrary.h"
 #include "BanetteGenerator/generator/bindings.h"
 
-void UBanetteGeneratorLibrary::GenerateOpenApi(const FString OpenApiPath,
-                                               const FString OutputDir,
-                                               const FString FileName,
-                                               const FString ModuleName,
-                                               const FString ExtraHeaders)
+int32 UBanetteGeneratorLibrary::GenerateOpenApi(const FString OpenApiPath,
+                                                const FString OutputDir,
+                                                const FString FileName,
+                                                const FString ModuleName,
+                                                const FString ExtraHeaders)
 {
 	using namespace banette::ffi::generator::openapi;
-	generate(StringCast<ANSICHAR>(*OpenApiPath).Get(),
-	         StringCast<ANSICHAR>(*OutputDir).Get(),
-	         StringCast<ANSICHAR>(*FileName).Get(),
-	         StringCast<ANSICHAR>(*ModuleName).Get(), StringCast<ANSICHAR>(*ExtraHeaders).Get());
+	return generate(StringCast<ANSICHAR>(*OpenApiPath).Get(),
+	                StringCast<ANSICHAR>(*OutputDir).Get(),
+	                StringCast<ANSICHAR>(*FileName).Get(),
+	                StringCast<ANSICHAR>(*ModuleName).Get(), StringCast<ANSICHAR>(*ExtraHeaders).Get());
+}
+
+int32 UBanetteGeneratorLibrary::GenerateOpenApiWithHeaders(const FString OpenApiPath,
+                                                           const FString OutputDir,
+                                                           const FString FileName,
+                                                           const FString ModuleName,
+                                                           const TArray<FString>& ExtraHeaders)
+{
+	using namespace banette::ffi::generator::openapi;
+	TArray<TArray<ANSICHAR>> Converted;
+	for (const FString& Header : ExtraHeaders)
+	{
+		const auto Ansi = StringCast<ANSICHAR>(*Header);
+		Converted.Emplace(Ansi.Get(), Ansi.Length() + 1);
+	}
+	TArray<const char*> Headers;
+	for (const TArray<ANSICHAR>& Header : Converted)
+	{
+		Headers.Add(Header.GetData());
+	}
+	return generate_with_headers(StringCast<ANSICHAR>(*OpenApiPath).Get(),
+	                             StringCast<ANSICHAR>(*OutputDir).Get(),
+	                             StringCast<ANSICHAR>(*FileName).Get(),
+	                             StringCast<ANSICHAR>(*ModuleName).Get(),
+	                             Headers.GetData(), Headers.Num());
 }
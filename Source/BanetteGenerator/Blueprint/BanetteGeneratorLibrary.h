@@ -15,7 +15,13 @@ class BANETTEGENERATOR_API UBanetteGeneratorLibrary : public UBlueprintFunctionL
 	GENERATED_BODY()
 
 public:
+	/** Returns 0 on success, otherwise the generator's nonzero error category. */
 	UFUNCTION(BlueprintCallable)
-	static void GenerateOpenApi(FString OpenApiPath, FString OutputDir, FString FileName, FString ModuleName,
-	                            FString ExtraHeaders);
+	static int32 GenerateOpenApi(FString OpenApiPath, FString OutputDir, FString FileName, FString ModuleName,
+	                             FString ExtraHeaders);
+
+	/** Same as GenerateOpenApi, with one header per ExtraHeaders entry, e.g. "Custom.h" or "#include <vector>". */
+	UFUNCTION(BlueprintCallable)
+	static int32 GenerateOpenApiWithHeaders(FString OpenApiPath, FString OutputDir, FString FileName,
+	                                        FString ModuleName, const TArray<FString>& ExtraHeaders);
 };
@@ -0,0 +1,161 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+//! Selecting the body schema of an operation with large nested schemas, cloned by the
+//! Tera filters (as every caller did before the borrowing versions existed) versus
+//! borrowed by `request_body_schema`/`response_body_schema`.
+//!
+//! Both ways are timed, and the cloning filters are also measured in the allocations they
+//! make (the `body_schema_allocations` group). The borrowing versions make none, which
+//! criterion cannot report as it rejects zero measurements, so that is asserted instead.
+//! Run with `cargo bench --bench body_schema`.
+
+use criterion::measurement::{Measurement, ValueFormatter};
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use generator::filter::request_body_schema::{request_body_schema, request_body_schema_filter};
+use generator::filter::response_body_schema::{response_body_schema, response_body_schema_filter};
+use serde_json::{json, Value};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The system allocator, counting allocations.
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// Allocations made by `f`.
+fn allocations<T>(f: impl FnOnce() -> T) -> usize {
+    let start = Allocations.start();
+    black_box(f());
+    Allocations.end(start)
+}
+
+/// A criterion measurement counting the allocations made instead of the time taken.
+struct Allocations;
+
+impl Measurement for Allocations {
+    type Intermediate = usize;
+    type Value = usize;
+
+    fn start(&self) -> usize {
+        ALLOCATIONS.load(Ordering::Relaxed)
+    }
+
+    fn end(&self, start: usize) -> usize {
+        ALLOCATIONS.load(Ordering::Relaxed) - start
+    }
+
+    fn add(&self, v1: &usize, v2: &usize) -> usize {
+        v1 + v2
+    }
+
+    fn zero(&self) -> usize {
+        0
+    }
+
+    fn to_f64(&self, value: &usize) -> f64 {
+        *value as f64
+    }
+
+    fn formatter(&self) -> &dyn ValueFormatter {
+        self
+    }
+}
+
+impl ValueFormatter for Allocations {
+    fn scale_values(&self, _typical_value: f64, _values: &mut [f64]) -> &'static str {
+        "allocs"
+    }
+
+    fn scale_throughputs(
+        &self,
+        _typical_value: f64,
+        throughput: &Throughput,
+        values: &mut [f64],
+    ) -> &'static str {
+        let per = match throughput {
+            Throughput::Bytes(n) | Throughput::BytesDecimal(n) | Throughput::Elements(n) => *n,
+        };
+        values.iter_mut().for_each(|value| *value /= per as f64);
+        "allocs/elem"
+    }
+
+    fn scale_for_machines(&self, _values: &mut [f64]) -> &'static str {
+        "allocs"
+    }
+}
+
+/// An object schema `depth` levels deep, each level with `width` scalar properties and
+/// one nested object.
+fn nested_schema(depth: usize, width: usize) -> Value {
+    let mut properties: serde_json::Map<String, Value> = (0..width)
+        .map(|i| (format!("field{}", i), json!({ "type": "string", "maxLength": 64 })))
+        .collect();
+    if depth > 0 {
+        properties.insert("child".to_string(), nested_schema(depth - 1, width));
+    }
+    json!({ "type": "object", "properties": properties })
+}
+
+/// Benchmarks selecting a body schema through the filters, and unless `cloned_only` the
+/// borrowing versions too, in the group `name` with the measurement of `c`.
+fn select_body_schemas<M: Measurement>(c: &mut Criterion<M>, name: &str, cloned_only: bool) {
+    let schema = nested_schema(8, 32);
+    let request_body = json!({ "content": { "application/json": { "schema": schema } } });
+    let responses = json!({
+        "404": { "description": "Not found" },
+        "200": { "content": { "application/json": { "schema": schema } } }
+    });
+    let args = HashMap::new();
+    let request_borrowed = || request_body_schema(&request_body).map(|schema| schema.is_object());
+    let response_borrowed = || response_body_schema(&responses).map(|schema| schema.is_some());
+    assert_eq!(allocations(request_borrowed), 0);
+    assert_eq!(allocations(response_borrowed), 0);
+
+    let mut group = c.benchmark_group(name);
+    group.bench_function("request_cloned", |b| {
+        b.iter(|| request_body_schema_filter(black_box(&request_body), &args))
+    });
+    group.bench_function("response_cloned", |b| {
+        b.iter(|| response_body_schema_filter(black_box(&responses), &args))
+    });
+    if !cloned_only {
+        group.bench_function("request_borrowed", |b| b.iter(request_borrowed));
+        group.bench_function("response_borrowed", |b| b.iter(response_borrowed));
+    }
+    group.finish();
+}
+
+fn body_schema(c: &mut Criterion) {
+    select_body_schemas(c, "body_schema", false);
+}
+
+fn body_schema_allocations(c: &mut Criterion<Allocations>) {
+    select_body_schemas(c, "body_schema_allocations", true);
+}
+
+criterion_group!(benches, body_schema);
+criterion_group! {
+    name = allocation_benches;
+    // Every sample counts the same, which the plots cannot show
+    config = Criterion::default().with_measurement(Allocations).without_plots();
+    targets = body_schema_allocations
+}
+criterion_main!(benches, allocation_benches);
@@ -0,0 +1,102 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+//! Generating a directory of `FILES` specs with [`generate_dir`], whose specs share one
+//! engine and render in parallel on rayon's pool, versus the same run on a single-thread
+//! pool.
+//!
+//! Run with `cargo bench --bench render`.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use generator::openapi::batch::generate_dir;
+use generator::openapi::GenerateOptions;
+use serde_json::json;
+use std::fs;
+use std::hint::black_box;
+
+/// Files rendered per iteration.
+const FILES: usize = 8;
+
+/// Operations in each file's spec.
+const OPERATIONS: usize = 40;
+
+/// A spec for the tag `tag` with [`OPERATIONS`] operations on a shared record schema.
+fn tag_spec(tag: usize) -> String {
+    let paths: serde_json::Map<String, serde_json::Value> = (0..OPERATIONS)
+        .map(|operation| {
+            let path = format!("/tag{}/items{}/{{id}}", tag, operation);
+            let item = json!({
+                "get": {
+                    "tags": [format!("Tag{}", tag)],
+                    "parameters": [
+                        { "name": "id", "in": "path", "required": true,
+                          "schema": { "type": "integer" } }
+                    ],
+                    "responses": { "200": { "description": "OK", "content": {
+                        "application/json": { "schema": { "$ref": "#/components/schemas/Record" } }
+                    } } }
+                },
+                "put": {
+                    "tags": [format!("Tag{}", tag)],
+                    "requestBody": { "content": {
+                        "application/json": { "schema": { "$ref": "#/components/schemas/Record" } }
+                    } },
+                    "responses": { "204": { "description": "Updated" } }
+                }
+            });
+            (path, item)
+        })
+        .collect();
+    let spec = json!({
+        "openapi": "3.1.0",
+        "info": { "title": format!("Tag {}", tag), "version": "1.0.0" },
+        "paths": paths,
+        "components": { "schemas": { "Record": {
+            "type": "object",
+            "required": ["id"],
+            "properties": {
+                "id": { "type": "integer", "format": "int64" },
+                "name": { "type": "string" },
+                "tags": { "type": "array", "items": { "type": "string" } },
+                "score": { "type": "number" }
+            }
+        } } }
+    });
+    spec.to_string()
+}
+
+fn render_files(c: &mut Criterion) {
+    let dir = std::env::temp_dir().join("banette_bench_render");
+    fs::remove_dir_all(&dir).ok();
+    let (input, output) = (dir.join("specs"), dir.join("out"));
+    fs::create_dir_all(&input).unwrap();
+    for tag in 0..FILES {
+        fs::write(input.join(format!("tag{}.json", tag)), tag_spec(tag)).unwrap();
+    }
+
+    let options = GenerateOptions { source: true, ..Default::default() };
+    let generate = || {
+        let report = generate_dir(
+            input.to_str().unwrap(),
+            output.to_str().unwrap(),
+            false,
+            &[],
+            &options,
+        )
+        .unwrap();
+        assert!(report.is_ok());
+        black_box(report)
+    };
+    let single_thread = rayon::ThreadPoolBuilder::new().num_threads(1).build().unwrap();
+
+    let mut group = c.benchmark_group("render_files");
+    group.bench_function("sequential", |b| b.iter(|| single_thread.install(generate)));
+    group.bench_function("parallel", |b| b.iter(generate));
+    group.finish();
+
+    fs::remove_dir_all(&dir).ok();
+}
+
+criterion_group!(benches, render_files);
+criterion_main!(benches);
@@ -4,19 +4,88 @@
 #include <ostream>
 #include <new>
 
+/// Severity passed to a [`LogCallback`].
+enum class LogLevel : int32_t {
+  Info = 0,
+  Warning = 1,
+  Error = 2,
+  /// Detail for troubleshooting, only emitted at [`Verbosity::Verbose`].
+  Debug = 3,
+};
+
+/// Receives generator diagnostics; `msg` is only valid for the duration of the call.
+using LogCallback = void(*)(int32_t level, const char *msg);
+
 extern "C" {
 
 
+namespace banette {
+namespace ffi {
+namespace generator {
+
+/// Routes all generator diagnostics through `callback`, or back to stdio when null.
+void banette_set_log_callback(LogCallback callback);
+
+}  // namespace generator
+}  // namespace ffi
+}  // namespace banette
+
 namespace banette {
 namespace ffi {
 namespace generator {
 namespace openapi {
 
-void generate(const char *openapi_path,
-              const char *output_dir,
-              const char *file_name,
-              const char *module_name,
-              const char *extra_headers);
+/// Generates a header from an OpenAPI spec.
+///
+/// Returns `0` on success, otherwise the [`ErrorKind::code`] of the failure
+/// (`-1` if it could not be categorized). Details are logged to stderr.
+int32_t generate(const char *openapi_path,
+                 const char *output_dir,
+                 const char *file_name,
+                 const char *module_name,
+                 const char *extra_headers);
+
+/// Same as [`generate`], with the extra headers given as an array of `header_count` C
+/// strings instead of one delimited string. Each entry is parsed like `extra_headers`, so
+/// it may be a bare `Custom.h` or a full `#include <vector>`. A null `headers` or zero
+/// `header_count` means no extra headers.
+///
+/// # Safety
+///
+/// A non-null `headers` must point to at least `header_count` C string pointers.
+int32_t generate_with_headers(const char *openapi_path,
+                              const char *output_dir,
+                              const char *file_name,
+                              const char *module_name,
+                              const char *const *headers,
+                              uintptr_t header_count);
+
+/// Renders a header from an OpenAPI spec into `buf` instead of writing a file.
+///
+/// Returns the buffer size needed for the whole header including its NUL terminator,
+/// or `0` on failure (see [`banette_last_error`]). The copy is truncated to fit `len`
+/// and is always NUL-terminated; a null `buf` or zero `len` only reports the required size.
+///
+/// # Safety
+///
+/// A non-null `buf` must point to at least `len` writable bytes.
+uintptr_t generate_string(const char *openapi_path,
+                          const char *file_name,
+                          const char *module_name,
+                          const char *extra_headers,
+                          char *buf,
+                          uintptr_t len);
+
+/// Copies the message of the last failed generation call on this thread into `buf`.
+///
+/// Returns the buffer size needed for the whole message including its NUL terminator,
+/// or `0` if the last call succeeded. The copy is truncated to fit `len` and is always
+/// NUL-terminated; a null `buf` or zero `len` only reports the required size.
+///
+/// # Safety
+///
+/// A non-null `buf` must point to at least `len` writable bytes.
+uintptr_t banette_last_error(char *buf, uintptr_t len);
 
 }  // namespace openapi
 }  // namespace generator
@@ -0,0 +1,121 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use anyhow::{Context, Result};
+use generator::openapi::loader::STDIN_PATH;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Config file picked up from the working directory when `--config` is not given.
+pub const CONFIG_FILE_NAME: &str = "banette.toml";
+
+/// Defaults for the CLI inputs, read from a `banette.toml`. Flags override these values.
+///
+/// Relative `path`, `output_dir`, `template` and `source_template` values are relative to
+/// the config file's directory, so the config works from any working directory.
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    pub path: Option<String>,
+    pub output_dir: Option<String>,
+    pub file_name: Option<String>,
+    pub module_name: Option<String>,
+    pub extra_headers: Option<String>,
+    pub template: Option<PathBuf>,
+    pub source_template: Option<PathBuf>,
+}
+
+impl Config {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config: {}", path.display()))?;
+        let config: Self = toml::from_str(&content)
+            .with_context(|| format!("Invalid config: {}", path.display()))?;
+        Ok(config.relative_to(path.parent().unwrap_or(Path::new(""))))
+    }
+
+    /// Resolves the relative paths against `dir`. URLs and `-` for standard input are kept.
+    fn relative_to(mut self, dir: &Path) -> Self {
+        let join = |path: &str| dir.join(path).to_string_lossy().into_owned();
+        let is_local = |path: &str| path != STDIN_PATH && !path.contains("://");
+        self.path = self.path.map(|path| if is_local(&path) { join(&path) } else { path });
+        self.output_dir = self.output_dir.as_deref().map(join);
+        self.template = self.template.map(|path| dir.join(path));
+        self.source_template = self.source_template.map(|path| dir.join(path));
+        self
+    }
+
+    /// Loads `explicit` if given, otherwise `banette.toml` in the working directory if it
+    /// exists, otherwise an empty config.
+    pub fn discover(explicit: Option<&Path>) -> Result<Self> {
+        let default_path = Path::new(CONFIG_FILE_NAME);
+        match explicit {
+            Some(path) => Self::load(path),
+            None if default_path.is_file() => Self::load(default_path),
+            None => Ok(Self::default()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_load() {
+        let dir = std::env::temp_dir();
+        let path = dir.join("banette_config_load.toml");
+        fs::write(
+            &path,
+            "path = \"api.json\"\nmodule_name = \"ANX_API\"\nextra_headers = \"A.h;B.h\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            Config::load(&path).unwrap(),
+            Config {
+                path: Some(dir.join("api.json").to_string_lossy().into_owned()),
+                module_name: Some("ANX_API".to_string()),
+                extra_headers: Some("A.h;B.h".to_string()),
+                ..Default::default()
+            }
+        );
+
+        fs::write(&path, "pth = \"api.json\"\n").unwrap();
+        assert!(Config::load(&path).is_err());
+
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_config_load_resolves_paths_against_its_directory() {
+        let dir = std::env::temp_dir().join("banette_config_relative");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(CONFIG_FILE_NAME);
+        let resolved = |contents: &str| {
+            fs::write(&path, contents).unwrap();
+            Config::load(&path).unwrap()
+        };
+
+        let config = resolved(
+            "output_dir = \"Generated\"\ntemplate = \"templates/openapi.h.tera\"\n\
+             source_template = \"templates/openapi.cpp.tera\"\n",
+        );
+        let generated = dir.join("Generated");
+        assert_eq!(config.output_dir.as_deref(), Some(generated.to_string_lossy().as_ref()));
+        assert_eq!(config.template, Some(dir.join("templates/openapi.h.tera")));
+        assert_eq!(config.source_template, Some(dir.join("templates/openapi.cpp.tera")));
+
+        // URLs, standard input and absolute paths are kept
+        let url = "https://example.com/api.json";
+        assert_eq!(resolved(&format!("path = \"{}\"\n", url)).path.as_deref(), Some(url));
+        assert_eq!(resolved("path = \"-\"\n").path.as_deref(), Some("-"));
+        let absolute = std::env::temp_dir().join("api.json").to_string_lossy().into_owned();
+        let config = resolved(&format!("path = {:?}\n", absolute));
+        assert_eq!(config.path, Some(absolute));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
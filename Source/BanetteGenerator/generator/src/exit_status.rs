@@ -0,0 +1,101 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use generator::openapi::error::ErrorKind;
+use std::fmt;
+use std::process::ExitCode;
+
+/// Exit code of the `generator` binary, telling CI why a run failed.
+///
+/// Code 2 is left to clap, which exits with it on an invalid command line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitStatus {
+    /// Generation succeeded, or a dry run found everything up to date.
+    Success = 0,
+    /// A failure without a more specific code, such as a missing input.
+    Failure = 1,
+    /// The spec could not be read, fetched, or parsed.
+    SpecUnavailable = 3,
+    /// The template could not be prepared or rendered.
+    RenderFailed = 4,
+    /// The output could not be written.
+    WriteFailed = 5,
+    /// A dry run found generated files that would change.
+    OutOfDate = 6,
+    /// Linting the spec found generation hazards, with `validate` or `--lint-strict`.
+    LintFailed = 7,
+}
+
+impl ExitStatus {
+    /// The status for a run that failed with `error`, from its [`ErrorKind`], or an
+    /// [`OutOfDate`] or [`LintFailed`] error.
+    pub fn of(error: &anyhow::Error) -> Self {
+        if error.is::<OutOfDate>() {
+            return ExitStatus::OutOfDate;
+        }
+        if error.is::<LintFailed>() {
+            return ExitStatus::LintFailed;
+        }
+        match ErrorKind::of(error) {
+            Some(ErrorKind::Load) => ExitStatus::SpecUnavailable,
+            Some(ErrorKind::Render) => ExitStatus::RenderFailed,
+            Some(ErrorKind::Write) => ExitStatus::WriteFailed,
+            Some(ErrorKind::NullArgument | ErrorKind::InvalidArgument) | None => {
+                ExitStatus::Failure
+            }
+        }
+    }
+}
+
+impl From<ExitStatus> for ExitCode {
+    fn from(status: ExitStatus) -> Self {
+        ExitCode::from(status as u8)
+    }
+}
+
+/// The error of a dry run that found generated files out of date.
+#[derive(Debug)]
+pub struct OutOfDate;
+
+impl fmt::Display for OutOfDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Generated output is out of date")
+    }
+}
+
+impl std::error::Error for OutOfDate {}
+
+/// The error of a lint that found generation hazards where none are allowed.
+#[derive(Debug)]
+pub struct LintFailed;
+
+impl fmt::Display for LintFailed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Lint found generation hazards")
+    }
+}
+
+impl std::error::Error for LintFailed {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::{anyhow, Context};
+
+    #[test]
+    fn test_exit_status_of_error_kind() {
+        let failed = |kind: ErrorKind| Err::<(), _>(anyhow!("cause")).context(kind).unwrap_err();
+
+        assert_eq!(ExitStatus::of(&failed(ErrorKind::Load)), ExitStatus::SpecUnavailable);
+        assert_eq!(ExitStatus::of(&failed(ErrorKind::Render)), ExitStatus::RenderFailed);
+        assert_eq!(ExitStatus::of(&failed(ErrorKind::Write)), ExitStatus::WriteFailed);
+        assert_eq!(ExitStatus::of(&anyhow!("Missing --path")), ExitStatus::Failure);
+    }
+
+    #[test]
+    fn test_exit_status_out_of_date_and_lint_failed() {
+        assert_eq!(ExitStatus::of(&anyhow::Error::new(OutOfDate)), ExitStatus::OutOfDate);
+        assert_eq!(ExitStatus::of(&anyhow::Error::new(LintFailed)), ExitStatus::LintFailed);
+    }
+}
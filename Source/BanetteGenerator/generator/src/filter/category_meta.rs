@@ -0,0 +1,123 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use crate::filter::escape::escape_cpp_string;
+use std::collections::HashMap;
+use tera::{to_value, Result, Value};
+
+/// Category used when an operation has no tags and no `fallback` is given.
+const DEFAULT_CATEGORY: &str = "Default";
+
+/// Tera filter to derive a UFUNCTION `Category` from an operation's first tag.
+///
+/// Takes the operation's `tags` and returns a fragment such as `Category="Users"`. Only the
+/// first tag is used, trimmed, with any `|` replaced so the tag stays one category level,
+/// and escaped for a C++ string literal. Without tags (an empty array or null) the
+/// `fallback` argument is used instead, `"Default"` unless given. The optional `prefix`
+/// argument becomes the parent category, as in `Category="Pets|Users"`.
+///
+/// Usage in the template: {{ operation.tags | category_meta(prefix=file_name) }}
+pub fn category_meta_filter(value: &Value, args: &HashMap<String, Value>) -> Result<Value> {
+    // 1. Check if the input is a list of tags
+    let tags = match value {
+        Value::Array(tags) => tags.as_slice(),
+        Value::Null => &[],
+        _ => {
+            return Err(tera::Error::msg(
+                "category_meta filter expects an array of tags as input.",
+            ));
+        }
+    };
+
+    // 2. Pick the first usable tag, or the fallback
+    let first_tag = tags
+        .first()
+        .map(|tag| {
+            tag.as_str().ok_or_else(|| {
+                tera::Error::msg("category_meta filter expects all tags to be strings.")
+            })
+        })
+        .transpose()?
+        .map(|tag| tag.trim().replace('|', " "))
+        .filter(|tag| !tag.is_empty());
+    let category = match first_tag {
+        Some(tag) => tag,
+        None => args
+            .get("fallback")
+            .and_then(|v| v.as_str())
+            .unwrap_or(DEFAULT_CATEGORY)
+            .to_string(),
+    };
+
+    // 3. Nest it under the prefix, if any
+    let category = match args.get("prefix").and_then(|v| v.as_str()) {
+        Some(prefix) if !prefix.is_empty() => format!("{}|{}", prefix, category),
+        _ => category,
+    };
+
+    to_value(format!("Category=\"{}\"", escape_cpp_string(&category)))
+        .map_err(|e| tera::Error::msg(format!("Failed to convert string to Value: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn category_of(tags: Value, args: &[(&str, &str)]) -> String {
+        let args = args
+            .iter()
+            .map(|(key, value)| (key.to_string(), json!(value)))
+            .collect();
+        let result = category_meta_filter(&tags, &args).unwrap();
+        result.as_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_category_meta_single_tag() {
+        assert_eq!(category_of(json!(["Users"]), &[]), "Category=\"Users\"");
+        assert_eq!(
+            category_of(json!(["Users"]), &[("prefix", "PetStore")]),
+            "Category=\"PetStore|Users\""
+        );
+    }
+
+    #[test]
+    fn test_category_meta_first_tag_wins() {
+        assert_eq!(
+            category_of(json!(["Store", "Users"]), &[]),
+            "Category=\"Store\""
+        );
+    }
+
+    #[test]
+    fn test_category_meta_sanitizes_tag() {
+        assert_eq!(
+            category_of(json!(["  Admin|Users \"v2\" "]), &[]),
+            "Category=\"Admin Users \\\"v2\\\"\""
+        );
+    }
+
+    #[test]
+    fn test_category_meta_no_tags() {
+        assert_eq!(category_of(json!([]), &[]), "Category=\"Default\"");
+        assert_eq!(
+            category_of(Value::Null, &[("fallback", "Misc")]),
+            "Category=\"Misc\""
+        );
+        assert_eq!(
+            category_of(json!([" "]), &[("prefix", "PetStore"), ("fallback", "Misc")]),
+            "Category=\"PetStore|Misc\""
+        );
+    }
+
+    #[test]
+    fn test_category_meta_invalid_input() {
+        let result = category_meta_filter(&json!("Users"), &HashMap::new());
+        assert!(result.unwrap_err().to_string().contains("expects an array of tags"));
+
+        let result = category_meta_filter(&json!([1]), &HashMap::new());
+        assert!(result.unwrap_err().to_string().contains("tags to be strings"));
+    }
+}
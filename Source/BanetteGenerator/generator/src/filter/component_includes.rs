@@ -0,0 +1,342 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use crate::filter::to_ue_type::INLINED_REF_KEY;
+use std::collections::{BTreeSet, HashMap};
+use tera::{to_value, Map, Result, Value};
+
+/// Prefix of the `$ref`s that point at a component schema.
+const COMPONENT_REF_PREFIX: &str = "#/components/schemas/";
+
+/// Collects the names of all component schemas `schema` references through `$ref`,
+/// anywhere in its properties, items, or compositions.
+pub fn referenced_components(schema: &Value) -> BTreeSet<String> {
+    fn collect(value: &Value, names: &mut BTreeSet<String>) {
+        match value {
+            Value::Object(map) => {
+                if let Some(name) = map
+                    .get("$ref")
+                    .and_then(|r| r.as_str())
+                    .and_then(|r| r.strip_prefix(COMPONENT_REF_PREFIX))
+                {
+                    names.insert(name.to_string());
+                }
+                // An inlined component is named like the `$ref` it replaced
+                if let Some(name) = map
+                    .get(INLINED_REF_KEY)
+                    .and_then(|r| r.as_str())
+                    .and_then(|r| r.strip_prefix(COMPONENT_REF_PREFIX))
+                {
+                    names.insert(name.to_string());
+                    return;
+                }
+                map.values().for_each(|v| collect(v, names));
+            }
+            Value::Array(items) => items.iter().for_each(|v| collect(v, names)),
+            _ => {}
+        }
+    }
+
+    let mut names = BTreeSet::new();
+    collect(schema, &mut names);
+    names
+}
+
+/// Returns the components referenced by `name` that its header must forward declare
+/// instead of including.
+///
+/// That is `name` itself, and within a reference cycle every component that leads back to
+/// `name` and sorts before it. Includes then only go from earlier to later names inside a
+/// cycle, so they cannot form an include cycle, and the later component holds the earlier
+/// one through a pointer or `TSharedPtr`, which an incomplete type is enough for.
+pub fn forward_declared_components(name: &str, schemas: &Map<String, Value>) -> BTreeSet<String> {
+    let Some(schema) = schemas.get(name) else {
+        return BTreeSet::new();
+    };
+
+    referenced_components(schema)
+        .into_iter()
+        .filter(|referenced| {
+            referenced == name || (referenced.as_str() < name && reaches(referenced, name, schemas))
+        })
+        .collect()
+}
+
+/// Whether following `$ref`s from the component `from` can arrive at `to`.
+fn reaches(from: &str, to: &str, schemas: &Map<String, Value>) -> bool {
+    let mut visited = BTreeSet::new();
+    let mut pending = vec![from.to_string()];
+    while let Some(current) = pending.pop() {
+        if !visited.insert(current.clone()) {
+            continue;
+        }
+        let Some(schema) = schemas.get(&current) else {
+            continue;
+        };
+        for referenced in referenced_components(schema) {
+            if referenced == to {
+                return true;
+            }
+            pending.push(referenced);
+        }
+    }
+    false
+}
+
+/// Orders the component names so every component comes after the ones it references,
+/// except across the references [`forward_declared_components`] breaks, keeping
+/// alphabetical order otherwise.
+///
+/// Every cycle is broken that way, so the order always exists.
+pub fn component_order(schemas: &Map<String, Value>) -> Vec<String> {
+    fn visit(
+        name: &str,
+        schemas: &Map<String, Value>,
+        done: &mut BTreeSet<String>,
+        order: &mut Vec<String>,
+    ) {
+        if !done.insert(name.to_string()) {
+            return;
+        }
+        let Some(schema) = schemas.get(name) else {
+            return;
+        };
+        let forward_declared = forward_declared_components(name, schemas);
+        for referenced in referenced_components(schema).difference(&forward_declared) {
+            visit(referenced, schemas, done, order);
+        }
+        order.push(name.to_string());
+    }
+
+    let mut done = BTreeSet::new();
+    let mut order = Vec::with_capacity(schemas.len());
+    for name in schemas.keys() {
+        visit(name, schemas, &mut done, &mut order);
+    }
+    order
+}
+
+/// Tera filter to list the component schema names in declaration order for a single
+/// header (see [`component_order`]).
+///
+/// Usage in the template: {% for name in components.schemas | component_order %}
+pub fn component_order_filter(value: &Value, _args: &HashMap<String, Value>) -> Result<Value> {
+    let schemas = value.as_object().ok_or_else(|| {
+        tera::Error::msg("component_order filter expects the component schemas object as input.")
+    })?;
+
+    to_value(component_order(schemas))
+        .map_err(|e| tera::Error::msg(format!("Failed to convert names to Value: {}", e)))
+}
+
+/// Tera filter to forward declare, as `struct F<Name>;` lines, every component that some
+/// component of a single header references across a cycle (see
+/// [`forward_declared_components`]). Yields an empty string when there is no cycle.
+///
+/// Usage in the template: {{ components.schemas | cycle_forward_declarations }}
+pub fn cycle_forward_declarations_filter(
+    value: &Value,
+    _args: &HashMap<String, Value>,
+) -> Result<Value> {
+    let schemas = value.as_object().ok_or_else(|| {
+        tera::Error::msg(
+            "cycle_forward_declarations filter expects the component schemas object as input.",
+        )
+    })?;
+
+    let declared: BTreeSet<String> = schemas
+        .keys()
+        .flat_map(|name| forward_declared_components(name, schemas))
+        .collect();
+    let lines: Vec<String> = declared.iter().map(|name| format!("struct F{};", name)).collect();
+
+    to_value(lines.join("\n"))
+        .map_err(|e| tera::Error::msg(format!("Failed to convert string to Value: {}", e)))
+}
+
+/// Header generated for the component `name`.
+pub fn component_header(name: &str) -> String {
+    format!("{}.h", name)
+}
+
+/// Tera filter to build the include block for the header of one component schema.
+///
+/// Every component the schema references gets an `#include` of its header (`<Name>.h`),
+/// sorted and deduplicated. A reference back to the component itself cannot be included,
+/// so it is forward declared as `struct F<Name>;` instead, which is enough for the
+/// `TArray` or pointer that must hold a self-reference. With the optional `schemas`
+/// argument (all component schemas), references that close a cycle are forward declared
+/// too (see [`forward_declared_components`]). Yields an empty string when nothing is
+/// referenced.
+///
+/// Usage in the template: {{ schema | component_includes(name=name, schemas=components.schemas) }}
+pub fn component_includes_filter(value: &Value, args: &HashMap<String, Value>) -> Result<Value> {
+    // 1. Check the input schema and the component's own name
+    if !value.is_object() {
+        return Err(tera::Error::msg(
+            "component_includes filter expects a schema object as input.",
+        ));
+    }
+    let name = args.get("name").and_then(|v| v.as_str()).ok_or_else(|| {
+        tera::Error::msg("component_includes filter requires a 'name' string argument.")
+    })?;
+
+    // 2. Split the references into includes and forward declarations
+    let referenced = referenced_components(value);
+    let forward_declared = match args.get("schemas").and_then(|v| v.as_object()) {
+        Some(schemas) => forward_declared_components(name, schemas),
+        None => referenced.iter().filter(|r| *r == name).cloned().collect(),
+    };
+    let mut lines: Vec<String> = referenced
+        .difference(&forward_declared)
+        .map(|referenced| format!("#include \"{}\"", component_header(referenced)))
+        .collect();
+
+    // 3. Forward declarations go after the includes
+    if !lines.is_empty() && !forward_declared.is_empty() {
+        lines.push(String::new());
+    }
+    lines.extend(forward_declared.iter().map(|name| format!("struct F{};", name)));
+
+    to_value(lines.join("\n"))
+        .map_err(|e| tera::Error::msg(format!("Failed to convert string to Value: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn includes_of(schema: Value, name: &str) -> String {
+        let args = HashMap::from([("name".to_string(), json!(name))]);
+        let result = component_includes_filter(&schema, &args).unwrap();
+        result.as_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_component_includes_two_references() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "profile": { "$ref": "#/components/schemas/Profile" },
+                "addresses": {
+                    "type": "array",
+                    "items": { "$ref": "#/components/schemas/Address" }
+                },
+                "billing": { "allOf": [{ "$ref": "#/components/schemas/Address" }] }
+            }
+        });
+
+        assert_eq!(
+            includes_of(schema, "User"),
+            "#include \"Address.h\"\n#include \"Profile.h\""
+        );
+    }
+
+    #[test]
+    fn test_component_includes_self_reference() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "children": { "type": "array", "items": { "$ref": "#/components/schemas/Node" } }
+            }
+        });
+
+        assert_eq!(includes_of(schema, "Node"), "struct FNode;");
+    }
+
+    #[test]
+    fn test_component_includes_mixed_and_empty() {
+        let schema = json!({
+            "properties": {
+                "parent": { "$ref": "#/components/schemas/Node" },
+                "tag": { "$ref": "#/components/schemas/Tag" },
+                "external": { "$ref": "common.yaml#/Thing" }
+            }
+        });
+        assert_eq!(
+            includes_of(schema, "Node"),
+            "#include \"Tag.h\"\n\nstruct FNode;"
+        );
+
+        assert_eq!(includes_of(json!({ "type": "string" }), "Name"), "");
+    }
+
+    #[test]
+    fn test_component_includes_two_schema_cycle() {
+        let schemas = json!({
+            "Guild": {
+                "properties": {
+                    "leader": { "$ref": "#/components/schemas/Player" },
+                    "emblem": { "$ref": "#/components/schemas/Emblem" }
+                }
+            },
+            "Player": {
+                "properties": { "guild": { "$ref": "#/components/schemas/Guild" } }
+            },
+            "Emblem": { "type": "string" }
+        });
+        let includes = |name: &str| {
+            let args = HashMap::from([
+                ("name".to_string(), json!(name)),
+                ("schemas".to_string(), schemas.clone()),
+            ]);
+            let result = component_includes_filter(&schemas[name], &args).unwrap();
+            result.as_str().unwrap().to_string()
+        };
+
+        // Guild sorts first, so it includes Player, and Player only forward declares Guild
+        assert_eq!(includes("Guild"), "#include \"Emblem.h\"\n#include \"Player.h\"");
+        assert_eq!(includes("Player"), "struct FGuild;");
+        assert!(!includes("Player").contains("#include \"Guild.h\""));
+    }
+
+    #[test]
+    fn test_forward_declared_components_longer_cycle() {
+        let schemas = json!({
+            "A": { "properties": { "b": { "$ref": "#/components/schemas/B" } } },
+            "B": { "properties": { "c": { "$ref": "#/components/schemas/C" } } },
+            "C": {
+                "properties": {
+                    "a": { "$ref": "#/components/schemas/A" },
+                    "c": { "$ref": "#/components/schemas/C" }
+                }
+            }
+        });
+        let schemas = schemas.as_object().unwrap();
+
+        assert!(forward_declared_components("A", schemas).is_empty());
+        assert!(forward_declared_components("B", schemas).is_empty());
+        assert_eq!(
+            forward_declared_components("C", schemas),
+            BTreeSet::from(["A".to_string(), "C".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_component_order_breaks_cycles() {
+        let schemas = json!({
+            "Alpha": { "properties": { "beta": { "$ref": "#/components/schemas/Beta" } } },
+            "Beta": { "properties": {
+                "alpha": { "$ref": "#/components/schemas/Alpha" },
+                "gamma": { "$ref": "#/components/schemas/Gamma" }
+            } },
+            "Gamma": { "type": "object" },
+            "Node": { "properties": { "next": { "$ref": "#/components/schemas/Node" } } }
+        });
+        let schemas = schemas.as_object().unwrap();
+
+        assert_eq!(component_order(schemas), ["Gamma", "Beta", "Alpha", "Node"]);
+        let declarations =
+            cycle_forward_declarations_filter(&json!(schemas), &HashMap::new()).unwrap();
+        assert_eq!(declarations, json!("struct FAlpha;\nstruct FNode;"));
+    }
+
+    #[test]
+    fn test_component_includes_requires_name() {
+        let result = component_includes_filter(&json!({}), &HashMap::new());
+
+        assert!(result.unwrap_err().to_string().contains("'name' string argument"));
+    }
+}
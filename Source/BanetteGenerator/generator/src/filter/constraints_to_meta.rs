@@ -0,0 +1,211 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use std::collections::HashMap;
+use tera::{to_value, Map, Result, Value};
+
+/// Tera filter to turn a schema's constraints into UPROPERTY metadata.
+///
+/// For numbers, reads `minimum`, `maximum`, `exclusiveMinimum`, and `exclusiveMaximum` (both the
+/// OpenAPI 3.1 numeric form and the 3.0 boolean form) and returns a fragment such as
+/// `meta=(ClampMin="0", ClampMax="10")`, or an empty string when there is no bound.
+///
+/// Unreal clamps are inclusive, so for `integer` schemas an exclusive bound is moved one
+/// step inwards (`exclusiveMinimum: 0` becomes `ClampMin="1"`). Floats have no next
+/// representable step worth emitting, so an exclusive float bound is emitted as-is and
+/// the boundary value itself stays allowed in the editor.
+///
+/// Unreal has no metadata limiting a string's length, so a string's `minLength` and
+/// `maxLength` are documented in its tooltip instead: `ToolTip="Length: 1..64"`, or
+/// `Length: 8` for a fixed length and `Length: >= 1` / `Length: <= 64` for a single bound.
+/// The length is not enforced.
+///
+/// With `wrap=false` the entries are returned without `meta=(...)`, for joining with other
+/// metadata into one block.
+///
+/// Usage in the template: UPROPERTY(EditAnywhere, {{ prop_schema | constraints_to_meta }})
+pub fn constraints_to_meta_filter(value: &Value, args: &HashMap<String, Value>) -> Result<Value> {
+    // 1. Check if the input is a schema object
+    let schema = value.as_object().ok_or_else(|| {
+        tera::Error::msg("constraints_to_meta filter expects a schema object as input.")
+    })?;
+
+    let has_type = |name: &str| match schema.get("type") {
+        Some(Value::String(t)) => t == name,
+        Some(Value::Array(types)) => types.iter().any(|t| t == name),
+        _ => false,
+    };
+    let is_integer = has_type("integer");
+
+    // 2. Resolve each bound
+    let min = bound(schema, "minimum", "exclusiveMinimum", is_integer, 1);
+    let max = bound(schema, "maximum", "exclusiveMaximum", is_integer, -1);
+
+    // 3. Build the metadata fragment
+    let mut entries = Vec::new();
+    if let Some(min) = min {
+        entries.push(format!("ClampMin=\"{}\"", min));
+    }
+    if let Some(max) = max {
+        entries.push(format!("ClampMax=\"{}\"", max));
+    }
+    if has_type("string")
+        && let Some(length) = length_tooltip(schema)
+    {
+        entries.push(format!("ToolTip=\"{}\"", length));
+    }
+
+    let wrap = args.get("wrap").and_then(|v| v.as_bool()).unwrap_or(true);
+    let meta = if entries.is_empty() {
+        String::new()
+    } else if wrap {
+        format!("meta=({})", entries.join(", "))
+    } else {
+        entries.join(", ")
+    };
+
+    to_value(meta)
+        .map_err(|e| tera::Error::msg(format!("Failed to convert string to Value: {}", e)))
+}
+
+/// Describes the `minLength`/`maxLength` of a string schema, if it has either.
+fn length_tooltip(schema: &Map<String, Value>) -> Option<String> {
+    let length = |key| schema.get(key).and_then(|v| v.as_u64());
+    let range = match (length("minLength"), length("maxLength")) {
+        (Some(min), Some(max)) if min == max => min.to_string(),
+        (Some(min), Some(max)) => format!("{}..{}", min, max),
+        (Some(min), None) => format!(">= {}", min),
+        (None, Some(max)) => format!("<= {}", max),
+        (None, None) => return None,
+    };
+    Some(format!("Length: {}", range))
+}
+
+/// Returns the inclusive bound as a literal, moving exclusive integer bounds by `step`.
+fn bound(
+    schema: &Map<String, Value>,
+    inclusive_key: &str,
+    exclusive_key: &str,
+    is_integer: bool,
+    step: i64,
+) -> Option<String> {
+    let (limit, exclusive) = match (schema.get(exclusive_key), schema.get(inclusive_key)) {
+        // OpenAPI 3.1: the exclusive bound is a number of its own
+        (Some(Value::Number(limit)), _) => (limit, true),
+        // OpenAPI 3.0: the exclusive bound is a flag on the inclusive one
+        (flag, Some(Value::Number(limit))) => (limit, flag == Some(&Value::Bool(true))),
+        _ => return None,
+    };
+
+    if exclusive && is_integer {
+        let limit = limit.as_f64()?;
+        let adjusted = if step > 0 {
+            limit.floor() as i64 + step
+        } else {
+            limit.ceil() as i64 + step
+        };
+        Some(adjusted.to_string())
+    } else {
+        Some(limit.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn meta_of(schema: Value) -> String {
+        let result = constraints_to_meta_filter(&schema, &HashMap::new()).unwrap();
+        result.as_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_constraints_to_meta_inclusive() {
+        assert_eq!(
+            meta_of(json!({ "type": "integer", "minimum": 0, "maximum": 10 })),
+            "meta=(ClampMin=\"0\", ClampMax=\"10\")"
+        );
+        assert_eq!(
+            meta_of(json!({ "type": "number", "minimum": 0.5 })),
+            "meta=(ClampMin=\"0.5\")"
+        );
+    }
+
+    #[test]
+    fn test_constraints_to_meta_exclusive_integer() {
+        // OpenAPI 3.1 numeric form
+        assert_eq!(
+            meta_of(json!({ "type": "integer", "exclusiveMinimum": 0, "exclusiveMaximum": 10 })),
+            "meta=(ClampMin=\"1\", ClampMax=\"9\")"
+        );
+        // OpenAPI 3.0 boolean form
+        assert_eq!(
+            meta_of(json!({
+                "type": ["integer", "null"],
+                "minimum": 0,
+                "exclusiveMinimum": true,
+                "maximum": 10,
+                "exclusiveMaximum": false
+            })),
+            "meta=(ClampMin=\"1\", ClampMax=\"10\")"
+        );
+    }
+
+    #[test]
+    fn test_constraints_to_meta_exclusive_float() {
+        assert_eq!(
+            meta_of(json!({ "type": "number", "exclusiveMinimum": 0.0, "exclusiveMaximum": 1.5 })),
+            "meta=(ClampMin=\"0.0\", ClampMax=\"1.5\")"
+        );
+    }
+
+    #[test]
+    fn test_constraints_to_meta_string_length() {
+        assert_eq!(
+            meta_of(json!({ "type": "string", "minLength": 1, "maxLength": 64 })),
+            "meta=(ToolTip=\"Length: 1..64\")"
+        );
+        assert_eq!(
+            meta_of(json!({ "type": ["string", "null"], "minLength": 8, "maxLength": 8 })),
+            "meta=(ToolTip=\"Length: 8\")"
+        );
+    }
+
+    #[test]
+    fn test_constraints_to_meta_string_single_length_bound() {
+        assert_eq!(
+            meta_of(json!({ "type": "string", "minLength": 3 })),
+            "meta=(ToolTip=\"Length: >= 3\")"
+        );
+        assert_eq!(
+            meta_of(json!({ "type": "string", "maxLength": 255 })),
+            "meta=(ToolTip=\"Length: <= 255\")"
+        );
+    }
+
+    #[test]
+    fn test_constraints_to_meta_no_constraints() {
+        assert_eq!(meta_of(json!({ "type": "integer" })), "");
+        assert_eq!(meta_of(json!({ "type": "string" })), "");
+        // Length bounds only apply to strings
+        assert_eq!(meta_of(json!({ "type": "array", "minLength": 3 })), "");
+    }
+
+    #[test]
+    fn test_constraints_to_meta_unwrapped() {
+        let args = HashMap::from([("wrap".to_string(), json!(false))]);
+        let schema = json!({ "type": "integer", "minimum": 0, "maximum": 10 });
+        let result = constraints_to_meta_filter(&schema, &args).unwrap();
+
+        assert_eq!(result, json!("ClampMin=\"0\", ClampMax=\"10\""));
+    }
+
+    #[test]
+    fn test_constraints_to_meta_invalid_input() {
+        let result = constraints_to_meta_filter(&json!([]), &HashMap::new());
+
+        assert!(result.unwrap_err().to_string().contains("expects a schema object"));
+    }
+}
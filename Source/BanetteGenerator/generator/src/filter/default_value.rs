@@ -0,0 +1,99 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use crate::filter::escape::escape_cpp_string;
+use std::collections::HashMap;
+use tera::{to_value, Result, Value};
+
+/// Tera filter to convert a schema's `default` into a C++ initializer literal.
+///
+/// Numbers are emitted as-is, strings as escaped `TEXT("...")` literals, and booleans as
+/// `true`/`false`. A missing or null `default`, as well as array and object defaults, yields
+/// an empty string so the member keeps its default construction.
+///
+/// Usage in the template: {{ prop_schema | default_value }}
+pub fn default_value_filter(value: &Value, _args: &HashMap<String, Value>) -> Result<Value> {
+    // 1. Check if the input is a schema object
+    let schema = value
+        .as_object()
+        .ok_or_else(|| tera::Error::msg("default_value filter expects a schema object as input."))?;
+
+    // 2. Convert the default into a literal
+    let literal = match schema.get("default") {
+        Some(Value::Number(n)) => n.to_string(),
+        Some(Value::Bool(b)) => b.to_string(),
+        Some(Value::String(s)) => format!("TEXT(\"{}\")", escape_cpp_string(s)),
+        _ => String::new(),
+    };
+
+    to_value(literal)
+        .map_err(|e| tera::Error::msg(format!("Failed to convert string to Value: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn default_of(schema: Value) -> String {
+        let result = default_value_filter(&schema, &HashMap::new()).unwrap();
+        result.as_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_default_value_integer() {
+        assert_eq!(default_of(json!({ "type": "integer", "default": 5 })), "5");
+        assert_eq!(default_of(json!({ "type": "integer", "default": -12 })), "-12");
+    }
+
+    #[test]
+    fn test_default_value_number() {
+        assert_eq!(default_of(json!({ "type": "number", "default": 0.5 })), "0.5");
+    }
+
+    #[test]
+    fn test_default_value_string() {
+        assert_eq!(
+            default_of(json!({ "type": "string", "default": "CN-1" })),
+            "TEXT(\"CN-1\")"
+        );
+    }
+
+    #[test]
+    fn test_default_value_string_escaping() {
+        assert_eq!(
+            default_of(json!({ "type": "string", "default": "say \"hi\"\\now" })),
+            "TEXT(\"say \\\"hi\\\"\\\\now\")"
+        );
+        assert_eq!(
+            default_of(json!({ "type": "string", "default": "a\nb" })),
+            "TEXT(\"a\\nb\")"
+        );
+    }
+
+    #[test]
+    fn test_default_value_boolean() {
+        assert_eq!(default_of(json!({ "type": "boolean", "default": true })), "true");
+        assert_eq!(default_of(json!({ "type": "boolean", "default": false })), "false");
+    }
+
+    #[test]
+    fn test_default_value_array_and_object() {
+        assert_eq!(default_of(json!({ "type": "array", "default": [1, 2] })), "");
+        assert_eq!(default_of(json!({ "type": "object", "default": { "a": 1 } })), "");
+    }
+
+    #[test]
+    fn test_default_value_missing() {
+        assert_eq!(default_of(json!({ "type": "integer" })), "");
+        assert_eq!(default_of(json!({ "type": "integer", "default": null })), "");
+    }
+
+    #[test]
+    fn test_default_value_invalid_input() {
+        let result = default_value_filter(&json!("integer"), &HashMap::new());
+
+        assert!(result.unwrap_err().to_string().contains("expects a schema object"));
+    }
+}
@@ -0,0 +1,110 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use std::collections::HashMap;
+use tera::{to_value, Result, Value};
+
+/// Tera filter to turn a description into a Doxygen `/** ... */` comment block.
+///
+/// Each line of the description is prefixed with ` * `, and `*/` inside the text is
+/// escaped so it cannot close the comment early. A missing or blank description yields an
+/// empty string, so templates can emit the result unconditionally. The optional `indent`
+/// argument is the number of spaces put before every line after the first.
+///
+/// Usage in the template: {{ operation.description | default(value="") | description_to_doxygen(indent=4) }}
+pub fn description_to_doxygen_filter(
+    value: &Value,
+    args: &HashMap<String, Value>,
+) -> Result<Value> {
+    // 1. Accept a string, treating null as a missing description
+    let description = match value {
+        Value::Null => "",
+        Value::String(s) => s.as_str(),
+        _ => {
+            return Err(tera::Error::msg(
+                "description_to_doxygen filter expects a string as input.",
+            ));
+        }
+    };
+
+    let indent = " ".repeat(args.get("indent").and_then(|v| v.as_u64()).unwrap_or(0) as usize);
+
+    // 2. Split into lines without surrounding blank lines
+    let lines: Vec<&str> = description.trim().lines().map(str::trim_end).collect();
+    if lines.is_empty() {
+        return Ok(Value::String(String::new()));
+    }
+
+    // 3. Build the comment block
+    let mut block = String::from("/**\n");
+    for line in lines {
+        block.push_str(&indent);
+        if line.is_empty() {
+            block.push_str(" *\n");
+        } else {
+            block.push_str(" * ");
+            block.push_str(&line.replace("*/", "*\\/"));
+            block.push('\n');
+        }
+    }
+    block.push_str(&indent);
+    block.push_str(" */");
+
+    to_value(block)
+        .map_err(|e| tera::Error::msg(format!("Failed to convert string to Value: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_description_to_doxygen_single_line() {
+        let value = json!("Gets a user.");
+        let result = description_to_doxygen_filter(&value, &HashMap::new()).unwrap();
+
+        assert_eq!(result.as_str().unwrap(), "/**\n * Gets a user.\n */");
+    }
+
+    #[test]
+    fn test_description_to_doxygen_multi_line() {
+        let mut args = HashMap::new();
+        args.insert("indent".to_string(), json!(4));
+        let value = json!("\nGets a user.\n\nReturns **404** if missing.  \n");
+        let result = description_to_doxygen_filter(&value, &args).unwrap();
+
+        assert_eq!(
+            result.as_str().unwrap(),
+            "/**\n     * Gets a user.\n     *\n     * Returns **404** if missing.\n     */"
+        );
+    }
+
+    #[test]
+    fn test_description_to_doxygen_escapes_comment_end() {
+        let value = json!("Ends early */ int Injected; /*");
+        let result = description_to_doxygen_filter(&value, &HashMap::new()).unwrap();
+
+        assert_eq!(
+            result.as_str().unwrap(),
+            "/**\n * Ends early *\\/ int Injected; /*\n */"
+        );
+        assert_eq!(result.as_str().unwrap().matches("*/").count(), 1);
+    }
+
+    #[test]
+    fn test_description_to_doxygen_empty() {
+        for value in [json!(null), json!(""), json!("  \n ")] {
+            let result = description_to_doxygen_filter(&value, &HashMap::new()).unwrap();
+            assert_eq!(result.as_str().unwrap(), "");
+        }
+    }
+
+    #[test]
+    fn test_description_to_doxygen_invalid_input() {
+        let result = description_to_doxygen_filter(&json!(42), &HashMap::new());
+
+        assert!(result.unwrap_err().to_string().contains("expects a string"));
+    }
+}
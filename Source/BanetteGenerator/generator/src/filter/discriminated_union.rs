@@ -0,0 +1,133 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use crate::filter::to_ue_type::{schema_ref, to_ue_type_filter};
+use serde_json::json;
+use std::collections::HashMap;
+use tera::{Map, Result, Value};
+
+/// Type used for a union that cannot be tagged, matching `to_ue_type`.
+const UNTAGGED_UNION_TYPE: &str = "FInstancedStruct";
+
+/// Tera filter to describe a `oneOf`/`anyOf` schema with a `discriminator` as a tagged union.
+///
+/// Returns an object with:
+/// - `property`: the discriminator's `propertyName`
+/// - `mapping`: discriminator value -> UE type of the variant
+///
+/// Explicit `discriminator.mapping` entries (schema names or `$ref`s) come first; every
+/// `$ref` variant not mapped explicitly is added under its schema name, as the OpenAPI
+/// implicit mapping does. Without a discriminator the plain `FInstancedStruct` string is
+/// returned, like `to_ue_type` gives for any union.
+///
+/// Usage in the template: {% set union = schema | discriminated_union %}{% if union is object %}
+pub fn discriminated_union_filter(value: &Value, _args: &HashMap<String, Value>) -> Result<Value> {
+    // 1. Check that the input is a union schema
+    let schema = value.as_object().ok_or_else(|| {
+        tera::Error::msg("discriminated_union filter expects a schema object as input.")
+    })?;
+    let variants = schema
+        .get("oneOf")
+        .or_else(|| schema.get("anyOf"))
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| {
+            tera::Error::msg("discriminated_union filter expects a oneOf or anyOf schema.")
+        })?;
+
+    // 2. Fall back to an untagged union without a discriminator
+    let Some(discriminator) = schema.get("discriminator").and_then(|v| v.as_object()) else {
+        return Ok(Value::String(UNTAGGED_UNION_TYPE.to_string()));
+    };
+    let property = discriminator
+        .get("propertyName")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| tera::Error::msg("discriminator is missing 'propertyName'."))?;
+
+    // 3. Explicit mapping
+    let mut mapping = Map::new();
+    let mut mapped_refs = Vec::new();
+    if let Some(explicit) = discriminator.get("mapping").and_then(|v| v.as_object()) {
+        for (tag, target) in explicit {
+            let Some(target) = target.as_str() else {
+                continue;
+            };
+            let reference = if target.contains('/') {
+                target.to_string()
+            } else {
+                format!("#/components/schemas/{}", target)
+            };
+            mapping.insert(tag.clone(), ref_to_ue_type(&reference)?);
+            mapped_refs.push(reference);
+        }
+    }
+
+    // 4. Implicit mapping for the remaining `$ref` variants
+    for reference in variants.iter().filter_map(schema_ref) {
+        if mapped_refs.iter().any(|r| r == reference) {
+            continue;
+        }
+        let tag = reference.rsplit('/').next().unwrap_or(reference);
+        if !mapping.contains_key(tag) {
+            mapping.insert(tag.to_string(), ref_to_ue_type(reference)?);
+        }
+    }
+
+    Ok(json!({ "property": property, "mapping": mapping }))
+}
+
+fn ref_to_ue_type(reference: &str) -> Result<Value> {
+    to_ue_type_filter(&json!({ "$ref": reference }), &HashMap::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discriminated_union_one_of() {
+        let schema = json!({
+            "oneOf": [
+                { "$ref": "#/components/schemas/Dog" },
+                { "$ref": "#/components/schemas/Cat" },
+                { "$ref": "#/components/schemas/Lizard" }
+            ],
+            "discriminator": {
+                "propertyName": "petType",
+                "mapping": {
+                    "dog": "#/components/schemas/Dog",
+                    "cat": "Cat"
+                }
+            }
+        });
+        let result = discriminated_union_filter(&schema, &HashMap::new()).unwrap();
+
+        assert_eq!(
+            result,
+            json!({
+                "property": "petType",
+                "mapping": { "dog": "FDog", "cat": "FCat", "Lizard": "FLizard" }
+            })
+        );
+    }
+
+    #[test]
+    fn test_discriminated_union_bare_any_of() {
+        let schema = json!({
+            "anyOf": [
+                { "$ref": "#/components/schemas/Dog" },
+                { "type": "string" }
+            ]
+        });
+        let result = discriminated_union_filter(&schema, &HashMap::new()).unwrap();
+
+        assert_eq!(result, json!("FInstancedStruct"));
+    }
+
+    #[test]
+    fn test_discriminated_union_invalid_input() {
+        let result = discriminated_union_filter(&json!({ "type": "object" }), &HashMap::new());
+
+        assert!(result.unwrap_err().to_string().contains("oneOf or anyOf"));
+    }
+}
@@ -0,0 +1,72 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use crate::filter::escape::escape_cpp_string;
+use std::collections::HashMap;
+use tera::{to_value, Result, Value};
+
+/// Tera filter to turn a property schema's `title` into a `DisplayName` meta entry.
+///
+/// Returns a fragment such as `DisplayName="User ID"`, with the title escaped for a C++
+/// string literal, or an empty string when the schema has no `title`. The fragment has no
+/// `meta=(...)` around it, so it can be joined with the other metadata of the property.
+///
+/// Usage in the template: UPROPERTY(EditAnywhere, meta=({{ prop_schema | display_name_meta }}))
+pub fn display_name_meta_filter(value: &Value, _args: &HashMap<String, Value>) -> Result<Value> {
+    // 1. Check if the input is a schema object
+    let schema = value.as_object().ok_or_else(|| {
+        tera::Error::msg("display_name_meta filter expects a schema object as input.")
+    })?;
+
+    // 2. Build the fragment from the title
+    let fragment = match schema.get("title").and_then(|t| t.as_str()) {
+        Some(title) if !title.trim().is_empty() => {
+            format!("DisplayName=\"{}\"", escape_cpp_string(title.trim()))
+        }
+        _ => String::new(),
+    };
+
+    to_value(fragment)
+        .map_err(|e| tera::Error::msg(format!("Failed to convert string to Value: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn display_name_of(schema: Value) -> String {
+        let result = display_name_meta_filter(&schema, &HashMap::new()).unwrap();
+        result.as_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_display_name_meta_with_title() {
+        assert_eq!(
+            display_name_of(json!({ "type": "string", "title": "User ID" })),
+            "DisplayName=\"User ID\""
+        );
+    }
+
+    #[test]
+    fn test_display_name_meta_without_title() {
+        assert_eq!(display_name_of(json!({ "type": "string" })), "");
+        assert_eq!(display_name_of(json!({ "type": "string", "title": "  " })), "");
+    }
+
+    #[test]
+    fn test_display_name_meta_title_with_quotes() {
+        assert_eq!(
+            display_name_of(json!({ "title": "The \"Best\" Score" })),
+            "DisplayName=\"The \\\"Best\\\" Score\""
+        );
+    }
+
+    #[test]
+    fn test_display_name_meta_invalid_input() {
+        let result = display_name_meta_filter(&json!("title"), &HashMap::new());
+
+        assert!(result.unwrap_err().to_string().contains("expects a schema object"));
+    }
+}
@@ -0,0 +1,124 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use std::collections::HashMap;
+use tera::{Result, Value};
+
+/// Tera filter to extract the error payload schema from an OpenAPI responses object.
+///
+/// Mirrors `response_body_schema`, but selects the error response instead:
+/// 1. The `default` response
+/// 2. Otherwise the lowest 4xx status (including the `4XX` range)
+/// 3. Otherwise the lowest 5xx status (including the `5XX` range)
+///
+/// From the selected response, the schema is taken preferring `application/json`, then
+/// the first available media type. An error response without `content` yields `null`.
+/// Responses with no error status at all are reported as an error, or yield `null` too
+/// when the `optional` argument is `true`.
+///
+/// Usage in the template: `{{ operation.responses | error_body_schema | to_ue_type }}`
+pub fn error_body_schema_filter(value: &Value, args: &HashMap<String, Value>) -> Result<Value> {
+    // 1. Check that the input is an object (responses object)
+    let responses = value.as_object().ok_or_else(|| {
+        tera::Error::msg("Input to error_body_schema must be a valid responses object.")
+    })?;
+
+    // 2. Pick the error response: default, then 4xx, then 5xx
+    let mut codes: Vec<&String> = responses.keys().collect();
+    codes.sort();
+    let response = responses.get("default").or_else(|| {
+        ['4', '5'].iter().find_map(|class| {
+            codes
+                .iter()
+                .find(|code| code.len() == 3 && code.starts_with(*class))
+                .and_then(|code| responses.get(*code))
+        })
+    });
+    let optional = args.get("optional").and_then(|v| v.as_bool()).unwrap_or(false);
+    let response = match response {
+        Some(response) => response,
+        None if optional => return Ok(Value::Null),
+        None => {
+            return Err(tera::Error::msg(
+                "No error response defined (expected 'default', 4xx, or 5xx).",
+            ));
+        }
+    };
+
+    // 3. Extract the schema, preferring application/json
+    let Some(content) = response.get("content").and_then(|c| c.as_object()) else {
+        return Ok(Value::Null);
+    };
+
+    let media_type = content.get("application/json").or_else(|| content.values().next());
+    match media_type.and_then(|m| m.get("schema")) {
+        Some(schema) => Ok(schema.clone()),
+        None => Ok(Value::Null),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_error_body_schema_prefers_default() {
+        let responses = json!({
+            "200": { "content": { "application/json": { "schema": { "type": "string" } } } },
+            "404": {
+                "content": {
+                    "application/json": { "schema": { "$ref": "#/components/schemas/NotFound" } }
+                }
+            },
+            "default": {
+                "content": {
+                    "application/json": { "schema": { "$ref": "#/components/schemas/Error" } }
+                }
+            }
+        });
+        let result = error_body_schema_filter(&responses, &HashMap::new()).unwrap();
+
+        assert_eq!(result, json!({ "$ref": "#/components/schemas/Error" }));
+    }
+
+    #[test]
+    fn test_error_body_schema_422_only() {
+        let responses = json!({
+            "201": { "description": "Created" },
+            "422": {
+                "content": {
+                    "text/plain": { "schema": { "type": "string" } },
+                    "application/json": { "schema": { "$ref": "#/components/schemas/Invalid" } }
+                }
+            },
+            "503": { "content": { "application/json": { "schema": { "type": "string" } } } }
+        });
+        let result = error_body_schema_filter(&responses, &HashMap::new()).unwrap();
+
+        assert_eq!(result, json!({ "$ref": "#/components/schemas/Invalid" }));
+    }
+
+    #[test]
+    fn test_error_body_schema_without_content() {
+        let responses = json!({ "5XX": { "description": "Server error" } });
+        let result = error_body_schema_filter(&responses, &HashMap::new()).unwrap();
+
+        assert!(result.is_null());
+    }
+
+    #[test]
+    fn test_error_body_schema_no_error_responses() {
+        let responses = json!({
+            "200": { "content": { "application/json": { "schema": { "type": "string" } } } },
+            "204": { "description": "No Content" }
+        });
+        let result = error_body_schema_filter(&responses, &HashMap::new());
+
+        assert!(result.unwrap_err().to_string().contains("No error response defined"));
+
+        let args = HashMap::from([("optional".to_string(), json!(true))]);
+        assert!(error_body_schema_filter(&responses, &args).unwrap().is_null());
+    }
+}
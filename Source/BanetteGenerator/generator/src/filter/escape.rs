@@ -0,0 +1,77 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use std::collections::HashMap;
+use tera::{to_value, Result, Value};
+
+/// Escapes a string for use inside a C++ string literal.
+///
+/// Backslashes and double quotes are escaped so the value cannot end the literal early,
+/// and newlines, carriage returns, and tabs become `\n`, `\r`, and `\t`.
+pub fn escape_cpp_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Tera filter to escape a string for use inside a C++ string literal.
+///
+/// See [`escape_cpp_string`] for the rules.
+///
+/// Usage in the template: TEXT("{{ url | escape_cpp_string }}")
+pub fn escape_cpp_string_filter(value: &Value, _args: &HashMap<String, Value>) -> Result<Value> {
+    let s = value.as_str().ok_or_else(|| {
+        tera::Error::msg("escape_cpp_string filter expects a string as input.")
+    })?;
+
+    to_value(escape_cpp_string(s))
+        .map_err(|e| tera::Error::msg(format!("Failed to convert string to Value: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_cpp_string_newline() {
+        assert_eq!(escape_cpp_string("line1\nline2"), "line1\\nline2");
+    }
+
+    #[test]
+    fn test_escape_cpp_string_tab() {
+        assert_eq!(escape_cpp_string("a\tb"), "a\\tb");
+    }
+
+    #[test]
+    fn test_escape_cpp_string_carriage_return() {
+        assert_eq!(escape_cpp_string("a\r\nb"), "a\\r\\nb");
+    }
+
+    #[test]
+    fn test_escape_cpp_string_combined() {
+        assert_eq!(
+            escape_cpp_string("say \"hi\"\\\t\r\n"),
+            "say \\\"hi\\\"\\\\\\t\\r\\n"
+        );
+        assert_eq!(escape_cpp_string("/plain/path"), "/plain/path");
+    }
+
+    #[test]
+    fn test_escape_cpp_string_filter() {
+        let result = escape_cpp_string_filter(&Value::from("a\"b"), &HashMap::new()).unwrap();
+        assert_eq!(result, Value::from("a\\\"b"));
+
+        let result = escape_cpp_string_filter(&Value::from(1), &HashMap::new());
+        assert!(result.unwrap_err().to_string().contains("expects a string"));
+    }
+}
@@ -0,0 +1,119 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use std::collections::HashMap;
+use tera::{to_value, Result, Value};
+
+/// Tera filter to pretty-print the example of a schema or media type as JSON.
+///
+/// Uses `example` if present, otherwise the first of `examples`: the first item of a
+/// schema's `examples` array, or the `value` of the first (by name) Example Object in a
+/// media type's `examples` map. A media type without either falls back to its `schema`.
+/// Any `*/` in the output is written as `*\/`, which is still valid JSON, so the result can
+/// sit inside a `/* */` comment. Without an example, an empty string is returned.
+///
+/// Usage in the template: {{ media_type | example_value }}
+pub fn example_value_filter(value: &Value, _args: &HashMap<String, Value>) -> Result<Value> {
+    // 1. Check if the input is a schema or media type object
+    let object = value.as_object().ok_or_else(|| {
+        tera::Error::msg("example_value filter expects a schema or media type object as input.")
+    })?;
+
+    // 2. Find the example
+    let Some(example) = find_example(object) else {
+        return Ok(Value::String(String::new()));
+    };
+
+    // 3. Pretty-print it
+    let json = serde_json::to_string_pretty(example)
+        .map_err(|e| tera::Error::msg(format!("Failed to print example: {}", e)))?;
+
+    to_value(json.replace("*/", "*\\/"))
+        .map_err(|e| tera::Error::msg(format!("Failed to convert string to Value: {}", e)))
+}
+
+/// Returns the example of a schema or media type, see [`example_value_filter`].
+fn find_example(object: &serde_json::Map<String, Value>) -> Option<&Value> {
+    if let Some(example) = object.get("example").filter(|example| !example.is_null()) {
+        return Some(example);
+    }
+    let example = match object.get("examples") {
+        Some(Value::Array(examples)) => examples.first(),
+        Some(Value::Object(examples)) => examples.values().find_map(|example| example.get("value")),
+        _ => None,
+    };
+    example.or_else(|| object.get("schema")?.as_object().and_then(find_example))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn example_of(value: Value) -> String {
+        let result = example_value_filter(&value, &HashMap::new()).unwrap();
+        result.as_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_example_value_schema_example() {
+        let schema = json!({
+            "type": "object",
+            "example": { "name": "Alice", "age": 30 }
+        });
+
+        assert_eq!(example_of(schema), "{\n  \"age\": 30,\n  \"name\": \"Alice\"\n}");
+        assert_eq!(example_of(json!({ "type": "integer", "example": 7 })), "7");
+    }
+
+    #[test]
+    fn test_example_value_media_type_example() {
+        let media_type = json!({
+            "schema": { "type": "array", "items": { "type": "string" }, "example": ["schema"] },
+            "example": ["a", "b"]
+        });
+
+        assert_eq!(example_of(media_type), "[\n  \"a\",\n  \"b\"\n]");
+    }
+
+    #[test]
+    fn test_example_value_examples_first_chosen() {
+        let media_type = json!({
+            "examples": {
+                "b_admin": { "summary": "Admin", "value": { "role": "admin" } },
+                "a_guest": { "summary": "Guest", "value": { "role": "guest" } }
+            }
+        });
+        let schema = json!({ "type": "string", "examples": ["first", "second"] });
+
+        assert_eq!(example_of(media_type), "{\n  \"role\": \"guest\"\n}");
+        assert_eq!(example_of(schema), "\"first\"");
+    }
+
+    #[test]
+    fn test_example_value_falls_back_to_schema() {
+        let media_type = json!({ "schema": { "type": "string", "example": "from schema" } });
+
+        assert_eq!(example_of(media_type), "\"from schema\"");
+    }
+
+    #[test]
+    fn test_example_value_keeps_comments_closed() {
+        assert_eq!(example_of(json!({ "example": "a */ b" })), "\"a *\\/ b\"");
+    }
+
+    #[test]
+    fn test_example_value_none_present() {
+        assert_eq!(example_of(json!({ "type": "string" })), "");
+        assert_eq!(example_of(json!({ "example": null })), "");
+        assert_eq!(example_of(json!({ "examples": { "external": { "externalValue": "x" } } })), "");
+    }
+
+    #[test]
+    fn test_example_value_invalid_input() {
+        let result = example_value_filter(&json!("example"), &HashMap::new());
+
+        assert!(result.unwrap_err().to_string().contains("expects a schema or media type"));
+    }
+}
@@ -0,0 +1,139 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use crate::filter::escape::escape_cpp_string;
+use crate::filter::path_to_func_name::convert_to_pascal_case_with;
+use crate::filter::sanitize_identifier::sanitize_identifier;
+use crate::filter::to_ue_type::get_effective_type;
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use tera::{Result, Value};
+
+/// Values that read as `true`, compared ignoring case.
+const TRUE_VALUES: &[&str] = &["true", "yes", "y", "on", "1", "enabled", "enable", "active"];
+
+/// Whether `schema` is a `string` schema whose `enum` holds exactly two distinct strings
+/// (and possibly `null`), such as `["on", "off"]`.
+pub fn is_flag_enum(schema: &Value) -> bool {
+    let Some(values) = schema.get("enum").and_then(|v| v.as_array()) else {
+        return false;
+    };
+
+    get_effective_type(schema) == "string"
+        && values.iter().all(|v| v.is_null() || v.is_string())
+        && flag_values(values).len() == 2
+}
+
+/// Tera filter to describe a two-value string enum (see [`is_flag_enum`]) for a `UENUM`.
+///
+/// Returns `false` for any other schema, otherwise an object with:
+/// - `entries`: `{ name, value, literal }` per enum value, in spec order, `literal` being
+///   the value escaped for a C++ string
+/// - `true_name`: the entry a `ToBool()` helper maps to `true`, the first value reading as
+///   true (`yes`, `on`, `enabled`, ...), or else the first value
+///
+/// Entry names are the values sanitized into PascalCase identifiers (`on` -> `On`,
+/// `not set` -> `NotSet`). A name already taken by the other entry gets `_` suffixes, so
+/// `["Yes", "yes"]` becomes `Yes` and `Yes_`.
+///
+/// Usage in the template: {% set flag_enum = schema | flag_enum %}
+pub fn flag_enum_filter(value: &Value, _args: &HashMap<String, Value>) -> Result<Value> {
+    if !value.is_object() {
+        return Err(tera::Error::msg("flag_enum filter expects a schema object as input."));
+    }
+    if !is_flag_enum(value) {
+        return Ok(Value::Bool(false));
+    }
+
+    let values = flag_values(value["enum"].as_array().into_iter().flatten());
+    let mut used = HashSet::new();
+    let mut entries = Vec::with_capacity(values.len());
+    for text in &values {
+        let mut name = sanitize_identifier(&convert_to_pascal_case_with(
+            &sanitize_identifier(text),
+            &[],
+        ));
+        while used.contains(&name) {
+            name.push('_');
+        }
+
+        used.insert(name.clone());
+        entries.push(json!({ "name": name, "value": text, "literal": escape_cpp_string(text) }));
+    }
+
+    let true_index = values
+        .iter()
+        .position(|text| TRUE_VALUES.iter().any(|t| t.eq_ignore_ascii_case(text.trim())))
+        .unwrap_or(0);
+    let true_name = entries[true_index]["name"].clone();
+
+    Ok(json!({ "entries": entries, "true_name": true_name }))
+}
+
+/// The distinct string values of an `enum`, in order.
+fn flag_values<'a>(values: impl IntoIterator<Item = &'a Value>) -> Vec<&'a str> {
+    let mut distinct: Vec<&str> = Vec::new();
+    for text in values.into_iter().filter_map(Value::as_str) {
+        if !distinct.contains(&text) {
+            distinct.push(text);
+        }
+    }
+    distinct
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn describe(schema: Value) -> Value {
+        flag_enum_filter(&schema, &HashMap::new()).unwrap()
+    }
+
+    #[test]
+    fn test_flag_enum_on_off() {
+        let result = describe(json!({ "type": "string", "enum": ["off", "on"] }));
+
+        assert_eq!(
+            result,
+            json!({
+                "entries": [
+                    { "name": "Off", "value": "off", "literal": "off" },
+                    { "name": "On", "value": "on", "literal": "on" }
+                ],
+                "true_name": "On"
+            })
+        );
+    }
+
+    #[test]
+    fn test_flag_enum_disambiguates_names() {
+        let result = describe(json!({ "type": ["string", "null"], "enum": ["Yes", "yes", null] }));
+
+        assert_eq!(result["entries"][0]["name"], "Yes");
+        assert_eq!(result["entries"][1]["name"], "Yes_");
+        assert_eq!(result["true_name"], "Yes");
+    }
+
+    #[test]
+    fn test_flag_enum_sanitizes_names() {
+        let result = describe(json!({ "type": "string", "enum": ["not set", "1st \"pick\""] }));
+
+        assert_eq!(result["entries"][0]["name"], "NotSet");
+        assert_eq!(result["entries"][1]["name"], "_1stPick");
+        assert_eq!(result["entries"][1]["literal"], "1st \\\"pick\\\"");
+        // Neither value reads as true, so the first one does
+        assert_eq!(result["true_name"], "NotSet");
+    }
+
+    #[test]
+    fn test_flag_enum_not_a_flag_enum() {
+        assert_eq!(describe(json!({ "type": "string", "enum": ["a", "b", "c"] })), json!(false));
+        assert_eq!(describe(json!({ "type": "string", "enum": ["a", "a"] })), json!(false));
+        assert_eq!(describe(json!({ "type": "integer", "enum": [0, 1] })), json!(false));
+        assert_eq!(describe(json!({ "type": "string" })), json!(false));
+
+        let result = flag_enum_filter(&json!("on"), &HashMap::new());
+        assert!(result.unwrap_err().to_string().contains("expects a schema object"));
+    }
+}
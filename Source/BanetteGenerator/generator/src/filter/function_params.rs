@@ -0,0 +1,269 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use crate::filter::has_request_body::has_request_body_filter;
+use crate::filter::parameter_default::parameter_default_filter;
+use crate::filter::request_body_schema::request_body_schema;
+use crate::filter::required_params::{is_required_param, typed_param};
+use crate::filter::to_ue_type::to_ue_type_filter;
+use std::collections::HashMap;
+use tera::{to_value, Result, Value};
+
+/// Tera filter to build the C++ parameter list of an operation's function.
+///
+/// Parameters are emitted in this order, named by `sanitize_identifier` and typed by
+/// `to_ue_type`:
+/// 1. Path parameters, in the order they appear in the `path` argument
+/// 2. Other required parameters (query, header, cookie)
+/// 3. Optional parameters without a schema `default`
+/// 4. `const FBody& RequestBody` when the operation has a request body
+/// 5. Optional parameters with a schema `default`, as C++ default arguments converted by
+///    `parameter_default` (e.g. `FString shard = TEXT("CN-1")`)
+///
+/// Default arguments come last because C++ only allows them on trailing parameters.
+///
+/// Optional arguments:
+/// - `exclude`: the name of a query parameter to leave out, such as the page an
+///   `_AllPages` helper walks by itself
+/// - `request_body`: `false` to leave out the request body
+/// - `trailing`: declarations to place after the request body but ahead of the defaulted
+///   parameters, such as out parameters, which may not follow a default argument
+/// - `defaults`: `false` to leave out the default arguments, as an out-of-line definition
+///   must, keeping the order
+/// - `as_list`: `true` to return the declarations as an array instead of one string
+///
+/// Usage in the template: {{ operation | function_params(path=path) }}
+pub fn function_params_filter(value: &Value, args: &HashMap<String, Value>) -> Result<Value> {
+    // 1. Check the operation and path
+    let operation = value.as_object().ok_or_else(|| {
+        tera::Error::msg("function_params filter expects an operation object as input.")
+    })?;
+    let path = args
+        .get("path")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| tera::Error::msg("function_params requires a 'path' argument"))?;
+    let exclude = args.get("exclude").and_then(|v| v.as_str());
+    let flag = |name: &str| args.get(name).and_then(|v| v.as_bool());
+    let with_body = flag("request_body").unwrap_or(true);
+    let with_defaults = flag("defaults").unwrap_or(true);
+
+    let is_excluded = |param: &&Value| {
+        exclude.is_some()
+            && param.get("name").and_then(|v| v.as_str()) == exclude
+            && param.get("in").and_then(|v| v.as_str()) == Some("query")
+    };
+    let params = match operation.get("parameters") {
+        Some(Value::Array(params)) => params
+            .iter()
+            .filter(|param| !is_excluded(param))
+            .map(typed_param)
+            .collect::<Result<Vec<_>>>()?,
+        _ => Vec::new(),
+    };
+    let is_in_path = |param: &Value| param.get("in").and_then(|v| v.as_str()) == Some("path");
+
+    // 2. Path parameters, ordered by their position in the path
+    let mut path_params: Vec<&Value> = params.iter().filter(|p| is_in_path(p)).collect();
+    sort_by_path_position(&mut path_params, path);
+
+    let mut declarations: Vec<String> = path_params.iter().map(|p| declare(p)).collect();
+
+    // 3. Required, then optional, non-path parameters
+    let (required, optional): (Vec<&Value>, Vec<&Value>) = params
+        .iter()
+        .filter(|p| !is_in_path(p))
+        .partition(|p| is_required_param(p));
+    declarations.extend(required.iter().map(|p| declare(p)));
+    let mut defaulted = Vec::new();
+    for param in optional {
+        match default_of(param)? {
+            Some(default) if with_defaults => {
+                defaulted.push(format!("{} = {}", declare(param), default))
+            }
+            Some(_) => defaulted.push(declare(param)),
+            None => declarations.push(declare(param)),
+        }
+    }
+
+    // 4. Request body
+    if with_body && has_request_body_filter(value, &HashMap::new())?.as_bool() == Some(true) {
+        let schema = request_body_schema(&operation["requestBody"])?;
+        let body_type = to_ue_type_filter(schema, &HashMap::new())?;
+        declarations.push(format!("const {}& RequestBody", body_type.as_str().unwrap_or_default()));
+    }
+
+    // 5. Parameters that must come before the default arguments
+    if let Some(trailing) = args.get("trailing").and_then(|v| v.as_str()).filter(|t| !t.is_empty())
+    {
+        declarations.push(trailing.to_string());
+    }
+
+    // 6. Parameters with a default argument
+    declarations.extend(defaulted);
+
+    if flag("as_list") == Some(true) {
+        return Ok(to_value(declarations)?);
+    }
+    to_value(declarations.join(", "))
+        .map_err(|e| tera::Error::msg(format!("Failed to convert string to Value: {}", e)))
+}
+
+/// Orders path parameters by the position of their `{name}` placeholder in `path`.
+pub fn sort_by_path_position(params: &mut [&Value], path: &str) {
+    params.sort_by_key(|param| {
+        let placeholder = format!("{{{}}}", param["name"].as_str().unwrap_or_default());
+        path.find(&placeholder).unwrap_or(usize::MAX)
+    });
+}
+
+/// Returns the C++ literal for the schema `default` of `param`, if it declares one.
+fn default_of(param: &Value) -> Result<Option<String>> {
+    let default = parameter_default_filter(param, &HashMap::new())?;
+    Ok(default.as_str().filter(|d| !d.is_empty()).map(String::from))
+}
+
+fn declare(param: &Value) -> String {
+    format!(
+        "{} {}",
+        param["ue_type"].as_str().unwrap_or_default(),
+        param["identifier"].as_str().unwrap_or_default()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn params_of(operation: Value, path: &str) -> String {
+        let mut args = HashMap::new();
+        args.insert("path".to_string(), json!(path));
+        let result = function_params_filter(&operation, &args).unwrap();
+        result.as_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_function_params_get_with_path_and_query() {
+        let operation = json!({
+            "parameters": [
+                { "name": "limit", "in": "query", "schema": { "type": "integer", "default": 20 } },
+                { "name": "item_id", "in": "path", "schema": { "type": "integer" } },
+                { "name": "sort", "in": "query", "schema": { "type": "string" } },
+                { "name": "user-id", "in": "path", "schema": { "type": "string" } },
+                { "name": "class", "in": "query", "required": true, "schema": { "type": "string" } }
+            ]
+        });
+
+        assert_eq!(
+            params_of(operation, "/users/{user-id}/items/{item_id}"),
+            "FString user_id, int32 item_id, FString class_, FString sort, int32 limit = 20"
+        );
+    }
+
+    #[test]
+    fn test_function_params_post_with_body() {
+        let operation = json!({
+            "parameters": [
+                { "name": "dry", "in": "query", "schema": { "type": "boolean", "default": true } },
+                { "name": "id", "in": "path", "required": true, "schema": { "type": "string" } }
+            ],
+            "requestBody": {
+                "content": {
+                    "application/json": { "schema": { "$ref": "#/components/schemas/Character" } }
+                }
+            }
+        });
+
+        assert_eq!(
+            params_of(operation, "/characters/{id}"),
+            "FString id, const FCharacter& RequestBody, bool dry = true"
+        );
+    }
+
+    #[test]
+    fn test_function_params_string_default() {
+        let operation = json!({
+            "parameters": [
+                {
+                    "name": "shard",
+                    "in": "query",
+                    "schema": { "type": "string", "default": "CN-1" }
+                }
+            ]
+        });
+
+        assert_eq!(params_of(operation, "/servers"), "FString shard = TEXT(\"CN-1\")");
+    }
+
+    #[test]
+    fn test_function_params_integer_default() {
+        let operation = json!({
+            "parameters": [
+                { "name": "page", "in": "query", "schema": { "type": "integer", "default": 1 } }
+            ]
+        });
+
+        assert_eq!(params_of(operation, "/servers"), "int32 page = 1");
+    }
+
+    #[test]
+    fn test_function_params_optional_without_default() {
+        let operation = json!({
+            "parameters": [
+                { "name": "cursor", "in": "query", "schema": { "type": "string" } },
+                { "name": "region", "in": "header", "required": false, "schema": {} }
+            ]
+        });
+
+        let params = params_of(operation, "/servers");
+        assert!(!params.contains('='), "{}", params);
+        assert!(params.starts_with("FString cursor, "), "{}", params);
+    }
+
+    #[test]
+    fn test_function_params_options() {
+        let operation = json!({
+            "parameters": [
+                { "name": "page", "in": "query", "schema": { "type": "integer", "default": 1 } },
+                { "name": "shard", "in": "query", "schema": { "type": "string", "default": "CN-1" } },
+                { "name": "id", "in": "path", "required": true, "schema": { "type": "string" } }
+            ],
+            "requestBody": {
+                "content": { "application/json": { "schema": { "type": "string" } } }
+            }
+        });
+        let params_with = |options: Value| {
+            let mut args: HashMap<String, Value> = serde_json::from_value(options).unwrap();
+            args.insert("path".to_string(), json!("/servers/{id}"));
+            function_params_filter(&operation, &args).unwrap()
+        };
+
+        assert_eq!(
+            params_with(json!({ "exclude": "page", "defaults": false })),
+            "FString id, const FString& RequestBody, FString shard"
+        );
+        assert_eq!(
+            params_with(json!({ "request_body": false, "as_list": true })),
+            json!(["FString id", "int32 page = 1", "FString shard = TEXT(\"CN-1\")"])
+        );
+        assert_eq!(
+            params_with(json!({ "request_body": false, "trailing": "bool& bSuccess" })),
+            "FString id, bool& bSuccess, int32 page = 1, FString shard = TEXT(\"CN-1\")"
+        );
+        // Only a query parameter is excluded
+        assert!(params_with(json!({ "exclude": "id" })).as_str().unwrap().starts_with("FString id"));
+    }
+
+    #[test]
+    fn test_function_params_no_params() {
+        assert_eq!(params_of(json!({ "responses": {} }), "/health"), "");
+    }
+
+    #[test]
+    fn test_function_params_missing_path() {
+        let result = function_params_filter(&json!({}), &HashMap::new());
+
+        assert!(result.unwrap_err().to_string().contains("'path' argument"));
+    }
+}
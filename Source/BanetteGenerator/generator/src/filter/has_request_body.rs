@@ -0,0 +1,76 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use std::collections::HashMap;
+use tera::{to_value, Result, Value};
+
+/// Tera filter to check if an operation has a usable request body.
+///
+/// True when the operation's `requestBody` is an object with a non-empty `content` map,
+/// whatever its media types. A missing, `null`, or empty body is false, so the result can
+/// guard `request_body_schema`.
+///
+/// Usage in the template: {% if operation | has_request_body %}
+pub fn has_request_body_filter(value: &Value, _args: &HashMap<String, Value>) -> Result<Value> {
+    let operation = value.as_object().ok_or_else(|| {
+        tera::Error::msg("has_request_body filter expects an operation object as input.")
+    })?;
+
+    let has_body = operation
+        .get("requestBody")
+        .and_then(|body| body.get("content"))
+        .and_then(|content| content.as_object())
+        .is_some_and(|content| !content.is_empty());
+
+    to_value(has_body)
+        .map_err(|e| tera::Error::msg(format!("Failed to convert bool to Value: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn has_body(operation: Value) -> bool {
+        let result = has_request_body_filter(&operation, &HashMap::new()).unwrap();
+        result.as_bool().unwrap()
+    }
+
+    #[test]
+    fn test_has_request_body_json() {
+        assert!(has_body(json!({
+            "requestBody": {
+                "content": { "application/json": { "schema": { "type": "object" } } }
+            }
+        })));
+    }
+
+    #[test]
+    fn test_has_request_body_non_json() {
+        assert!(has_body(json!({
+            "requestBody": {
+                "content": { "application/octet-stream": {} }
+            }
+        })));
+    }
+
+    #[test]
+    fn test_has_request_body_null_or_empty() {
+        assert!(!has_body(json!({ "requestBody": null })));
+        assert!(!has_body(json!({ "requestBody": { "content": {} } })));
+        assert!(!has_body(json!({ "requestBody": { "description": "No content" } })));
+    }
+
+    #[test]
+    fn test_has_request_body_absent() {
+        assert!(!has_body(json!({ "responses": {} })));
+    }
+
+    #[test]
+    fn test_has_request_body_invalid_input() {
+        let result = has_request_body_filter(&json!("post"), &HashMap::new());
+
+        assert!(result.unwrap_err().to_string().contains("expects an operation object"));
+    }
+}
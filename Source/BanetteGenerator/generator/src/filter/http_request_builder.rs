@@ -2,6 +2,8 @@
  * Copyright 2019-Present tarnishablec. All Rights Reserved.
  */
 
+use crate::filter::escape::escape_cpp_string;
+use crate::filter::sanitize_identifier::sanitize_identifier;
 use std::collections::HashMap;
 use tera::{to_value, Result, Value};
 
@@ -16,6 +18,15 @@ use tera::{to_value, Result, Value};
 /// - `.With_ContentType(...)` - Content-Type (from requestBody.content)
 /// - `.With_Body(...)` - Request body using ToBinary(RequestBody)
 ///
+/// An optional `base_url` argument holds a C++ `FString` expression, such as a settings
+/// property, that is prepended to the URL.
+///
+/// Each parameter is read from the variable its name gives after `sanitize_identifier`, as
+/// the function parameters are declared, while the URL keeps the original name. An optional
+/// `prefix` argument reads the parameters and body from the members of an object instead:
+/// with `prefix="Request."` the URL takes `Request.<identifier>` for each parameter, and
+/// the body is `Request.RequestBody`.
+///
 /// Usage in template:
 /// ```tera
 /// {{ path | http_request_builder(method=method, parameters=operation.parameters, request_body=operation.requestBody) }}
@@ -53,8 +64,14 @@ pub fn http_request_builder_filter(value: &Value, args: &HashMap<String, Value>)
     // 7. Extract query parameters from the parameter array (where "in": "query")
     let query_params = extract_query_parameters(parameters);
 
-    // 8. Build the URL expression
-    let url_expr = build_url_expression(path, &path_params, &query_params);
+    // 8. Build the URL expression, prefixed with the base URL if given
+    let prefix = args.get("prefix").and_then(|v| v.as_str()).filter(|p| !p.is_empty());
+    let mut url_expr = build_url_expression(path, &path_params, &query_params, prefix);
+    if let Some(base_url) = args.get("base_url").and_then(|v| v.as_str())
+        && !base_url.is_empty()
+    {
+        url_expr = format!("{} + {}", base_url, url_expr);
+    }
 
     // 9. Build the chain calls
     let mut chain_calls = Vec::new();
@@ -75,7 +92,7 @@ pub fn http_request_builder_filter(value: &Value, args: &HashMap<String, Value>)
                 escape_cpp_string(&content_type)
             ));
         }
-        chain_calls.push(".With_Body(ToBytes(RequestBody))".to_string());
+        chain_calls.push(format!(".With_Body(ToBytes({}RequestBody))", prefix.unwrap_or("")));
     }
 
     // Join all chain calls
@@ -103,13 +120,6 @@ fn convert_to_http_method(method: &str) -> Result<&'static str> {
     }
 }
 
-/// Escape special characters in a string for use in a C++ string literal.
-///
-/// Escapes backslashes and double quotes to prevent code injection.
-fn escape_cpp_string(s: &str) -> String {
-    s.replace('\\', "\\\\").replace('"', "\\\"")
-}
-
 /// Extract path parameters from the OpenAPI parameters array.
 ///
 /// Path parameters have `"in": "path"` in their definition.
@@ -157,8 +167,14 @@ fn extract_query_parameters(parameters: Option<&Vec<Value>>) -> Vec<String> {
 /// Build the URL expression for the FHttpRequest.
 ///
 /// If there are path parameters or query parameters, use FString::Format with
-/// FStringFormatNamedArguments. Otherwise, uses a simple TEXT() macro.
-fn build_url_expression(path: &str, path_params: &[String], query_params: &[String]) -> String {
+/// FStringFormatNamedArguments. Otherwise, uses a simple TEXT() macro. The argument values
+/// are the sanitized parameter names, behind `prefix` if given.
+fn build_url_expression(
+    path: &str,
+    path_params: &[String],
+    query_params: &[String],
+    prefix: Option<&str>,
+) -> String {
     let escaped_path = escape_cpp_string(path);
 
     // If no parameters, use simple TEXT() macro
@@ -182,7 +198,10 @@ fn build_url_expression(path: &str, path_params: &[String], query_params: &[Stri
     // Build FStringFormatNamedArguments
     let args_entries: Vec<String> = all_params
         .iter()
-        .map(|name| format!("{{\"{}\", {}}}", name, name))
+        .map(|name| {
+            let prefix = prefix.unwrap_or_default();
+            format!("{{\"{}\", {}{}}}", name, prefix, sanitize_identifier(name))
+        })
         .collect();
     let format_args = format!("FStringFormatNamedArguments{{{}}}", args_entries.join(", "));
 
@@ -629,4 +648,62 @@ mod tests {
             ".With_Url(TEXT(\"/v1/characters\")).With_Method(EHttpMethod::Get)"
         );
     }
+
+    // Test 25: Base URL expression is prepended to the URL
+    #[test]
+    fn test_base_url_prefix() {
+        let path = json!("/v1/users/{id}");
+        let parameters = json!([{"in": "path", "name": "id", "required": true}]);
+        let mut args = create_full_args("get", Some(parameters), None);
+        args.insert("base_url".to_string(), json!("Settings->BaseUrl"));
+
+        let result = http_request_builder_filter(&path, &args).unwrap();
+        assert!(result.as_str().unwrap().contains(
+            ".With_Url(Settings->BaseUrl + FString::Format(TEXT(\"/v1/users/{id}\"), \
+             FStringFormatNamedArguments{{\"id\", id}}))"
+        ));
+
+        // An empty base URL leaves the URL untouched
+        args.insert("base_url".to_string(), json!(""));
+        let result = http_request_builder_filter(&path, &args).unwrap();
+        assert!(result.as_str().unwrap().contains(".With_Url(FString::Format("));
+    }
+
+    // Test 26: Parameters and body are read from a request struct
+    #[test]
+    fn test_request_struct_prefix() {
+        let path = json!("/v1/users/{user-id}");
+        let parameters = json!([
+            {"in": "path", "name": "user-id", "required": true},
+            {"in": "query", "name": "class"}
+        ]);
+        let request_body = json!({"content": {"application/json": {"schema": {}}}});
+        let mut args = create_full_args("put", Some(parameters), Some(request_body));
+        args.insert("prefix".to_string(), json!("Request."));
+
+        let result = http_request_builder_filter(&path, &args).unwrap();
+        let result = result.as_str().unwrap();
+        assert!(result.contains(
+            "FStringFormatNamedArguments{{\"user-id\", Request.user_id}, \
+             {\"class\", Request.class_}}"
+        ));
+        assert!(result.ends_with(".With_Body(ToBytes(Request.RequestBody))"), "{}", result);
+    }
+
+    // Test 27: Parameters are read from their sanitized variables, keyed by their names
+    #[test]
+    fn test_sanitized_parameter_variables() {
+        let path = json!("/v1/users/{user-id}");
+        let parameters = json!([
+            {"in": "path", "name": "user-id", "required": true},
+            {"in": "query", "name": "class"}
+        ]);
+        let args = create_full_args("get", Some(parameters), None);
+
+        let result = http_request_builder_filter(&path, &args).unwrap();
+        assert!(result.as_str().unwrap().contains(
+            "TEXT(\"/v1/users/{user-id}?class={class}\"), \
+             FStringFormatNamedArguments{{\"user-id\", user_id}, {\"class\", class_}}"
+        ));
+    }
 }
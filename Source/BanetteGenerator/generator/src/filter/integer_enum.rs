@@ -0,0 +1,271 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use crate::filter::sanitize_identifier::sanitize_identifier;
+use crate::filter::to_ue_type::{get_effective_type, schema_ref, ENUM_REF_KEY, UE_TYPE_KEY};
+use serde_json::json;
+use std::collections::{HashMap, HashSet};
+use tera::{Result, Value};
+
+/// Extensions that name the variants of an integer enum, in order of preference.
+const VARNAME_EXTENSIONS: &[&str] = &["x-enum-varnames", "x-enumNames"];
+
+/// Whether `schema` is an `integer` schema whose `enum` holds only integers (and `null`).
+/// A `const` counts as an `enum` of its one value.
+pub fn is_integer_enum(schema: &Value) -> bool {
+    let Some(values) = enum_values(schema) else {
+        return false;
+    };
+
+    get_effective_type(schema) == "integer"
+        && values.iter().any(|v| !v.is_null())
+        && values.iter().all(|v| v.is_null() || v.as_i64().is_some())
+}
+
+/// The integer type an integer enum schema is stored in: `uint8` if every value fits,
+/// else `int32`. `None` when `schema` is not an integer enum (see [`is_integer_enum`]).
+pub fn integer_enum_storage(schema: &Value) -> Option<&'static str> {
+    if !is_integer_enum(schema) {
+        return None;
+    }
+
+    let values = enum_values(schema).into_iter().flatten().filter_map(Value::as_i64);
+    Some(if values.into_iter().all(|v| u8::try_from(v).is_ok()) { "uint8" } else { "int32" })
+}
+
+/// Tera filter to describe an integer enum schema for a `UENUM`.
+///
+/// Returns `false` for anything that is not an integer enum (see [`is_integer_enum`]),
+/// otherwise an object with:
+/// - `underlying`: the [`integer_enum_storage`] type, `uint8` being required for
+///   `BlueprintType`
+/// - `entries`: `{ name, value }` per enum value, in spec order
+///
+/// A `const` integer schema is described as an enum with that one value.
+///
+/// Entry names come from `x-enum-varnames` or `x-enumNames` when present, sanitized into
+/// C++ identifiers. A value without a usable name (missing, empty, or duplicate) is named
+/// `Value<n>`, e.g. `Value2` or `ValueMinus1`.
+///
+/// Usage in the template: {% set int_enum = schema | integer_enum %}
+pub fn integer_enum_filter(value: &Value, _args: &HashMap<String, Value>) -> Result<Value> {
+    if !value.is_object() {
+        return Err(tera::Error::msg("integer_enum filter expects a schema object as input."));
+    }
+    if !is_integer_enum(value) {
+        return Ok(Value::Bool(false));
+    }
+
+    let entries: Vec<Value> = integer_enum_entries(value)
+        .into_iter()
+        .map(|(name, number)| json!({ "name": name, "value": number }))
+        .collect();
+    Ok(json!({ "underlying": integer_enum_storage(value), "entries": entries }))
+}
+
+/// The enumerator an integer enum's holders start out as, when `0` is not one of its
+/// values and so not a valid default: the first entry's name, as the
+/// [`integer_enum_filter`] names it. `None` when `0` is a value or `schema` is not an
+/// integer enum.
+pub fn integer_enum_default(schema: &Value) -> Option<String> {
+    if !is_integer_enum(schema) {
+        return None;
+    }
+    let entries = integer_enum_entries(schema);
+    if entries.iter().any(|(_, number)| *number == 0) {
+        return None;
+    }
+    entries.into_iter().next().map(|(name, _)| name)
+}
+
+/// `(name, value)` of each entry of an integer enum, in spec order (see
+/// [`integer_enum_filter`] for the naming).
+fn integer_enum_entries(schema: &Value) -> Vec<(String, i64)> {
+    let values: Vec<i64> =
+        enum_values(schema).into_iter().flatten().filter_map(Value::as_i64).collect();
+    let varnames = VARNAME_EXTENSIONS
+        .iter()
+        .find_map(|key| schema.get(*key).and_then(|v| v.as_array()));
+
+    let mut used = HashSet::new();
+    let mut entries = Vec::with_capacity(values.len());
+    for (index, &number) in values.iter().enumerate() {
+        let given = varnames
+            .and_then(|names| names.get(index))
+            .and_then(|name| name.as_str())
+            .filter(|name| !name.trim().is_empty())
+            .map(sanitize_identifier)
+            .filter(|name| !used.contains(name));
+        let name = given.unwrap_or_else(|| synthesized_name(number, &used));
+
+        used.insert(name.clone());
+        entries.push((name, number));
+    }
+    entries
+}
+
+/// Tera filter to name the `UENUM` behind a `$ref` to an integer enum, which
+/// [`mark_enum_refs`](crate::openapi::enums::mark_enum_refs) stores as its number (see
+/// [`integer_enum_storage`]).
+///
+/// Returns `E<Name>` for such a `$ref` and `false` for any other schema, so a struct field
+/// holding the number can offer a typed accessor.
+///
+/// Usage in the template: {% set enum_type = prop_schema | integer_enum_ref %}
+pub fn integer_enum_ref_filter(value: &Value, _args: &HashMap<String, Value>) -> Result<Value> {
+    let stored_as_number =
+        value.get(ENUM_REF_KEY) == Some(&Value::Bool(true)) && value.get(UE_TYPE_KEY).is_some();
+    let name = schema_ref(value)
+        .filter(|_| stored_as_number)
+        .and_then(|ref_path| ref_path.rsplit('/').next());
+
+    Ok(name.map_or(Value::Bool(false), |name| Value::String(format!("E{}", name))))
+}
+
+/// The values of the schema's `enum`, or its `const` as the only value.
+fn enum_values(schema: &Value) -> Option<Vec<&Value>> {
+    match (schema.get("enum"), schema.get("const")) {
+        (Some(Value::Array(values)), _) => Some(values.iter().collect()),
+        (_, Some(value)) => Some(vec![value]),
+        _ => None,
+    }
+}
+
+/// `Value<n>` for `number`, with `_` suffixes until it is not in `used`.
+fn synthesized_name(number: i64, used: &HashSet<String>) -> String {
+    let mut name = if number < 0 {
+        format!("ValueMinus{}", number.unsigned_abs())
+    } else {
+        format!("Value{}", number)
+    };
+    while used.contains(&name) {
+        name.push('_');
+    }
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn describe(schema: Value) -> Value {
+        integer_enum_filter(&schema, &HashMap::new()).unwrap()
+    }
+
+    #[test]
+    fn test_integer_enum_ref() {
+        let describe_ref =
+            |schema: Value| integer_enum_ref_filter(&schema, &HashMap::new()).unwrap();
+
+        let stored = json!({
+            "$ref": "#/components/schemas/Priority",
+            ENUM_REF_KEY: true,
+            UE_TYPE_KEY: "uint8"
+        });
+        assert_eq!(describe_ref(stored), json!("EPriority"));
+        let flag = json!({ "$ref": "#/components/schemas/Power", ENUM_REF_KEY: true });
+        assert_eq!(describe_ref(flag), json!(false));
+        assert_eq!(describe_ref(json!({ "$ref": "#/components/schemas/User" })), json!(false));
+        assert_eq!(describe_ref(json!(true)), json!(false));
+    }
+
+    #[test]
+    fn test_integer_enum_const() {
+        let result = describe(json!({ "const": 2, "x-enum-varnames": ["Current"] }));
+
+        assert_eq!(
+            result,
+            json!({ "underlying": "uint8", "entries": [{ "name": "Current", "value": 2 }] })
+        );
+        assert_eq!(describe(json!({ "const": "fixed" })), json!(false));
+    }
+
+    #[test]
+    fn test_integer_enum_default() {
+        let default_of = |schema: Value| integer_enum_default(&schema);
+
+        let version = json!({ "const": 2, "x-enum-varnames": ["Current"] });
+        assert_eq!(default_of(version).unwrap(), "Current");
+        let code = json!({ "type": "integer", "enum": [-1, 300] });
+        assert_eq!(default_of(code).unwrap(), "ValueMinus1");
+        assert_eq!(default_of(json!({ "type": "integer", "enum": [1, 0] })), None);
+        assert_eq!(default_of(json!({ "type": "string", "enum": ["a"] })), None);
+    }
+
+    #[test]
+    fn test_integer_enum_with_varnames() {
+        let result = describe(json!({
+            "type": "integer",
+            "enum": [0, 1, 2],
+            "x-enum-varnames": ["None", "Low", "High"]
+        }));
+
+        assert_eq!(
+            result,
+            json!({
+                "underlying": "uint8",
+                "entries": [
+                    { "name": "None", "value": 0 },
+                    { "name": "Low", "value": 1 },
+                    { "name": "High", "value": 2 }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_integer_enum_with_enum_names() {
+        let result = describe(json!({
+            "type": ["integer", "null"],
+            "enum": [10, -1, null],
+            "x-enumNames": ["Ten", "not set"]
+        }));
+
+        assert_eq!(result["underlying"], "int32");
+        assert_eq!(
+            result["entries"],
+            json!([{ "name": "Ten", "value": 10 }, { "name": "not_set", "value": -1 }])
+        );
+    }
+
+    #[test]
+    fn test_integer_enum_without_varnames() {
+        let result = describe(json!({ "type": "integer", "enum": [1, 2, -3] }));
+
+        assert_eq!(
+            result["entries"],
+            json!([
+                { "name": "Value1", "value": 1 },
+                { "name": "Value2", "value": 2 },
+                { "name": "ValueMinus3", "value": -3 }
+            ])
+        );
+    }
+
+    #[test]
+    fn test_integer_enum_partial_varnames() {
+        let result = describe(json!({
+            "type": "integer",
+            "enum": [0, 1, 2, 3],
+            "x-enum-varnames": ["Off", "", "Off"]
+        }));
+
+        assert_eq!(
+            result["entries"],
+            json!([
+                { "name": "Off", "value": 0 },
+                { "name": "Value1", "value": 1 },
+                { "name": "Value2", "value": 2 },
+                { "name": "Value3", "value": 3 }
+            ])
+        );
+    }
+
+    #[test]
+    fn test_integer_enum_not_an_integer_enum() {
+        assert_eq!(describe(json!({ "type": "string", "enum": ["a", "b"] })), json!(false));
+        assert_eq!(describe(json!({ "type": "integer" })), json!(false));
+        assert_eq!(describe(json!({ "type": "integer", "enum": [1.5] })), json!(false));
+    }
+}
@@ -0,0 +1,95 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use crate::filter::escape::escape_cpp_string;
+use std::collections::HashMap;
+use tera::{to_value, Result, Value};
+
+/// Message used when a deprecated operation or property has no description.
+const DEFAULT_DEPRECATION_MESSAGE: &str = "Deprecated by the API specification.";
+
+/// Tera filter to check if an operation or schema property is marked `deprecated`.
+///
+/// Pair it with `deprecation_message` to emit `DeprecatedFunction` metadata or a
+/// `UE_DEPRECATED(...)` marker.
+///
+/// Usage in the template: {% if operation | is_deprecated %}
+pub fn is_deprecated_filter(value: &Value, _args: &HashMap<String, Value>) -> Result<Value> {
+    let object = value.as_object().ok_or_else(|| {
+        tera::Error::msg("is_deprecated filter expects an operation or schema object as input.")
+    })?;
+
+    let deprecated = object.get("deprecated").and_then(|v| v.as_bool()).unwrap_or(false);
+
+    to_value(deprecated)
+        .map_err(|e| tera::Error::msg(format!("Failed to convert bool to Value: {}", e)))
+}
+
+/// Tera filter to build the deprecation message for an operation or schema property.
+///
+/// Uses the object's `description` collapsed onto one line, or a default reason, escaped
+/// for use inside a C++ string literal.
+///
+/// Usage in the template: DeprecationMessage="{{ operation | deprecation_message }}"
+pub fn deprecation_message_filter(value: &Value, _args: &HashMap<String, Value>) -> Result<Value> {
+    let object = value.as_object().ok_or_else(|| {
+        tera::Error::msg(
+            "deprecation_message filter expects an operation or schema object as input.",
+        )
+    })?;
+
+    let description = object
+        .get("description")
+        .and_then(|v| v.as_str())
+        .map(|d| d.split_whitespace().collect::<Vec<_>>().join(" "))
+        .filter(|d| !d.is_empty());
+    let message = description.as_deref().unwrap_or(DEFAULT_DEPRECATION_MESSAGE);
+
+    to_value(escape_cpp_string(message))
+        .map_err(|e| tera::Error::msg(format!("Failed to convert string to Value: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn is_deprecated(value: Value) -> bool {
+        is_deprecated_filter(&value, &HashMap::new()).unwrap().as_bool().unwrap()
+    }
+
+    #[test]
+    fn test_is_deprecated_operation() {
+        assert!(is_deprecated(json!({ "deprecated": true, "responses": {} })));
+        assert!(!is_deprecated(json!({ "deprecated": false, "responses": {} })));
+        assert!(!is_deprecated(json!({ "responses": {} })));
+    }
+
+    #[test]
+    fn test_is_deprecated_property() {
+        assert!(is_deprecated(json!({ "type": "string", "deprecated": true })));
+        assert!(!is_deprecated(json!({ "type": "string" })));
+    }
+
+    #[test]
+    fn test_is_deprecated_invalid_input() {
+        let result = is_deprecated_filter(&json!("deprecated"), &HashMap::new());
+
+        assert!(result.unwrap_err().to_string().contains("expects an operation or schema"));
+    }
+
+    #[test]
+    fn test_deprecation_message() {
+        let operation = json!({
+            "deprecated": true,
+            "description": "Use \"GET /v2/users\"\n  instead."
+        });
+        let result = deprecation_message_filter(&operation, &HashMap::new()).unwrap();
+        assert_eq!(result.as_str().unwrap(), "Use \\\"GET /v2/users\\\" instead.");
+
+        let property = json!({ "type": "string", "deprecated": true });
+        let result = deprecation_message_filter(&property, &HashMap::new()).unwrap();
+        assert_eq!(result.as_str().unwrap(), DEFAULT_DEPRECATION_MESSAGE);
+    }
+}
@@ -0,0 +1,90 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use crate::filter::escape::escape_cpp_string;
+use crate::filter::sanitize_identifier::sanitize_identifier;
+use std::collections::HashMap;
+use tera::{to_value, Result, Value};
+
+/// Tera filter to record a property's JSON key in UPROPERTY metadata.
+///
+/// Takes the original JSON key as input and returns `meta=(JsonName="user_id")` when the
+/// C++ field name differs from it, so the marshaling layer can write the key as it appears
+/// on the wire. The field name is the optional `field` argument, or else the key passed
+/// through `sanitize_identifier`. Yields an empty string when the names match. With
+/// `wrap=false` only the `JsonName="..."` entry is returned.
+///
+/// Usage in the template: UPROPERTY(EditAnywhere, {{ prop_name | json_name_meta }})
+pub fn json_name_meta_filter(value: &Value, args: &HashMap<String, Value>) -> Result<Value> {
+    // 1. Check if the input is a JSON key
+    let key = value.as_str().ok_or_else(|| {
+        tera::Error::msg("json_name_meta filter expects a property name as input string.")
+    })?;
+
+    // 2. Compare it with the C++ field name
+    let field = match args.get("field") {
+        Some(field) => field.as_str().map(String::from).ok_or_else(|| {
+            tera::Error::msg("json_name_meta filter expects 'field' to be a string.")
+        })?,
+        None => sanitize_identifier(key),
+    };
+    let wrap = args.get("wrap").and_then(|v| v.as_bool()).unwrap_or(true);
+    let meta = if field == key {
+        String::new()
+    } else if wrap {
+        format!("meta=(JsonName=\"{}\")", escape_cpp_string(key))
+    } else {
+        format!("JsonName=\"{}\"", escape_cpp_string(key))
+    };
+
+    to_value(meta)
+        .map_err(|e| tera::Error::msg(format!("Failed to convert string to Value: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn meta_of(key: &str, field: Option<&str>) -> String {
+        let mut args = HashMap::new();
+        if let Some(field) = field {
+            args.insert("field".to_string(), json!(field));
+        }
+        let result = json_name_meta_filter(&json!(key), &args).unwrap();
+        result.as_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_json_name_meta_snake_case_key() {
+        assert_eq!(meta_of("user_id", Some("UserId")), "meta=(JsonName=\"user_id\")");
+    }
+
+    #[test]
+    fn test_json_name_meta_sanitized_key() {
+        assert_eq!(meta_of("x-request-id", None), "meta=(JsonName=\"x-request-id\")");
+        assert_eq!(meta_of("class", None), "meta=(JsonName=\"class\")");
+    }
+
+    #[test]
+    fn test_json_name_meta_valid_identifier() {
+        assert_eq!(meta_of("user_id", None), "");
+        assert_eq!(meta_of("UserId", Some("UserId")), "");
+    }
+
+    #[test]
+    fn test_json_name_meta_unwrapped() {
+        let args = HashMap::from([("wrap".to_string(), json!(false))]);
+        let result = json_name_meta_filter(&json!("user-id"), &args).unwrap();
+
+        assert_eq!(result, json!("JsonName=\"user-id\""));
+    }
+
+    #[test]
+    fn test_json_name_meta_invalid_input() {
+        let result = json_name_meta_filter(&json!(1), &HashMap::new());
+
+        assert!(result.unwrap_err().to_string().contains("expects a property name"));
+    }
+}
@@ -0,0 +1,126 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use crate::filter::to_snake_case::split_words;
+use std::collections::HashMap;
+use tera::{to_value, Result, Value};
+
+/// Short suffixes of the common media types.
+const KNOWN_SUFFIXES: &[(&str, &str)] = &[
+    ("application/json", "Json"),
+    ("application/xml", "Xml"),
+    ("text/plain", "Text"),
+    ("text/html", "Html"),
+    ("text/csv", "Csv"),
+    ("application/octet-stream", "Binary"),
+    ("application/x-www-form-urlencoded", "Form"),
+    ("multipart/form-data", "Multipart"),
+];
+
+/// Tera filter to turn a media type into a function name suffix, to tell apart the
+/// functions generated for the media types of one operation.
+///
+/// Parameters such as `; charset=utf-8` and case are ignored. Common types get a short
+/// suffix (`application/json` -> `Json`, `application/xml` -> `Xml`, `text/plain` ->
+/// `Text`, ...); any other type is named after the PascalCased words of its subtype, so
+/// `application/problem+json` -> `ProblemJson` and `application/vnd.ms-excel` ->
+/// `VndMsExcel`, or of its type for a wildcard subtype (`image/*` -> `Image`, `*/*` ->
+/// `Any`). The suffix only ever contains ASCII letters and digits.
+///
+/// Usage in the template: {{ func_name }}_{{ media_type | media_type_suffix }}
+pub fn media_type_suffix_filter(value: &Value, _args: &HashMap<String, Value>) -> Result<Value> {
+    let media_type = value
+        .as_str()
+        .map(|media_type| media_type.split(';').next().unwrap_or_default().trim())
+        .filter(|media_type| !media_type.is_empty())
+        .ok_or_else(|| {
+            tera::Error::msg("media_type_suffix filter expects a media type string as input.")
+        })?;
+
+    to_value(media_type_suffix(media_type))
+        .map_err(|e| tera::Error::msg(format!("Failed to convert string to Value: {}", e)))
+}
+
+/// Suffix of a media type without parameters, see [`media_type_suffix_filter`].
+fn media_type_suffix(media_type: &str) -> String {
+    let media_type = media_type.to_ascii_lowercase();
+    if let Some((_, suffix)) = KNOWN_SUFFIXES.iter().find(|(known, _)| *known == media_type) {
+        return suffix.to_string();
+    }
+
+    let (main_type, subtype) = media_type.split_once('/').unwrap_or((&media_type, ""));
+    let words = |part: &str| {
+        let ascii: String = part
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { ' ' })
+            .collect();
+        split_words(&ascii)
+            .iter()
+            .map(|word| word[..1].to_uppercase() + &word[1..])
+            .collect::<String>()
+    };
+
+    [words(subtype), words(main_type)]
+        .into_iter()
+        .find(|suffix| !suffix.is_empty())
+        .unwrap_or_else(|| "Any".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter::path_to_func_name::path_to_func_name_filter;
+    use crate::filter::tests::create_method_args;
+    use serde_json::json;
+
+    fn suffix_of(media_type: &str) -> String {
+        let result = media_type_suffix_filter(&json!(media_type), &HashMap::new()).unwrap();
+        result.as_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_media_type_suffix_common_types() {
+        assert_eq!(suffix_of("application/json"), "Json");
+        assert_eq!(suffix_of("application/xml"), "Xml");
+        assert_eq!(suffix_of("text/plain"), "Text");
+        assert_eq!(suffix_of("application/octet-stream"), "Binary");
+        assert_eq!(suffix_of("Application/JSON; charset=utf-8"), "Json");
+    }
+
+    #[test]
+    fn test_media_type_suffix_structured_syntax_suffix() {
+        assert_eq!(suffix_of("application/problem+json"), "ProblemJson");
+        assert_eq!(suffix_of("application/vnd.api+json"), "VndApiJson");
+        assert_eq!(suffix_of("application/atom+xml"), "AtomXml");
+    }
+
+    #[test]
+    fn test_media_type_suffix_vendor_type_sanitized() {
+        assert_eq!(suffix_of("application/vnd.ms-excel"), "VndMsExcel");
+        assert_eq!(suffix_of("application/x.acme~report_v2"), "XAcmeReportV2");
+        assert_eq!(suffix_of("video/3gpp"), "3gpp");
+        assert_eq!(suffix_of("image/*"), "Image");
+        assert_eq!(suffix_of("*/*"), "Any");
+    }
+
+    #[test]
+    fn test_media_type_suffix_composes_with_func_name() {
+        let args = create_method_args("get");
+        let func_name = path_to_func_name_filter(&json!("/reports/{id}"), &args).unwrap();
+        let names: Vec<String> = ["application/json", "text/csv"]
+            .iter()
+            .map(|media_type| format!("{}_{}", func_name.as_str().unwrap(), suffix_of(media_type)))
+            .collect();
+
+        assert_eq!(names, ["GET_Reports_By_Id_Json", "GET_Reports_By_Id_Csv"]);
+    }
+
+    #[test]
+    fn test_media_type_suffix_invalid_input() {
+        for input in [json!(1), json!(""), json!("; charset=utf-8")] {
+            let result = media_type_suffix_filter(&input, &HashMap::new());
+            assert!(result.unwrap_err().to_string().contains("expects a media type string"));
+        }
+    }
+}
@@ -0,0 +1,74 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use std::collections::HashMap;
+use tera::{to_value, Result, Value};
+
+/// Tera filter to list the media types a requestBody or response declares.
+///
+/// Returns the keys of `content` sorted alphabetically, so the output is the same however
+/// the spec orders them, or an empty array when there is no `content`.
+///
+/// Usage in the template: {% for media_type in operation.requestBody | media_types %}
+pub fn media_types_filter(value: &Value, _args: &HashMap<String, Value>) -> Result<Value> {
+    let object = value.as_object().ok_or_else(|| {
+        tera::Error::msg("media_types filter expects a requestBody or response object as input.")
+    })?;
+
+    let mut media_types: Vec<&String> = object
+        .get("content")
+        .and_then(|content| content.as_object())
+        .map(|content| content.keys().collect())
+        .unwrap_or_default();
+    media_types.sort();
+
+    to_value(media_types)
+        .map_err(|e| tera::Error::msg(format!("Failed to convert array to Value: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_media_types_multiple() {
+        let body = json!({
+            "content": {
+                "text/plain": {},
+                "application/xml": {},
+                "application/json": {}
+            }
+        });
+        let result = media_types_filter(&body, &HashMap::new()).unwrap();
+
+        assert_eq!(result, json!(["application/json", "application/xml", "text/plain"]));
+    }
+
+    #[test]
+    fn test_media_types_single() {
+        let response = json!({
+            "description": "OK",
+            "content": { "application/json": { "schema": { "type": "string" } } }
+        });
+        let result = media_types_filter(&response, &HashMap::new()).unwrap();
+
+        assert_eq!(result, json!(["application/json"]));
+    }
+
+    #[test]
+    fn test_media_types_missing_content() {
+        let response = json!({ "description": "No Content" });
+        let result = media_types_filter(&response, &HashMap::new()).unwrap();
+
+        assert_eq!(result, json!([]));
+    }
+
+    #[test]
+    fn test_media_types_invalid_input() {
+        let result = media_types_filter(&json!(null), &HashMap::new());
+
+        assert!(result.unwrap_err().to_string().contains("expects a requestBody"));
+    }
+}
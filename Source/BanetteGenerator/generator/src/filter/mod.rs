@@ -2,13 +2,45 @@
  * Copyright 2019-Present tarnishablec. All Rights Reserved.
  */
 
+pub mod category_meta;
+pub mod component_includes;
+pub mod constraints_to_meta;
+pub mod default_value;
+pub mod description_to_doxygen;
+pub mod discriminated_union;
+pub mod display_name_meta;
+pub mod error_body_schema;
+pub mod escape;
+pub mod example_value;
+pub mod flag_enum;
+pub mod function_params;
+pub mod has_request_body;
 pub mod http_request_builder;
+pub mod integer_enum;
+pub mod is_deprecated;
 pub mod is_required;
+pub mod json_name_meta;
+pub mod media_type_suffix;
+pub mod media_types;
+pub mod pagination;
+pub mod pattern;
+pub mod parameter_default;
 pub mod path_to_func_name;
 pub mod request_body_schema;
+pub mod request_fields;
+pub mod required_params;
 pub mod response_body_schema;
+pub mod response_delegate;
+pub mod response_status_codes;
+pub mod sanitize_identifier;
+pub mod schema_kind;
+pub mod servers;
+pub mod tag_description;
 pub mod tags_to_pipe_separated;
+pub mod to_pascal_case;
+pub mod to_snake_case;
 pub mod to_ue_type;
+pub mod x_extension;
 
 use tera::Tera;
 
@@ -35,6 +67,92 @@ pub fn register_all_filters(tera: &mut Tera) {
         "f_http_request_builder",
         http_request_builder::http_request_builder_filter,
     );
+    tera.register_filter(
+        "f_description_to_doxygen",
+        description_to_doxygen::description_to_doxygen_filter,
+    );
+    tera.register_filter("f_default_value", default_value::default_value_filter);
+    tera.register_filter(
+        "f_sanitize_identifier",
+        sanitize_identifier::sanitize_identifier_filter,
+    );
+    tera.register_filter(
+        "f_identifier_mapping",
+        sanitize_identifier::identifier_mapping_filter,
+    );
+    tera.register_filter("f_is_deprecated", is_deprecated::is_deprecated_filter);
+    tera.register_filter(
+        "f_deprecation_message",
+        is_deprecated::deprecation_message_filter,
+    );
+    tera.register_filter(
+        "f_constraints_to_meta",
+        constraints_to_meta::constraints_to_meta_filter,
+    );
+    tera.register_filter("f_to_snake_case", to_snake_case::to_snake_case_filter);
+    tera.register_filter(
+        "f_has_request_body",
+        has_request_body::has_request_body_filter,
+    );
+    tera.register_filter("f_media_types", media_types::media_types_filter);
+    tera.register_filter("f_is_ref", schema_kind::is_ref_filter);
+    tera.register_filter("f_is_array", schema_kind::is_array_filter);
+    tera.register_filter("f_is_object", schema_kind::is_object_filter);
+    tera.register_filter("f_is_primitive", schema_kind::is_primitive_filter);
+    tera.register_filter("f_integer_enum", integer_enum::integer_enum_filter);
+    tera.register_filter("f_integer_enum_ref", integer_enum::integer_enum_ref_filter);
+    tera.register_filter("f_flag_enum", flag_enum::flag_enum_filter);
+    tera.register_filter(
+        "f_discriminated_union",
+        discriminated_union::discriminated_union_filter,
+    );
+    tera.register_filter(
+        "f_error_body_schema",
+        error_body_schema::error_body_schema_filter,
+    );
+    tera.register_filter(
+        "f_response_status_codes",
+        response_status_codes::response_status_codes_filter,
+    );
+    tera.register_filter("f_tag_description", tag_description::tag_description_filter);
+    tera.register_filter("f_required_params", required_params::required_params_filter);
+    tera.register_filter("f_function_params", function_params::function_params_filter);
+    tera.register_filter("f_servers", servers::servers_filter);
+    tera.register_filter("f_escape_cpp_string", escape::escape_cpp_string_filter);
+    tera.register_filter(
+        "f_component_includes",
+        component_includes::component_includes_filter,
+    );
+    tera.register_filter("f_component_order", component_includes::component_order_filter);
+    tera.register_filter(
+        "f_cycle_forward_declarations",
+        component_includes::cycle_forward_declarations_filter,
+    );
+    tera.register_filter("f_json_name_meta", json_name_meta::json_name_meta_filter);
+    tera.register_filter(
+        "f_display_name_meta",
+        display_name_meta::display_name_meta_filter,
+    );
+    tera.register_filter("f_category_meta", category_meta::category_meta_filter);
+    tera.register_filter(
+        "f_parameter_default",
+        parameter_default::parameter_default_filter,
+    );
+    tera.register_filter("f_to_pascal_case", to_pascal_case::to_pascal_case_filter);
+    tera.register_filter("f_pagination", pagination::pagination_filter);
+    tera.register_filter("f_example_value", example_value::example_value_filter);
+    tera.register_filter("f_x_extension", x_extension::x_extension_filter);
+    tera.register_filter(
+        "f_response_delegate",
+        response_delegate::response_delegate_filter,
+    );
+    tera.register_filter(
+        "f_media_type_suffix",
+        media_type_suffix::media_type_suffix_filter,
+    );
+    tera.register_filter("f_request_fields", request_fields::request_fields_filter);
+    tera.register_filter("f_pattern_comment", pattern::pattern_comment_filter);
+    tera.register_filter("f_pattern_constant", pattern::pattern_constant_filter);
 }
 
 #[cfg(test)]
@@ -0,0 +1,287 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use crate::filter::response_body_schema::response_body_schema;
+use crate::filter::sanitize_identifier::sanitize_identifier;
+use crate::filter::to_ue_type::{get_effective_type, schema_ref, to_ue_type_filter};
+use serde_json::json;
+use std::collections::HashMap;
+use tera::{Map, Result, Value};
+
+/// Page number and cursor parameters recognized when the filter gets no `params` argument.
+pub const DEFAULT_PAGINATION_PARAMS: &[&str] = &["page", "cursor", "page_token", "next_token"];
+
+/// Most pages an `_AllPages` helper fetches, so a server that never runs out of pages
+/// cannot keep it looping forever.
+pub const MAX_PAGES: u32 = 1000;
+
+/// Page size parameters, whose value tells a short (and so last) page apart from a full one.
+const PAGE_SIZE_PARAMS: &[&str] = &["limit", "per_page", "page_size"];
+
+/// Response fields carrying the cursor of the next page.
+const NEXT_CURSOR_FIELDS: &[&str] = &["next_cursor", "next_page_token", "next_token", "next"];
+
+/// Tera filter to recognize a paginated list operation.
+///
+/// An operation is paginated when it has a query parameter named like one of the `params`
+/// argument ([`DEFAULT_PAGINATION_PARAMS`] by default), and either:
+/// - the parameter is an integer page number and the success response body is an inline
+///   array, or
+/// - the parameter is a string cursor and the success response body references a
+///   component with an array field and a string next-cursor field, such as `nextCursor`.
+///
+/// Names match ignoring case, `_` and `-`, so `page_token` also matches `pageToken`. It
+/// then yields an object describing how to walk the pages, otherwise `false`:
+/// - `kind`: `"page"` or `"cursor"`
+/// - `param`: the page or cursor parameter's name
+/// - `type`: its C++ type
+/// - `start`: for pages, the first page, the parameter's integer `default` or else `1`
+/// - `limit`: for pages, the name of an integer page size parameter such as `limit`, whose
+///   value marks a shorter page as the last one, or `false`
+/// - `items`, `next`: for cursors, the identifiers of the array and next-cursor fields
+/// - `page_type`: the C++ type of one page's response body
+/// - `body_type`: the C++ type of all pages' items together
+/// - `max_pages`: the most pages to fetch, [`MAX_PAGES`]
+///
+/// The optional `components` argument (the spec's `components` object) resolves the
+/// cursor page's `$ref`; without it cursors are not recognized.
+///
+/// Usage in the template:
+/// {% set paging = operation | pagination(params=pagination_params, components=components) %}
+pub fn pagination_filter(value: &Value, args: &HashMap<String, Value>) -> Result<Value> {
+    // 1. Check the operation and the page parameter names
+    let operation = value.as_object().ok_or_else(|| {
+        tera::Error::msg("pagination filter expects an operation object as input.")
+    })?;
+    let names: Vec<&str> = match args.get("params") {
+        Some(Value::Array(names)) => names.iter().filter_map(|name| name.as_str()).collect(),
+        Some(_) => {
+            return Err(tera::Error::msg(
+                "pagination filter expects 'params' to be an array of names.",
+            ));
+        }
+        None => DEFAULT_PAGINATION_PARAMS.to_vec(),
+    };
+
+    // 2. Find an integer or string query parameter with one of those names
+    let find_query_param = |names: &[&str], types: &[&str]| {
+        let parameters = operation.get("parameters").and_then(|v| v.as_array());
+        parameters.into_iter().flatten().find(|param| {
+            let name = param.get("name").and_then(|v| v.as_str()).unwrap_or_default();
+            param.get("in").and_then(|v| v.as_str()) == Some("query")
+                && is_named(name, names)
+                && param.get("schema").is_some_and(|schema| {
+                    types.contains(&get_effective_type(schema).as_str())
+                })
+        })
+    };
+    let Some(param) = find_query_param(&names, &["integer", "string"]) else {
+        return Ok(Value::Bool(false));
+    };
+
+    // 3. Find the success response body
+    let responses = operation.get("responses").unwrap_or(&Value::Null);
+    let body = if responses.is_object() { response_body_schema(responses)? } else { None };
+    let Some(body) = body else {
+        return Ok(Value::Bool(false));
+    };
+    let type_args = HashMap::from([(
+        "components".to_string(),
+        args.get("components").cloned().unwrap_or(Value::Null),
+    )]);
+    let page_type = to_ue_type_filter(body, &type_args)?;
+    let schema = &param["schema"];
+    let param_type = to_ue_type_filter(schema, &HashMap::new())?;
+
+    // 4. A page number walks an array body, stopping at a short or empty page
+    if get_effective_type(schema) == "integer" {
+        if get_effective_type(body) != "array" {
+            return Ok(Value::Bool(false));
+        }
+        let limit = find_query_param(PAGE_SIZE_PARAMS, &["integer"])
+            .map_or(Value::Bool(false), |limit| limit["name"].clone());
+        let start = schema.get("default").and_then(|v| v.as_i64()).unwrap_or(1);
+        return Ok(json!({
+            "kind": "page",
+            "param": param["name"],
+            "type": param_type,
+            "start": start,
+            "limit": limit,
+            "page_type": page_type,
+            "body_type": page_type,
+            "max_pages": MAX_PAGES,
+        }));
+    }
+
+    // 5. A cursor walks a component body, stopping when the next cursor is empty
+    let Some(properties) = schema_ref(body)
+        .and_then(|ref_path| ref_path.strip_prefix("#/components/schemas/"))
+        .and_then(|name| args.get("components")?.get("schemas")?.get(name))
+        .and_then(|component| component.get("properties"))
+        .and_then(|properties| properties.as_object())
+    else {
+        return Ok(Value::Bool(false));
+    };
+    let Some((items, next)) = cursor_fields(properties) else {
+        return Ok(Value::Bool(false));
+    };
+    Ok(json!({
+        "kind": "cursor",
+        "param": param["name"],
+        "type": param_type,
+        "items": sanitize_identifier(items),
+        "next": sanitize_identifier(next),
+        "page_type": page_type,
+        "body_type": to_ue_type_filter(&properties[items], &type_args)?,
+        "max_pages": MAX_PAGES,
+    }))
+}
+
+/// Whether `name` is one of `candidates`, ignoring case, `_` and `-`.
+fn is_named(name: &str, candidates: &[&str]) -> bool {
+    let normalize = |name: &str| -> String {
+        name.chars().filter(|c| !matches!(c, '_' | '-')).map(|c| c.to_ascii_lowercase()).collect()
+    };
+    candidates.iter().any(|candidate| normalize(candidate) == normalize(name))
+}
+
+/// The names of a cursor page's items array and next-cursor string fields.
+fn cursor_fields(properties: &Map<String, Value>) -> Option<(&str, &str)> {
+    let items = properties
+        .iter()
+        .find(|(_, schema)| get_effective_type(schema) == "array")
+        .map(|(name, _)| name.as_str())?;
+    let next = properties
+        .iter()
+        .find(|(name, schema)| {
+            is_named(name, NEXT_CURSOR_FIELDS)
+                && get_effective_type(schema) == "string"
+        })
+        .map(|(name, _)| name.as_str())?;
+    Some((items, next))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list_operation(parameters: Value) -> Value {
+        json!({
+            "parameters": parameters,
+            "responses": {
+                "200": {
+                    "content": {
+                        "application/json": {
+                            "schema": {
+                                "type": "array",
+                                "items": { "$ref": "#/components/schemas/User" }
+                            }
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_pagination_page_and_limit() {
+        let operation = list_operation(json!([
+            { "name": "page", "in": "query", "schema": { "type": "integer", "default": 0 } },
+            { "name": "limit", "in": "query", "schema": { "type": "integer" } }
+        ]));
+        let result = pagination_filter(&operation, &HashMap::new()).unwrap();
+
+        assert_eq!(
+            result,
+            json!({
+                "kind": "page",
+                "param": "page",
+                "type": "int32",
+                "start": 0,
+                "limit": "limit",
+                "page_type": "TArray<FUser>",
+                "body_type": "TArray<FUser>",
+                "max_pages": MAX_PAGES,
+            })
+        );
+    }
+
+    #[test]
+    fn test_pagination_custom_param_names() {
+        let operation = list_operation(json!([
+            { "name": "pageNumber", "in": "query", "schema": { "type": "integer" } }
+        ]));
+        let args = HashMap::from([("params".to_string(), json!(["pagenumber"]))]);
+
+        let result = pagination_filter(&operation, &args).unwrap();
+
+        assert_eq!(result["param"], "pageNumber");
+        assert_eq!(result["start"], 1);
+        assert_eq!(result["limit"], false);
+        assert_eq!(pagination_filter(&operation, &HashMap::new()).unwrap(), json!(false));
+    }
+
+    #[test]
+    fn test_pagination_cursor() {
+        let mut operation = list_operation(json!([
+            { "name": "cursor", "in": "query", "schema": { "type": "string" } }
+        ]));
+        operation["responses"]["200"]["content"]["application/json"]["schema"] =
+            json!({ "$ref": "#/components/schemas/UserPage" });
+        let components = json!({ "schemas": { "UserPage": { "type": "object", "properties": {
+            "total": { "type": "integer" },
+            "items": { "type": "array", "items": { "$ref": "#/components/schemas/User" } },
+            "next-cursor": { "type": ["string", "null"] }
+        } } } });
+        let args = HashMap::from([("components".to_string(), components)]);
+
+        assert_eq!(
+            pagination_filter(&operation, &args).unwrap(),
+            json!({
+                "kind": "cursor",
+                "param": "cursor",
+                "type": "FString",
+                "items": "items",
+                "next": "next_cursor",
+                "page_type": "FUserPage",
+                "body_type": "TArray<FUser>",
+                "max_pages": MAX_PAGES,
+            })
+        );
+        // Without the components the page's fields are unknown
+        assert_eq!(pagination_filter(&operation, &HashMap::new()).unwrap(), json!(false));
+    }
+
+    #[test]
+    fn test_pagination_requires_list_response() {
+        let mut operation = list_operation(json!([
+            { "name": "page", "in": "query", "schema": { "type": "integer" } }
+        ]));
+        operation["responses"]["200"]["content"]["application/json"]["schema"] =
+            json!({ "$ref": "#/components/schemas/User" });
+
+        assert_eq!(pagination_filter(&operation, &HashMap::new()).unwrap(), json!(false));
+    }
+
+    #[test]
+    fn test_pagination_requires_integer_query_param() {
+        let path_page = list_operation(json!([
+            { "name": "page", "in": "path", "required": true, "schema": { "type": "integer" } }
+        ]));
+        let string_page = list_operation(json!([
+            { "name": "page", "in": "query", "schema": { "type": "string" } }
+        ]));
+
+        for operation in [path_page, string_page, list_operation(json!([]))] {
+            assert_eq!(pagination_filter(&operation, &HashMap::new()).unwrap(), json!(false));
+        }
+    }
+
+    #[test]
+    fn test_pagination_invalid_input() {
+        let result = pagination_filter(&json!([]), &HashMap::new());
+
+        assert!(result.unwrap_err().to_string().contains("expects an operation object"));
+    }
+}
@@ -0,0 +1,71 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use crate::filter::default_value::default_value_filter;
+use std::collections::HashMap;
+use tera::{Result, Value};
+
+/// Tera filter to convert an operation parameter's schema `default` into a C++ literal.
+///
+/// Takes one OpenAPI parameter object and converts its `schema.default` the way
+/// `default_value` does. A parameter without a `schema`, or whose schema has no usable
+/// default, yields an empty string.
+///
+/// Usage in the template: {{ param | parameter_default }}
+pub fn parameter_default_filter(value: &Value, _args: &HashMap<String, Value>) -> Result<Value> {
+    // 1. Check if the input is a parameter object
+    let param = value.as_object().ok_or_else(|| {
+        tera::Error::msg("parameter_default filter expects a parameter object as input.")
+    })?;
+
+    // 2. Convert the default of its schema, if any
+    match param.get("schema") {
+        Some(schema) if schema.is_object() => default_value_filter(schema, &HashMap::new()),
+        _ => Ok(Value::String(String::new())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn default_of(param: Value) -> String {
+        let result = parameter_default_filter(&param, &HashMap::new()).unwrap();
+        result.as_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_parameter_default_string() {
+        let param = json!({
+            "name": "shard",
+            "in": "query",
+            "schema": { "type": "string", "default": "CN-1" }
+        });
+
+        assert_eq!(default_of(param), "TEXT(\"CN-1\")");
+    }
+
+    #[test]
+    fn test_parameter_default_integer_and_boolean() {
+        let limit = json!({ "name": "limit", "in": "query", "schema": { "default": 20 } });
+        let dry = json!({ "name": "dry", "in": "query", "schema": { "default": false } });
+
+        assert_eq!(default_of(limit), "20");
+        assert_eq!(default_of(dry), "false");
+    }
+
+    #[test]
+    fn test_parameter_default_without_default() {
+        assert_eq!(default_of(json!({ "name": "id", "in": "path" })), "");
+        assert_eq!(default_of(json!({ "name": "id", "schema": { "type": "string" } })), "");
+    }
+
+    #[test]
+    fn test_parameter_default_invalid_input() {
+        let result = parameter_default_filter(&json!("shard"), &HashMap::new());
+
+        assert!(result.unwrap_err().to_string().contains("expects a parameter object"));
+    }
+}
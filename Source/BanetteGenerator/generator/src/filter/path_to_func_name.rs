@@ -2,6 +2,9 @@
  * Copyright 2019-Present tarnishablec. All Rights Reserved.
  */
 
+use crate::filter::sanitize_identifier::sanitize_identifier;
+use crate::filter::to_snake_case::split_words;
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use tera::{to_value, Result, Value};
 
@@ -14,30 +17,178 @@ use tera::{to_value, Result, Value};
 /// - `/character/{id}`, method="get" -> `GET_Character_By_Id`
 /// - `/user/{user_id}/posts`, method="get" -> `GET_User_Posts_By_UserId`
 /// - `/api/{resource_id}/sub/{sub_id}`, method="post" -> `POST_Api_Sub_By_ResourceId_SubId`
+///
+/// A `strip_prefix` argument (e.g. `v1`, or several segments as `api/v1`) drops the path's
+/// leading segments when they match it, ignoring case: `/v1/player/characters` becomes
+/// `GET_Player_Characters`, while `/v2/player/characters` keeps its name.
+///
+/// The root path `/` has no segments and yields just the method, e.g. `GET`, unless a
+/// non-empty `root` argument names it: `/`, method="get", root="Root" -> `GET_Root`. The
+/// same goes for a path that is only the stripped prefix, such as `/v1`.
+///
+/// When a non-empty `operation_id` argument is given, it is used instead, PascalCased and
+/// sanitized (see [`operation_id_to_func_name`]). An optional `acronyms` array keeps the
+/// listed words in the given casing (see [`convert_to_pascal_case_with`]), and an optional
+/// `max_len` caps the name's length (see [`cap_func_name_len`]).
 pub fn path_to_func_name_filter(value: &Value, args: &HashMap<String, Value>) -> Result<Value> {
     let path = value
         .as_str()
         .ok_or_else(|| tera::Error::msg("Path must be a string"))?;
 
-    // 1. Get and uppercase the HTTP method (GET, POST, etc.)
+    // 1. Get the HTTP method (GET, POST, etc.)
     let method = args
         .get("method")
         .and_then(|v| v.as_str())
-        .ok_or_else(|| tera::Error::msg("path_to_func_name requires a 'method' argument"))?
-        .to_uppercase();
+        .ok_or_else(|| tera::Error::msg("path_to_func_name requires a 'method' argument"))?;
+
+    // 2. Prefer the curated operationId when the spec provides one
+    let operation_id = args.get("operation_id").and_then(|v| v.as_str());
+
+    // 3. Collect the acronyms to keep intact, if any
+    let acronyms: Vec<String> = match args.get("acronyms") {
+        None => Vec::new(),
+        Some(Value::Array(acronyms)) => acronyms
+            .iter()
+            .filter_map(|v| v.as_str().map(String::from))
+            .collect(),
+        Some(_) => {
+            return Err(tera::Error::msg(
+                "path_to_func_name 'acronyms' argument must be an array of strings",
+            ));
+        }
+    };
+
+    // 4. Strip the prefix and name the root path if requested
+    let string_arg = |name: &str| match args.get(name) {
+        None | Some(Value::Null) => Ok(None),
+        Some(Value::String(value)) => Ok(Some(value.as_str())),
+        Some(_) => Err(tera::Error::msg(format!(
+            "path_to_func_name '{}' argument must be a string",
+            name
+        ))),
+    };
+    let naming = FuncNaming {
+        acronyms: &acronyms,
+        root: string_arg("root")?,
+        strip_prefix: string_arg("strip_prefix")?,
+    };
+
+    // 5. Cap the length if requested
+    let name = func_name(path, method, operation_id, &naming);
+    let name = match args.get("max_len") {
+        None => name,
+        Some(max_len) => {
+            let max_len = max_len
+                .as_u64()
+                .filter(|&len| len as usize >= MIN_CAPPED_LEN)
+                .ok_or_else(|| {
+                    tera::Error::msg(format!(
+                        "path_to_func_name 'max_len' argument must be an integer of at least {}",
+                        MIN_CAPPED_LEN
+                    ))
+                })?;
+            cap_func_name_len(&name, max_len as usize)
+        }
+    };
+
+    Ok(to_value(name)?)
+}
 
-    // 2. Remove the leading slash
-    let cleaned_path = path.trim_start_matches('/');
+/// Hex digits of the hash [`cap_func_name_len`] appends.
+const HASH_SUFFIX_LEN: usize = 6;
+
+/// Smallest useful `max_len`: one kept character, `_`, and the hash.
+pub const MIN_CAPPED_LEN: usize = HASH_SUFFIX_LEN + 2;
+
+/// Shortens `name` to at most `max_len` characters.
+///
+/// A longer name is truncated and suffixed with `_` and the first hex digits of the
+/// SHA-256 of the full name, so two long names sharing a prefix still differ and the same
+/// name is always shortened the same way. Names within the limit are returned unchanged.
+/// `max_len` should be at least [`MIN_CAPPED_LEN`].
+pub fn cap_func_name_len(name: &str, max_len: usize) -> String {
+    if name.chars().count() <= max_len {
+        return name.to_string();
+    }
+
+    let hash = format!("{:x}", Sha256::digest(name.as_bytes()));
+    let kept: String = name
+        .chars()
+        .take(max_len.saturating_sub(HASH_SUFFIX_LEN + 1))
+        .collect();
+    format!("{}_{}", kept.trim_end_matches('_'), &hash[..HASH_SUFFIX_LEN])
+}
 
-    // 3. Split and separate into regular segments and parameters
+/// Settings for deriving function names, see [`path_to_func_name_filter`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FuncNaming<'a> {
+    /// Words kept in this exact casing (see [`convert_to_pascal_case_with`]).
+    pub acronyms: &'a [String],
+    /// Segment naming a path without segments or parameters, such as `/`.
+    pub root: Option<&'a str>,
+    /// Leading path segments dropped when they match, e.g. `v1` or `api/v1`.
+    pub strip_prefix: Option<&'a str>,
+}
+
+/// Function name for an operation: its `operationId` if present and non-empty, otherwise
+/// the name derived from `method` and `path`.
+pub fn func_name(
+    path: &str,
+    method: &str,
+    operation_id: Option<&str>,
+    naming: &FuncNaming,
+) -> String {
+    match operation_id {
+        Some(id) if !id.trim().is_empty() => operation_id_to_func_name(id, naming.acronyms),
+        _ => path_to_func_name(path, method, naming),
+    }
+}
+
+/// Converts an `operationId` to a PascalCase C++ identifier.
+///
+/// Any character that is not a letter or digit separates words, and the result is passed
+/// through [`sanitize_identifier`], e.g. `get user.profile` -> `GetUserProfile`.
+pub fn operation_id_to_func_name(operation_id: &str, acronyms: &[String]) -> String {
+    let words: String = operation_id
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+
+    sanitize_identifier(&convert_to_pascal_case_with(&words, acronyms))
+}
+
+/// Builds the `METHOD_Segments_By_Parameters` name for `path`.
+///
+/// Leading segments matching `naming.strip_prefix` are left out. A path without segments
+/// or parameters, such as `/`, becomes `METHOD_Root` for a non-empty `naming.root` of
+/// `Root`, and just `METHOD` otherwise.
+pub fn path_to_func_name(path: &str, method: &str, naming: &FuncNaming) -> String {
+    let method = method.to_uppercase();
+    let acronyms = naming.acronyms;
+
+    // Split into segments, skipping empty ones such as the leading slash's
+    let mut parts: Vec<&str> = path.split('/').filter(|part| !part.is_empty()).collect();
+
+    // Drop the prefix, only when all of its segments lead the path
+    let prefix: Vec<&str> = naming
+        .strip_prefix
+        .unwrap_or_default()
+        .split('/')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect();
+    if !prefix.is_empty()
+        && parts.len() >= prefix.len()
+        && parts.iter().zip(&prefix).all(|(part, prefix)| part.eq_ignore_ascii_case(prefix))
+    {
+        parts.drain(..prefix.len());
+    }
+
+    // Separate into regular segments and parameters
     let mut regular_segments = Vec::new();
     let mut parameters = Vec::new();
 
-    for part in cleaned_path.split('/') {
-        if part.is_empty() {
-            continue;
-        }
-
+    for part in parts {
         // Check if this part is a path parameter (enclosed in {})
         if part.starts_with('{') && part.ends_with('}') {
             // Remove the braces
@@ -50,14 +201,14 @@ pub fn path_to_func_name_filter(value: &Value, args: &HashMap<String, Value>) ->
             }
 
             // Convert parameter name to PascalCase and add to a parameter list
-            parameters.push(convert_to_pascal_case(param_name));
+            parameters.push(convert_to_pascal_case_with(param_name, acronyms));
         } else {
             // Regular path segment - convert to PascalCase for consistency
-            regular_segments.push(convert_to_pascal_case(part));
+            regular_segments.push(convert_to_pascal_case_with(part, acronyms));
         }
     }
 
-    // 4. Build the function name: METHOD_Segments_By_Parameters
+    // Build the function name: METHOD_Segments_By_Parameters
     let mut func_name = method.clone();
 
     // Add regular segments separated by underscores
@@ -73,7 +224,17 @@ pub fn path_to_func_name_filter(value: &Value, args: &HashMap<String, Value>) ->
         func_name.push_str(&parameters.join("_"));
     }
 
-    Ok(to_value(func_name)?)
+    // Name the root path after the configured segment, if any
+    let root = naming.root.map(|root| convert_to_pascal_case_with(root.trim(), acronyms));
+    if let Some(root) = root.filter(|root| !root.is_empty())
+        && regular_segments.is_empty()
+        && parameters.is_empty()
+    {
+        func_name.push('_');
+        func_name.push_str(&root);
+    }
+
+    func_name
 }
 
 /// Convert a string to PascalCase.
@@ -116,6 +277,27 @@ fn convert_to_pascal_case(input: &str) -> String {
     result
 }
 
+/// Like [`convert_to_pascal_case`], but words matching one of `acronyms` (ignoring case)
+/// are emitted in the acronym's own casing, e.g. `api_key` -> `APIKey` with `API` listed.
+///
+/// Words are split as by [`split_words`], so `HTTPStatus` is `HTTP` + `Status`. With no
+/// acronyms this is exactly [`convert_to_pascal_case`].
+pub fn convert_to_pascal_case_with(input: &str, acronyms: &[String]) -> String {
+    if acronyms.is_empty() {
+        return convert_to_pascal_case(input);
+    }
+
+    split_words(input)
+        .iter()
+        .map(|word| {
+            match acronyms.iter().find(|acronym| acronym.eq_ignore_ascii_case(word)) {
+                Some(acronym) => acronym.clone(),
+                None => convert_to_pascal_case(word),
+            }
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -281,6 +463,63 @@ mod tests {
         assert_eq!(result.as_str().unwrap(), "GET_Api_Resource");
     }
 
+    #[test]
+    fn test_convert_to_pascal_case_with_acronyms() {
+        let acronyms = ["ID", "API", "URL", "HTTP"].map(String::from);
+
+        assert_eq!(convert_to_pascal_case_with("api_key", &[]), "ApiKey");
+        assert_eq!(convert_to_pascal_case_with("api_key", &acronyms), "APIKey");
+        assert_eq!(convert_to_pascal_case_with("user_id", &acronyms), "UserID");
+        assert_eq!(convert_to_pascal_case_with("HttpStatus", &acronyms), "HTTPStatus");
+        assert_eq!(convert_to_pascal_case_with("redirectUrl", &acronyms), "RedirectURL");
+        assert_eq!(convert_to_pascal_case_with("v1", &acronyms), "V1");
+    }
+
+    #[test]
+    fn test_path_to_func_name_with_acronyms() {
+        let path = json!("/api/users/{user_id}");
+        let mut args = create_method_args("get");
+        args.insert("acronyms".to_string(), json!(["API", "ID"]));
+
+        let result = path_to_func_name_filter(&path, &args).unwrap();
+        assert_eq!(result.as_str().unwrap(), "GET_API_Users_By_UserID");
+
+        args.insert("acronyms".to_string(), json!("API"));
+        assert!(path_to_func_name_filter(&path, &args).is_err());
+    }
+
+    #[test]
+    fn test_path_to_func_name_max_len_truncates_long_names() {
+        let path = json!("/organizations/{organization_id}/projects/{project_id}/environments");
+        let mut args = create_method_args("get");
+        args.insert("max_len".to_string(), json!(40));
+
+        let result = path_to_func_name_filter(&path, &args).unwrap();
+        let name = result.as_str().unwrap();
+        assert_eq!(name.len(), 40);
+        assert!(name.starts_with("GET_Organizations_Projects_Enviro_"));
+
+        // Deterministic, and distinct from a name sharing the same prefix
+        let again = path_to_func_name_filter(&path, &args).unwrap();
+        assert_eq!(again, result);
+        let other = json!("/organizations/{organization_id}/projects/{project_id}/environment");
+        let other = path_to_func_name_filter(&other, &args).unwrap();
+        assert_ne!(other, result);
+    }
+
+    #[test]
+    fn test_path_to_func_name_max_len_keeps_short_names() {
+        let path = json!("/character/{id}");
+        let mut args = create_method_args("get");
+        args.insert("max_len".to_string(), json!(40));
+
+        let result = path_to_func_name_filter(&path, &args).unwrap();
+        assert_eq!(result.as_str().unwrap(), "GET_Character_By_Id");
+
+        args.insert("max_len".to_string(), json!(4));
+        assert!(path_to_func_name_filter(&path, &args).is_err());
+    }
+
     #[test]
     fn test_convert_to_pascal_case_empty_string() {
         // Test that an empty string returns empty string
@@ -295,6 +534,117 @@ mod tests {
         assert_eq!(convert_to_pascal_case("_-_"), "");
     }
 
+    #[test]
+    fn test_path_to_func_name_prefers_operation_id() {
+        let path = json!("/user/{user_id}");
+        let mut args = create_method_args("get");
+        args.insert("operation_id".to_string(), json!("getUserById"));
+
+        let result = path_to_func_name_filter(&path, &args).unwrap();
+        assert_eq!(result.as_str().unwrap(), "GetUserById");
+    }
+
+    #[test]
+    fn test_path_to_func_name_without_operation_id() {
+        let path = json!("/user/{user_id}");
+        let mut args = create_method_args("get");
+        args.insert("operation_id".to_string(), json!(""));
+
+        let result = path_to_func_name_filter(&path, &args).unwrap();
+        assert_eq!(result.as_str().unwrap(), "GET_User_By_UserId");
+    }
+
+    #[test]
+    fn test_path_to_func_name_sanitizes_operation_id() {
+        assert_eq!(operation_id_to_func_name("get user.profile", &[]), "GetUserProfile");
+        assert_eq!(operation_id_to_func_name("users/list-all", &[]), "UsersListAll");
+        assert_eq!(operation_id_to_func_name("2fa:verify", &[]), "_2faVerify");
+        assert_eq!(operation_id_to_func_name("delete", &[]), "Delete");
+    }
+
+    #[test]
+    fn test_func_name_collision_is_detectable() {
+        // An operationId can spell out the same name another operation derives from its path
+        assert_eq!(
+            func_name("/items", "get", Some("GET"), &FuncNaming::default()),
+            func_name("/", "get", None, &FuncNaming::default())
+        );
+    }
+
+    #[test]
+    fn test_path_to_func_name_root_without_fallback() {
+        let result = path_to_func_name_filter(&json!("/"), &create_method_args("get")).unwrap();
+        assert_eq!(result.as_str().unwrap(), "GET");
+
+        let mut args = create_method_args("post");
+        args.insert("root".to_string(), json!(""));
+        let result = path_to_func_name_filter(&json!("/"), &args).unwrap();
+        assert_eq!(result.as_str().unwrap(), "POST");
+    }
+
+    #[test]
+    fn test_path_to_func_name_root_with_fallback() {
+        let name = |path: &str, root: &str| {
+            let mut args = create_method_args("get");
+            args.insert("root".to_string(), json!(root));
+            let result = path_to_func_name_filter(&json!(path), &args).unwrap();
+            result.as_str().unwrap().to_string()
+        };
+
+        assert_eq!(name("/", "Root"), "GET_Root");
+        assert_eq!(name("", "service_root"), "GET_ServiceRoot");
+        // Only a path without segments or parameters is the root
+        assert_eq!(name("/users", "Root"), "GET_Users");
+        assert_eq!(name("/{id}", "Root"), "GET_By_Id");
+        // An operationId still wins
+        assert_ne!(
+            func_name("/items", "get", Some("GET"), &FuncNaming::default()),
+            func_name("/", "get", None, &FuncNaming { root: Some("Root"), ..Default::default() })
+        );
+
+        let mut args = create_method_args("get");
+        args.insert("root".to_string(), json!(1));
+        let error = path_to_func_name_filter(&json!("/"), &args).unwrap_err();
+        assert!(error.to_string().contains("'root' argument must be a string"));
+    }
+
+    fn stripped_name(path: &str, prefix: &str) -> String {
+        let mut args = create_method_args("get");
+        args.insert("strip_prefix".to_string(), json!(prefix));
+        let result = path_to_func_name_filter(&json!(path), &args).unwrap();
+        result.as_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_path_to_func_name_strip_prefix() {
+        assert_eq!(stripped_name("/v1/player/characters", "v1"), "GET_Player_Characters");
+        assert_eq!(stripped_name("/V1/character/{id}", "/v1/"), "GET_Character_By_Id");
+        assert_eq!(stripped_name("/api/v1/items", "api/v1"), "GET_Items");
+    }
+
+    #[test]
+    fn test_path_to_func_name_strip_prefix_no_match() {
+        assert_eq!(stripped_name("/v2/player/characters", "v1"), "GET_V2_Player_Characters");
+        assert_eq!(stripped_name("/v10/items", "v1"), "GET_V10_Items");
+        assert_eq!(stripped_name("/api/v1/items", "v1"), "GET_Api_V1_Items");
+        assert_eq!(stripped_name("/items/v1", "v1"), "GET_Items_V1");
+        assert_eq!(stripped_name("/v1/items", ""), "GET_V1_Items");
+    }
+
+    #[test]
+    fn test_path_to_func_name_strip_prefix_only_segment() {
+        assert_eq!(stripped_name("/v1", "v1"), "GET");
+        assert_eq!(stripped_name("/v1/", "v1"), "GET");
+
+        let naming = FuncNaming {
+            root: Some("Root"),
+            strip_prefix: Some("v1"),
+            ..Default::default()
+        };
+        assert_eq!(path_to_func_name("/v1", "get", &naming), "GET_Root");
+        assert_eq!(path_to_func_name("/v1/{id}", "get", &naming), "GET_By_Id");
+    }
+
     /// Tests for the specific examples from the problem statement
     #[test]
     fn test_path_to_func_name_problem_statement_example_1() {
@@ -0,0 +1,121 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use crate::filter::escape::escape_cpp_string;
+use std::collections::HashMap;
+use tera::{to_value, Result, Value};
+
+/// Tera filter to document a string schema's `pattern` in a `/** pattern: ... */` comment.
+///
+/// Unreal has no metadata for a regex, so the pattern is only documented on the field. It
+/// is kept as written, except that `*/` is escaped so it cannot close the comment early
+/// and line breaks are written as `\n`. A schema without a `pattern` yields an empty string.
+///
+/// Usage in the template: {{ prop_schema | pattern_comment }}
+pub fn pattern_comment_filter(value: &Value, _args: &HashMap<String, Value>) -> Result<Value> {
+    let comment = match pattern_of(value, "pattern_comment")? {
+        Some(pattern) => {
+            let pattern = pattern
+                .replace("*/", "*\\/")
+                .replace("\r\n", "\\n")
+                .replace(['\n', '\r'], "\\n");
+            format!("/** pattern: {} */", pattern)
+        }
+        None => String::new(),
+    };
+
+    to_value(comment)
+        .map_err(|e| tera::Error::msg(format!("Failed to convert string to Value: {}", e)))
+}
+
+/// Tera filter to declare a string schema's `pattern` as a constant for runtime validation,
+/// e.g. with `FRegexPattern`.
+///
+/// Takes the constant's name as the `name` argument and returns
+/// `static inline const FString <name> = TEXT("...");` with the pattern escaped for the
+/// C++ string literal, so `^\d+$` becomes `TEXT("^\\d+$")`. A schema without a `pattern`
+/// yields an empty string.
+///
+/// Usage in the template: {{ prop_schema | pattern_constant(name="CodePattern") }}
+pub fn pattern_constant_filter(value: &Value, args: &HashMap<String, Value>) -> Result<Value> {
+    let Some(pattern) = pattern_of(value, "pattern_constant")? else {
+        return Ok(Value::String(String::new()));
+    };
+    let name = args
+        .get("name")
+        .and_then(|v| v.as_str())
+        .filter(|name| !name.is_empty())
+        .ok_or_else(|| tera::Error::msg("pattern_constant filter expects a 'name' argument."))?;
+
+    let constant = format!(
+        "static inline const FString {} = TEXT(\"{}\");",
+        name,
+        escape_cpp_string(pattern)
+    );
+    to_value(constant)
+        .map_err(|e| tera::Error::msg(format!("Failed to convert string to Value: {}", e)))
+}
+
+/// The non-empty `pattern` of a schema object, if it declares one.
+fn pattern_of<'a>(value: &'a Value, filter: &str) -> Result<Option<&'a str>> {
+    let schema = value.as_object().ok_or_else(|| {
+        tera::Error::msg(format!("{} filter expects a schema object as input.", filter))
+    })?;
+    Ok(schema
+        .get("pattern")
+        .and_then(|v| v.as_str())
+        .filter(|pattern| !pattern.is_empty()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn comment_of(schema: Value) -> String {
+        let result = pattern_comment_filter(&schema, &HashMap::new()).unwrap();
+        result.as_str().unwrap().to_string()
+    }
+
+    fn constant_of(schema: Value) -> String {
+        let args = HashMap::from([("name".to_string(), json!("CodePattern"))]);
+        let result = pattern_constant_filter(&schema, &args).unwrap();
+        result.as_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_pattern_comment() {
+        let schema = json!({ "type": "string", "pattern": "^[A-Z]{3}-\\d{4}$" });
+
+        assert_eq!(comment_of(schema), "/** pattern: ^[A-Z]{3}-\\d{4}$ */");
+        assert_eq!(comment_of(json!({ "type": "string" })), "");
+    }
+
+    #[test]
+    fn test_pattern_comment_cannot_close_early() {
+        let schema = json!({ "pattern": "^a*/b\nc$" });
+
+        assert_eq!(comment_of(schema), "/** pattern: ^a*\\/b\\nc$ */");
+    }
+
+    #[test]
+    fn test_pattern_constant_escapes_backslashes() {
+        let schema = json!({ "type": "string", "pattern": "^\\d+\\.\"[a-z]\"$" });
+
+        assert_eq!(
+            constant_of(schema),
+            "static inline const FString CodePattern = TEXT(\"^\\\\d+\\\\.\\\"[a-z]\\\"$\");"
+        );
+        assert_eq!(constant_of(json!({ "type": "string", "pattern": "" })), "");
+    }
+
+    #[test]
+    fn test_pattern_invalid_input() {
+        let result = pattern_comment_filter(&json!("^a$"), &HashMap::new());
+        assert!(result.unwrap_err().to_string().contains("expects a schema object"));
+
+        let result = pattern_constant_filter(&json!({ "pattern": "^a$" }), &HashMap::new());
+        assert!(result.unwrap_err().to_string().contains("expects a 'name' argument"));
+    }
+}
@@ -6,6 +6,12 @@ use std::collections::HashMap;
 use tera::{Result, Value};
 
 pub fn request_body_schema_filter(value: &Value, _args: &HashMap<String, Value>) -> Result<Value> {
+    // Only the selected schema is cloned, as a filter has to return an owned value
+    request_body_schema(value).cloned()
+}
+
+/// Borrowing version of [`request_body_schema_filter`], for callers outside templates.
+pub fn request_body_schema(value: &Value) -> Result<&Value> {
     // 1. Check that the input is an object
     let req_body = value.as_object().ok_or_else(|| {
         tera::Error::msg("Input to get_body_schema must be a valid requestBody object.")
@@ -16,25 +22,58 @@ pub fn request_body_schema_filter(value: &Value, _args: &HashMap<String, Value>)
         .get("content")
         .ok_or_else(|| tera::Error::msg("requestBody object is missing 'content' field."))?;
 
-    // 3. Try to find the schema for "application/json"
-    if let Some(schema_obj) = content
+    // 3. Take the schema of application/json, or else of the first media type
+    media_type_schema(content).ok_or_else(|| {
+        tera::Error::msg(
+            "Could not find a valid schema object within requestBody content (checked application/json and first available type).",
+        )
+    })
+}
+
+/// The schema of the `application/json` entry of `content`, or else of its first entry.
+pub fn media_type_schema(content: &Value) -> Option<&Value> {
+    content
         .get("application/json")
         .and_then(|json_media_type| json_media_type.get("schema"))
-    {
-        return Ok(schema_obj.clone());
-    }
+        .or_else(|| content.as_object()?.values().next()?.get("schema"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
 
-    // 4. Fallback: if there is no application/json, try the first available media type
-    if let Some(content_map) = content.as_object() {
-        if let Some((_, media_type)) = content_map.iter().next() {
-            if let Some(schema_obj) = media_type.get("schema") {
-                return Ok(schema_obj.clone());
+    #[test]
+    fn test_request_body_schema_borrows_selected_schema() {
+        let request_body = json!({
+            "content": {
+                "text/plain": { "schema": { "type": "string" } },
+                "application/json": { "schema": { "$ref": "#/components/schemas/User" } }
             }
-        }
+        });
+
+        let schema = request_body_schema(&request_body).unwrap();
+        assert!(std::ptr::eq(schema, &request_body["content"]["application/json"]["schema"]));
+        assert_eq!(
+            request_body_schema_filter(&request_body, &HashMap::new()).unwrap(),
+            *schema
+        );
+    }
+
+    #[test]
+    fn test_request_body_schema_first_media_type() {
+        let request_body = json!({
+            "content": { "text/plain": { "schema": { "type": "string" } } }
+        });
+
+        assert_eq!(request_body_schema(&request_body).unwrap(), &json!({ "type": "string" }));
     }
 
-    // 5. Failure handling
-    Err(tera::Error::msg(
-        "Could not find a valid schema object within requestBody content (checked application/json and first available type).",
-    ))
+    #[test]
+    fn test_request_body_schema_missing_content() {
+        let request_body = json!({ "description": "No content" });
+        let result = request_body_schema(&request_body);
+
+        assert!(result.unwrap_err().to_string().contains("missing 'content' field"));
+    }
 }
@@ -0,0 +1,193 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use crate::filter::function_params::sort_by_path_position;
+use crate::filter::has_request_body::has_request_body_filter;
+use crate::filter::parameter_default::parameter_default_filter;
+use crate::filter::request_body_schema::request_body_schema;
+use crate::filter::required_params::{is_required_param, typed_param};
+use crate::filter::to_ue_type::to_ue_type_filter;
+use serde_json::json;
+use std::collections::HashMap;
+use tera::{Result, Value};
+
+/// Tera filter to list the members of an operation's request struct, which bundles its
+/// parameters and request body into one argument.
+///
+/// Each member is the parameter augmented by `typed_param` (`ue_type` and `identifier`),
+/// plus `required` and an `initializer`: the `parameter_default` literal, or else `0` for
+/// numbers and `false` for booleans, or an empty string for types that default-construct.
+/// Members follow the order of `function_params`, without moving defaulted parameters to
+/// the end: path parameters by their position in the `path` argument, other required
+/// parameters, optional parameters, then the request body as `RequestBody` with
+/// `in: "body"`. An operation without parameters or body yields an empty array.
+///
+/// Usage in the template:
+/// {% for field in operation | request_fields(path=path) %}
+///     {{ field.ue_type }} {{ field.identifier }};
+/// {% endfor %}
+pub fn request_fields_filter(value: &Value, args: &HashMap<String, Value>) -> Result<Value> {
+    // 1. Check the operation and path
+    let operation = value.as_object().ok_or_else(|| {
+        tera::Error::msg("request_fields filter expects an operation object as input.")
+    })?;
+    let path = args
+        .get("path")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| tera::Error::msg("request_fields requires a 'path' argument"))?;
+
+    let params = match operation.get("parameters") {
+        Some(Value::Array(params)) => {
+            params.iter().map(typed_param).collect::<Result<Vec<_>>>()?
+        }
+        _ => Vec::new(),
+    };
+    let is_in_path = |param: &Value| param.get("in").and_then(|v| v.as_str()) == Some("path");
+
+    // 2. Path parameters, then required and optional non-path parameters
+    let mut ordered: Vec<&Value> = params.iter().filter(|p| is_in_path(p)).collect();
+    sort_by_path_position(&mut ordered, path);
+    let (required, optional): (Vec<&Value>, Vec<&Value>) = params
+        .iter()
+        .filter(|p| !is_in_path(p))
+        .partition(|p| is_required_param(p));
+    ordered.extend(required);
+    ordered.extend(optional);
+
+    let mut fields = Vec::new();
+    for param in ordered {
+        let default = parameter_default_filter(param, &HashMap::new())?;
+        let initializer = initializer_of(default.as_str().unwrap_or_default(), &param["ue_type"]);
+        let mut field = param.clone();
+        if let Some(object) = field.as_object_mut() {
+            object.insert("required".to_string(), Value::Bool(is_required_param(param)));
+            object.insert("initializer".to_string(), Value::String(initializer));
+        }
+        fields.push(field);
+    }
+
+    // 3. Request body
+    if has_request_body_filter(value, &HashMap::new())?.as_bool() == Some(true) {
+        let request_body = &operation["requestBody"];
+        let body_type = to_ue_type_filter(request_body_schema(request_body)?, &HashMap::new())?;
+        fields.push(json!({
+            "name": "RequestBody",
+            "in": "body",
+            "identifier": "RequestBody",
+            "ue_type": body_type,
+            "required": request_body.get("required").and_then(|v| v.as_bool()) == Some(true),
+            "description": request_body.get("description").cloned().unwrap_or(Value::Null),
+            "initializer": "",
+        }));
+    }
+
+    Ok(Value::Array(fields))
+}
+
+/// The member initializer: the parameter's default, else the zero value of a numeric or
+/// boolean type.
+fn initializer_of(default: &str, ue_type: &Value) -> String {
+    if !default.is_empty() {
+        return default.to_string();
+    }
+    match ue_type.as_str().unwrap_or_default() {
+        "int32" | "int64" | "uint8" | "float" | "double" => "0".to_string(),
+        "bool" => "false".to_string(),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields_of(operation: Value, path: &str) -> Vec<Value> {
+        let args = HashMap::from([("path".to_string(), json!(path))]);
+        let result = request_fields_filter(&operation, &args).unwrap();
+        result.as_array().unwrap().clone()
+    }
+
+    fn declarations(fields: &[Value]) -> Vec<String> {
+        fields
+            .iter()
+            .map(|field| {
+                let declaration = format!(
+                    "{} {}",
+                    field["ue_type"].as_str().unwrap(),
+                    field["identifier"].as_str().unwrap()
+                );
+                match field["initializer"].as_str().unwrap() {
+                    "" => declaration,
+                    initializer => format!("{} = {}", declaration, initializer),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_request_fields_params_and_body() {
+        let operation = json!({
+            "parameters": [
+                { "name": "limit", "in": "query", "schema": { "type": "integer", "default": 20 } },
+                { "name": "item_id", "in": "path", "schema": { "type": "integer" } },
+                { "name": "verbose", "in": "query", "schema": { "type": "boolean" } },
+                { "name": "user-id", "in": "path", "schema": { "type": "string" } },
+                { "name": "class", "in": "query", "required": true, "schema": { "type": "string" } }
+            ],
+            "requestBody": {
+                "required": true,
+                "content": {
+                    "application/json": { "schema": { "$ref": "#/components/schemas/Item" } }
+                }
+            }
+        });
+
+        let fields = fields_of(operation, "/users/{user-id}/items/{item_id}");
+        assert_eq!(
+            declarations(&fields),
+            [
+                "FString user_id",
+                "int32 item_id = 0",
+                "FString class_",
+                "int32 limit = 20",
+                "bool verbose = false",
+                "FItem RequestBody",
+            ]
+        );
+        assert_eq!(fields[0]["name"], "user-id");
+        assert_eq!(fields[0]["required"], true);
+        assert_eq!(fields[3]["required"], false);
+        assert_eq!(fields[5]["in"], "body");
+        assert_eq!(fields[5]["required"], true);
+    }
+
+    #[test]
+    fn test_request_fields_string_default() {
+        let operation = json!({
+            "parameters": [
+                { "name": "shard", "in": "query", "schema": { "type": "string", "default": "CN-1" } }
+            ]
+        });
+
+        assert_eq!(
+            declarations(&fields_of(operation, "/servers")),
+            ["FString shard = TEXT(\"CN-1\")"]
+        );
+    }
+
+    #[test]
+    fn test_request_fields_empty_operation() {
+        assert!(fields_of(json!({ "responses": {} }), "/health").is_empty());
+    }
+
+    #[test]
+    fn test_request_fields_invalid_input() {
+        let args = HashMap::from([("path".to_string(), json!("/health"))]);
+        let result = request_fields_filter(&json!("operation"), &args);
+        assert!(result.unwrap_err().to_string().contains("expects an operation object"));
+
+        let result = request_fields_filter(&json!({}), &HashMap::new());
+        assert!(result.unwrap_err().to_string().contains("'path' argument"));
+    }
+}
@@ -0,0 +1,113 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use crate::filter::sanitize_identifier::sanitize_identifier;
+use crate::filter::to_ue_type::to_ue_type_filter;
+use std::collections::HashMap;
+use tera::{Result, Value};
+
+/// Whether `param` must be passed: `required: true`, or any path parameter, which the
+/// spec requires implicitly.
+pub fn is_required_param(param: &Value) -> bool {
+    param.get("in").and_then(|v| v.as_str()) == Some("path")
+        || param.get("required").and_then(|v| v.as_bool()) == Some(true)
+}
+
+/// Returns `param` with its UE type as `ue_type` and its C++ name as `identifier`.
+///
+/// The type comes from `to_ue_type` on the parameter's `schema` (a missing schema is
+/// treated like the templates do, as `false`), and the name from `sanitize_identifier`.
+pub fn typed_param(param: &Value) -> Result<Value> {
+    let name = param
+        .get("name")
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| tera::Error::msg("Parameter is missing a 'name' string."))?;
+    let schema = param.get("schema").cloned().unwrap_or(Value::Bool(false));
+    let ue_type = to_ue_type_filter(&schema, &HashMap::new())?;
+
+    let mut typed = param.clone();
+    if let Some(object) = typed.as_object_mut() {
+        object.insert("ue_type".to_string(), ue_type);
+        object.insert("identifier".to_string(), Value::String(sanitize_identifier(name)));
+    }
+    Ok(typed)
+}
+
+/// Tera filter to select the required parameters of an operation, ready for a signature.
+///
+/// Keeps the parameters with `required: true` plus every path parameter, in spec order,
+/// each augmented with `ue_type` and `identifier` (see [`typed_param`]).
+///
+/// Usage in the template:
+/// {% for param in operation.parameters | default(value=[]) | required_params %}
+///     {{ param.ue_type }} {{ param.identifier }},
+/// {% endfor %}
+pub fn required_params_filter(value: &Value, _args: &HashMap<String, Value>) -> Result<Value> {
+    let params = value.as_array().ok_or_else(|| {
+        tera::Error::msg("required_params filter expects a parameters array as input.")
+    })?;
+
+    params
+        .iter()
+        .filter(|param| is_required_param(param))
+        .map(typed_param)
+        .collect::<Result<Vec<_>>>()
+        .map(Value::Array)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_required_params_mixed() {
+        let params = json!([
+            { "name": "limit", "in": "query", "schema": { "type": "integer" } },
+            { "name": "class", "in": "query", "required": true, "schema": { "type": "string" } },
+            { "name": "verbose", "in": "query", "required": false, "schema": { "type": "number" } },
+            { "name": "X-Trace", "in": "header", "required": true, "schema": { "type": "string" } }
+        ]);
+        let result = required_params_filter(&params, &HashMap::new()).unwrap();
+
+        let result = result.as_array().unwrap();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0]["name"], "class");
+        assert_eq!(result[0]["identifier"], "class_");
+        assert_eq!(result[0]["ue_type"], "FString");
+        assert_eq!(result[1]["identifier"], "X_Trace");
+    }
+
+    #[test]
+    fn test_required_params_implicit_path_param() {
+        let params = json!([
+            {
+                "name": "character-id",
+                "in": "path",
+                "schema": { "type": "integer", "format": "int64" }
+            }
+        ]);
+        let result = required_params_filter(&params, &HashMap::new()).unwrap();
+
+        assert_eq!(
+            result,
+            json!([{
+                "name": "character-id",
+                "in": "path",
+                "schema": { "type": "integer", "format": "int64" },
+                "ue_type": "int64",
+                "identifier": "character_id"
+            }])
+        );
+    }
+
+    #[test]
+    fn test_required_params_invalid_input() {
+        let result = required_params_filter(&json!({}), &HashMap::new());
+        assert!(result.unwrap_err().to_string().contains("expects a parameters array"));
+
+        let result = required_params_filter(&json!([{ "in": "path" }]), &HashMap::new());
+        assert!(result.unwrap_err().to_string().contains("missing a 'name'"));
+    }
+}
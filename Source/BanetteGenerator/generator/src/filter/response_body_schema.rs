@@ -2,68 +2,74 @@
  * Copyright 2019-Present tarnishablec. All Rights Reserved.
  */
 
+use crate::filter::request_body_schema::media_type_schema;
 use std::collections::HashMap;
-use tera::{Result, Value};
-
-/// Successful HTTP status codes to prioritize when extracting response schemas
-const SUCCESS_STATUS_CODES: &[&str] = &["200", "201", "202", "203", "204"];
+use tera::{Map, Result, Value};
 
 /// Tera filter to extract the schema from an OpenAPI responses object.
 ///
 /// This filter handles the OpenAPI `responses` structure which contains status codes
 /// as keys (e.g., "200", "201", "404"). It attempts to extract the schema in the
 /// following order:
-/// 1. Looks for successful response status codes (200, 201, 202, 203, 204)
-/// 2. Falls back to the first available response
+/// 1. Looks for the lowest successful (2xx) status code, then a `2XX` range key (any case)
+/// 2. Falls back to the `default` response, then the first available response
 /// 3. From the selected response, extracts schema preferring `application/json`
 /// 4. If not found, use the first available media type
 ///
 /// Usage in the template: `{{ operation.responses | response_body_schema | to_ue_type }}`
 pub fn response_body_schema_filter(value: &Value, _args: &HashMap<String, Value>) -> Result<Value> {
+    // Only the selected schema is cloned, as a filter has to return an owned value
+    Ok(response_body_schema(value)?.cloned().unwrap_or(Value::Null))
+}
+
+/// Borrowing version of [`response_body_schema_filter`], for callers outside templates.
+/// `None` stands for the filter's `null`.
+pub fn response_body_schema(value: &Value) -> Result<Option<&Value>> {
     // 1. Check that the input is an object (response object)
     let responses = value.as_object().ok_or_else(|| {
         tera::Error::msg("Input to response_body_schema must be a valid responses object.")
     })?;
 
     // 2. Try to find a successful response or use the first available one
-    let response = SUCCESS_STATUS_CODES
-        .iter()
-        .find_map(|code| responses.get(*code))
+    let response = success_response(responses)
+        .or_else(|| responses.get("default"))
         .or_else(|| responses.values().next());
 
-    let response = match response {
-        Some(resp) => resp,
-        None => return Ok(Value::Null),
+    let Some(content) = response.and_then(|response| response.get("content")) else {
+        return Ok(None);
     };
-
-    let content = response
-        .get("content")
-        .unwrap_or_else(|| return &Value::Null);
-
     if content.is_null() {
-        return Ok(Value::Null);
-    }
-
-    // 5. Try to find the schema for "application/json"
-    if let Some(schema_obj) = content
-        .get("application/json")
-        .and_then(|json_media_type| json_media_type.get("schema"))
-    {
-        return Ok(schema_obj.clone());
+        return Ok(None);
     }
 
-    // 6. Fallback: if there is no application/json, try the first available media type
-    if let Some(content_map) = content.as_object()
-        && let Some((_, media_type)) = content_map.iter().next()
-        && let Some(schema_obj) = media_type.get("schema")
-    {
-        return Ok(schema_obj.clone());
+    // 3. Take the schema of application/json, or else of the first media type
+    match media_type_schema(content) {
+        Some(schema) => Ok(Some(schema)),
+        None => Err(tera::Error::msg(
+            "Could not find a valid schema object within responses content (checked application/json and first available type).",
+        )),
     }
+}
 
-    // 7. Failure handling
-    Err(tera::Error::msg(
-        "Could not find a valid schema object within responses content (checked application/json and first available type).",
-    ))
+/// The response with the lowest 2xx status code, or the `2XX` range response (matched
+/// case-insensitively) if the spec declares no concrete success code.
+fn success_response(responses: &Map<String, Value>) -> Option<&Value> {
+    responses
+        .iter()
+        .filter_map(|(code, response)| {
+            code.parse::<u16>()
+                .ok()
+                .filter(|status| (200..300).contains(status))
+                .map(|status| (status, response))
+        })
+        .min_by_key(|(status, _)| *status)
+        .map(|(_, response)| response)
+        .or_else(|| {
+            responses
+                .iter()
+                .find(|(code, _)| code.eq_ignore_ascii_case("2XX"))
+                .map(|(_, response)| response)
+        })
 }
 
 #[cfg(test)]
@@ -422,4 +428,82 @@ mod tests {
 
         assert_eq!(ue_type.as_str().unwrap(), "FString");
     }
+
+    fn schema_of(responses: Value) -> Value {
+        response_body_schema_filter(&responses, &HashMap::new()).unwrap()
+    }
+
+    fn json_response(schema: Value) -> Value {
+        json!({ "content": { "application/json": { "schema": schema } } })
+    }
+
+    #[test]
+    fn test_response_body_schema_206_only() {
+        let responses = json!({
+            "404": json_response(json!({ "$ref": "#/components/schemas/Error" })),
+            "206": json_response(json!({ "type": "string", "format": "binary" }))
+        });
+
+        assert_eq!(schema_of(responses), json!({ "type": "string", "format": "binary" }));
+    }
+
+    #[test]
+    fn test_response_body_schema_prefers_lowest_2xx() {
+        let responses = json!({
+            "206": json_response(json!({ "type": "string" })),
+            "200": json_response(json!({ "type": "integer" }))
+        });
+
+        assert_eq!(schema_of(responses), json!({ "type": "integer" }));
+    }
+
+    #[test]
+    fn test_response_body_schema_2xx_wildcard() {
+        let responses = json!({
+            "400": json_response(json!({ "$ref": "#/components/schemas/Error" })),
+            "2XX": json_response(json!({ "$ref": "#/components/schemas/User" }))
+        });
+
+        assert_eq!(schema_of(responses), json!({ "$ref": "#/components/schemas/User" }));
+    }
+
+    #[test]
+    fn test_response_body_schema_2xx_wildcard_lowercase() {
+        let responses = json!({
+            "2xx": json_response(json!({ "type": "boolean" }))
+        });
+
+        assert_eq!(schema_of(responses), json!({ "type": "boolean" }));
+    }
+
+    #[test]
+    fn test_response_body_schema_default_only() {
+        let responses = json!({
+            "default": json_response(json!({ "$ref": "#/components/schemas/Result" }))
+        });
+
+        assert_eq!(schema_of(responses), json!({ "$ref": "#/components/schemas/Result" }));
+    }
+
+    #[test]
+    fn test_response_body_schema_default_before_other_statuses() {
+        // "404" would be the first key, but `default` is the better fallback
+        let responses = json!({
+            "404": json_response(json!({ "$ref": "#/components/schemas/NotFound" })),
+            "default": json_response(json!({ "$ref": "#/components/schemas/Result" }))
+        });
+
+        assert_eq!(schema_of(responses), json!({ "$ref": "#/components/schemas/Result" }));
+    }
+
+    #[test]
+    fn test_response_body_schema_prefers_200_over_2xx() {
+        let responses = json!({
+            "2XX": json_response(json!({ "type": "string" })),
+            "200": json_response(json!({ "type": "integer" })),
+            "default": json_response(json!({ "type": "boolean" }))
+        });
+
+        assert_eq!(schema_of(responses), json!({ "type": "integer" }));
+    }
 }
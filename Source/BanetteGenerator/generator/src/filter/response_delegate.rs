@@ -0,0 +1,173 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use crate::filter::path_to_func_name::path_to_func_name_filter;
+use crate::filter::response_body_schema::response_body_schema_filter;
+use crate::filter::to_ue_type::to_ue_type_filter;
+use crate::openapi::naming::FUNC_NAME_KEY;
+use std::collections::HashMap;
+use tera::{to_value, Result, Value};
+
+/// Tera filter to declare the dynamic delegate a callback-based client calls with an
+/// operation's response.
+///
+/// The delegate is named `FOn<FuncName>Response` after the operation's assigned function
+/// name, or, for an operation without one, after `path_to_func_name` of the `path` and
+/// `method` arguments. An operation with a success response body yields an
+/// `F<FuncName>ResponseType` alias of the body's C++ type and a delegate taking it:
+/// ```text
+/// using FGET_PetsResponseType = TArray<FPet>;
+/// DECLARE_DYNAMIC_DELEGATE_OneParam(FOnGET_PetsResponse, const FGET_PetsResponseType&, Response);
+/// ```
+/// so a type with a comma, such as a `TMap`, stays one macro argument. One without (e.g.
+/// `204 No Content`) yields a parameterless `DECLARE_DYNAMIC_DELEGATE(FOnDELETE_UsersResponse);`.
+///
+/// Usage in the template: {{ operation | response_delegate }}
+pub fn response_delegate_filter(value: &Value, args: &HashMap<String, Value>) -> Result<Value> {
+    // 1. Check if the input is an operation
+    let operation = value.as_object().ok_or_else(|| {
+        tera::Error::msg("response_delegate filter expects an operation object as input.")
+    })?;
+
+    // 2. Name the delegate after the operation's function
+    let func_name = match operation.get(FUNC_NAME_KEY).and_then(|v| v.as_str()) {
+        Some(name) => name.to_string(),
+        None => {
+            let path = args.get("path").ok_or_else(|| {
+                tera::Error::msg(
+                    "response_delegate filter expects a 'path' argument for an operation \
+                     without a function name.",
+                )
+            })?;
+            let mut name_args = HashMap::new();
+            if let Some(method) = args.get("method") {
+                name_args.insert("method".to_string(), method.clone());
+            }
+            if let Some(operation_id) = operation.get("operationId") {
+                name_args.insert("operation_id".to_string(), operation_id.clone());
+            }
+            let name = path_to_func_name_filter(path, &name_args)?;
+            name.as_str().unwrap_or_default().to_string()
+        }
+    };
+
+    // 3. Take the response body's type as the parameter, if there is a body
+    let schema = match operation.get("responses") {
+        Some(responses) => response_body_schema_filter(responses, &HashMap::new())?,
+        None => Value::Null,
+    };
+    let declaration = if schema.is_null() {
+        format!("DECLARE_DYNAMIC_DELEGATE(FOn{}Response);", func_name)
+    } else {
+        let ue_type = to_ue_type_filter(&schema, &HashMap::new())?;
+        format!(
+            "using F{0}ResponseType = {1};\n\
+             DECLARE_DYNAMIC_DELEGATE_OneParam(FOn{0}Response, const F{0}ResponseType&, Response);",
+            func_name,
+            ue_type.as_str().unwrap_or_default()
+        )
+    };
+
+    to_value(declaration)
+        .map_err(|e| tera::Error::msg(format!("Failed to convert string to Value: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filter::to_ue_type::UE_TYPE_KEY;
+    use serde_json::json;
+
+    fn delegate_of(operation: Value, args: &[(&str, &str)]) -> String {
+        let args = args.iter().map(|(key, value)| (key.to_string(), json!(value))).collect();
+        let result = response_delegate_filter(&operation, &args).unwrap();
+        result.as_str().unwrap().to_string()
+    }
+
+    fn json_response(schema: Value) -> Value {
+        json!({
+            "200": {
+                "description": "OK",
+                "content": { "application/json": { "schema": schema } }
+            }
+        })
+    }
+
+    #[test]
+    fn test_response_delegate_object_response() {
+        let operation = json!({
+            "x-banette-func-name": "GET_Users_By_Id",
+            "responses": json_response(json!({ "$ref": "#/components/schemas/User" }))
+        });
+
+        assert_eq!(
+            delegate_of(operation, &[]),
+            "using FGET_Users_By_IdResponseType = FUser;\n\
+             DECLARE_DYNAMIC_DELEGATE_OneParam(FOnGET_Users_By_IdResponse, \
+             const FGET_Users_By_IdResponseType&, Response);"
+        );
+    }
+
+    #[test]
+    fn test_response_delegate_array_response() {
+        let operation = json!({
+            "responses": json_response(json!({
+                "type": "array",
+                "items": { "$ref": "#/components/schemas/User" }
+            }))
+        });
+
+        assert_eq!(
+            delegate_of(operation, &[("path", "/users"), ("method", "get")]),
+            "using FGET_UsersResponseType = TArray<FUser>;\n\
+             DECLARE_DYNAMIC_DELEGATE_OneParam(FOnGET_UsersResponse, \
+             const FGET_UsersResponseType&, Response);"
+        );
+    }
+
+    #[test]
+    fn test_response_delegate_map_response() {
+        // The comma in the map type must not reach the macro's argument list
+        let operation = json!({
+            "x-banette-func-name": "GET_Scores",
+            "responses": json_response(json!({
+                "type": "object",
+                UE_TYPE_KEY: "TMap<FString, int32>"
+            }))
+        });
+
+        assert_eq!(
+            delegate_of(operation, &[]),
+            "using FGET_ScoresResponseType = TMap<FString, int32>;\n\
+             DECLARE_DYNAMIC_DELEGATE_OneParam(FOnGET_ScoresResponse, \
+             const FGET_ScoresResponseType&, Response);"
+        );
+    }
+
+    #[test]
+    fn test_response_delegate_no_content() {
+        let operation = json!({
+            "x-banette-func-name": "DELETE_Users_By_Id",
+            "responses": { "204": { "description": "Deleted" } }
+        });
+
+        assert_eq!(
+            delegate_of(operation, &[]),
+            "DECLARE_DYNAMIC_DELEGATE(FOnDELETE_Users_By_IdResponse);"
+        );
+        assert_eq!(
+            delegate_of(json!({ "operationId": "ping" }), &[("path", "/ping"), ("method", "get")]),
+            "DECLARE_DYNAMIC_DELEGATE(FOnPingResponse);"
+        );
+    }
+
+    #[test]
+    fn test_response_delegate_invalid_input() {
+        let result = response_delegate_filter(&json!("operation"), &HashMap::new());
+        assert!(result.unwrap_err().to_string().contains("expects an operation object"));
+
+        let result = response_delegate_filter(&json!({ "responses": {} }), &HashMap::new());
+        assert!(result.unwrap_err().to_string().contains("expects a 'path' argument"));
+    }
+}
@@ -0,0 +1,67 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use std::collections::HashMap;
+use tera::{to_value, Result, Value};
+
+/// Tera filter to list the status codes an OpenAPI responses object declares.
+///
+/// Returns the codes as strings in ascending order, with ranges such as `4XX` after the
+/// codes they cover and `default` always last, e.g. `["200", "404", "4XX", "default"]`.
+///
+/// Usage in the template: {% for code in operation.responses | response_status_codes %}
+pub fn response_status_codes_filter(
+    value: &Value,
+    _args: &HashMap<String, Value>,
+) -> Result<Value> {
+    let responses = value.as_object().ok_or_else(|| {
+        tera::Error::msg("Input to response_status_codes must be a valid responses object.")
+    })?;
+
+    let mut codes: Vec<&String> = responses.keys().collect();
+    codes.sort_by_key(|code| (code.as_str() == "default", code.as_str()));
+
+    to_value(codes)
+        .map_err(|e| tera::Error::msg(format!("Failed to convert array to Value: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn codes(responses: Value) -> Value {
+        response_status_codes_filter(&responses, &HashMap::new()).unwrap()
+    }
+
+    #[test]
+    fn test_response_status_codes_mixed() {
+        let responses = json!({
+            "default": {},
+            "404": {},
+            "200": {},
+            "5XX": {},
+            "201": {}
+        });
+
+        assert_eq!(codes(responses), json!(["200", "201", "404", "5XX", "default"]));
+    }
+
+    #[test]
+    fn test_response_status_codes_single() {
+        assert_eq!(codes(json!({ "204": { "description": "No Content" } })), json!(["204"]));
+    }
+
+    #[test]
+    fn test_response_status_codes_empty() {
+        assert_eq!(codes(json!({})), json!([]));
+    }
+
+    #[test]
+    fn test_response_status_codes_invalid_input() {
+        let result = response_status_codes_filter(&json!([]), &HashMap::new());
+
+        assert!(result.unwrap_err().to_string().contains("valid responses object"));
+    }
+}
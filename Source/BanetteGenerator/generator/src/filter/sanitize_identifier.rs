@@ -0,0 +1,143 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use std::collections::HashMap;
+use tera::{to_value, Map, Result, Value};
+
+/// C++ keywords and alternative tokens that cannot be used as identifiers.
+const CPP_KEYWORDS: &[&str] = &[
+    "alignas", "alignof", "and", "and_eq", "asm", "auto", "bitand", "bitor", "bool", "break",
+    "case", "catch", "char", "char8_t", "char16_t", "char32_t", "class", "compl", "concept",
+    "const", "consteval", "constexpr", "constinit", "const_cast", "continue", "co_await",
+    "co_return", "co_yield", "decltype", "default", "delete", "do", "double", "dynamic_cast",
+    "else", "enum", "explicit", "export", "extern", "false", "float", "for", "friend", "goto",
+    "if", "inline", "int", "long", "mutable", "namespace", "new", "noexcept", "not", "not_eq",
+    "nullptr", "operator", "or", "or_eq", "private", "protected", "public", "register",
+    "reinterpret_cast", "requires", "return", "short", "signed", "sizeof", "static",
+    "static_assert", "static_cast", "struct", "switch", "template", "this", "thread_local",
+    "throw", "true", "try", "typedef", "typeid", "typename", "union", "unsigned", "using",
+    "virtual", "void", "volatile", "wchar_t", "while", "xor", "xor_eq",
+];
+
+/// Rewrites `name` into a valid C++ identifier.
+///
+/// Characters other than ASCII letters, digits, and `_` become `_`, a leading digit is
+/// prefixed with `_`, and a reserved word gets a trailing `_`. Valid names are returned
+/// unchanged, so applying this twice gives the same result as applying it once.
+pub fn sanitize_identifier(name: &str) -> String {
+    let mut identifier: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '_' { c } else { '_' })
+        .collect();
+
+    if identifier.is_empty() || identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        identifier.insert(0, '_');
+    }
+    if CPP_KEYWORDS.contains(&identifier.as_str()) {
+        identifier.push('_');
+    }
+    identifier
+}
+
+/// Tera filter to turn a property or parameter name into a valid C++ identifier.
+///
+/// See [`sanitize_identifier`] for the rules.
+///
+/// Usage in the template: {{ prop_name | sanitize_identifier }}
+pub fn sanitize_identifier_filter(value: &Value, _args: &HashMap<String, Value>) -> Result<Value> {
+    let name = value.as_str().ok_or_else(|| {
+        tera::Error::msg("sanitize_identifier filter expects a name as input string.")
+    })?;
+
+    to_value(sanitize_identifier(name))
+        .map_err(|e| tera::Error::msg(format!("Failed to convert string to Value: {}", e)))
+}
+
+/// Tera filter to map sanitized identifiers back to their original JSON keys.
+///
+/// Takes a `properties` object (or an array of names) and returns an object from each
+/// identifier [`sanitize_identifier`] changed to the original key, so the generated code
+/// can still marshal the key as it appears on the wire. Unchanged names are omitted.
+///
+/// Usage in the template: {% for field, key in schema.properties | identifier_mapping %}
+pub fn identifier_mapping_filter(value: &Value, _args: &HashMap<String, Value>) -> Result<Value> {
+    let names: Vec<&str> = match value {
+        Value::Object(properties) => properties.keys().map(String::as_str).collect(),
+        Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+        _ => {
+            return Err(tera::Error::msg(
+                "identifier_mapping filter expects a properties object or an array of names.",
+            ));
+        }
+    };
+
+    let mut mapping = Map::new();
+    for name in names {
+        let identifier = sanitize_identifier(name);
+        if identifier != name {
+            mapping.insert(identifier, Value::String(name.to_string()));
+        }
+    }
+
+    Ok(Value::Object(mapping))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_sanitize_identifier_keywords() {
+        assert_eq!(sanitize_identifier("class"), "class_");
+        assert_eq!(sanitize_identifier("operator"), "operator_");
+        assert_eq!(sanitize_identifier("Class"), "Class");
+    }
+
+    #[test]
+    fn test_sanitize_identifier_digit_leading() {
+        assert_eq!(sanitize_identifier("2fa"), "_2fa");
+        assert_eq!(sanitize_identifier(""), "_");
+    }
+
+    #[test]
+    fn test_sanitize_identifier_illegal_characters() {
+        assert_eq!(sanitize_identifier("user.name"), "user_name");
+        assert_eq!(sanitize_identifier("x-request-id"), "x_request_id");
+        assert_eq!(sanitize_identifier("$ref"), "_ref");
+        assert_eq!(sanitize_identifier("valid_Name1"), "valid_Name1");
+    }
+
+    #[test]
+    fn test_sanitize_identifier_idempotent() {
+        for name in ["class", "2fa", "user.name", "", "new-", "delete"] {
+            let once = sanitize_identifier(name);
+            assert_eq!(sanitize_identifier(&once), once);
+        }
+    }
+
+    #[test]
+    fn test_sanitize_identifier_filter() {
+        let result = sanitize_identifier_filter(&json!("class"), &HashMap::new()).unwrap();
+        assert_eq!(result.as_str().unwrap(), "class_");
+
+        assert!(sanitize_identifier_filter(&json!(1), &HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn test_identifier_mapping_filter() {
+        let properties = json!({
+            "class": { "type": "string" },
+            "user.name": { "type": "string" },
+            "age": { "type": "integer" }
+        });
+        let result = identifier_mapping_filter(&properties, &HashMap::new()).unwrap();
+
+        assert_eq!(result, json!({ "class_": "class", "user_name": "user.name" }));
+
+        let names = json!(["1st", "ok"]);
+        let result = identifier_mapping_filter(&names, &HashMap::new()).unwrap();
+        assert_eq!(result, json!({ "_1st": "1st" }));
+    }
+}
@@ -0,0 +1,133 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use crate::filter::to_ue_type::get_effective_type;
+use std::collections::HashMap;
+use tera::{to_value, Result, Value};
+
+/// The codegen path a schema takes. Exactly one applies to any schema object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SchemaKind {
+    Ref,
+    Array,
+    Object,
+    Primitive,
+}
+
+/// Classifies a schema the same way `to_ue_type` does: a `$ref` wins over `type`, a
+/// nullable type array counts as its non-null type, and anything else is an object.
+///
+/// Boolean schemas (`true`/`false`) have no kind.
+fn schema_kind(value: &Value, filter_name: &str) -> Result<Option<SchemaKind>> {
+    if value.is_boolean() {
+        return Ok(None);
+    }
+    if !value.is_object() {
+        return Err(tera::Error::msg(format!(
+            "{} filter expects a schema object as input.",
+            filter_name
+        )));
+    }
+
+    if value.get("$ref").and_then(|v| v.as_str()).is_some() {
+        return Ok(Some(SchemaKind::Ref));
+    }
+
+    let kind = match get_effective_type(value).as_str() {
+        "array" => SchemaKind::Array,
+        "string" | "integer" | "number" | "boolean" => SchemaKind::Primitive,
+        _ => SchemaKind::Object,
+    };
+    Ok(Some(kind))
+}
+
+fn is_kind(value: &Value, kind: SchemaKind, filter_name: &str) -> Result<Value> {
+    let matches = schema_kind(value, filter_name)? == Some(kind);
+
+    to_value(matches)
+        .map_err(|e| tera::Error::msg(format!("Failed to convert bool to Value: {}", e)))
+}
+
+/// Tera filter to check if a schema is a `$ref` to another schema.
+///
+/// Usage in the template: {% if prop_schema | is_ref %}
+pub fn is_ref_filter(value: &Value, _args: &HashMap<String, Value>) -> Result<Value> {
+    is_kind(value, SchemaKind::Ref, "is_ref")
+}
+
+/// Tera filter to check if a schema is an array, including a nullable one.
+///
+/// Usage in the template: {% if prop_schema | is_array %}
+pub fn is_array_filter(value: &Value, _args: &HashMap<String, Value>) -> Result<Value> {
+    is_kind(value, SchemaKind::Array, "is_array")
+}
+
+/// Tera filter to check if a schema is an inline object (or has no single concrete type).
+///
+/// Usage in the template: {% if prop_schema | is_object %}
+pub fn is_object_filter(value: &Value, _args: &HashMap<String, Value>) -> Result<Value> {
+    is_kind(value, SchemaKind::Object, "is_object")
+}
+
+/// Tera filter to check if a schema is a string, integer, number, or boolean.
+///
+/// Usage in the template: {% if prop_schema | is_primitive %}
+pub fn is_primitive_filter(value: &Value, _args: &HashMap<String, Value>) -> Result<Value> {
+    is_kind(value, SchemaKind::Primitive, "is_primitive")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Results of `[is_ref, is_array, is_object, is_primitive]` for `schema`.
+    fn kinds(schema: Value) -> [bool; 4] {
+        let args = HashMap::new();
+        [
+            is_ref_filter(&schema, &args),
+            is_array_filter(&schema, &args),
+            is_object_filter(&schema, &args),
+            is_primitive_filter(&schema, &args),
+        ]
+        .map(|result| result.unwrap().as_bool().unwrap())
+    }
+
+    #[test]
+    fn test_schema_kind_ref() {
+        assert_eq!(
+            kinds(json!({ "$ref": "#/components/schemas/User" })),
+            [true, false, false, false]
+        );
+    }
+
+    #[test]
+    fn test_schema_kind_array() {
+        let expected = [false, true, false, false];
+        assert_eq!(kinds(json!({ "type": "array", "items": {} })), expected);
+        assert_eq!(kinds(json!({ "type": ["array", "null"], "items": {} })), expected);
+    }
+
+    #[test]
+    fn test_schema_kind_object() {
+        let expected = [false, false, true, false];
+        assert_eq!(kinds(json!({ "type": "object", "properties": {} })), expected);
+        assert_eq!(kinds(json!({ "properties": {} })), expected);
+    }
+
+    #[test]
+    fn test_schema_kind_primitive() {
+        let expected = [false, false, false, true];
+        assert_eq!(kinds(json!({ "type": "string" })), expected);
+        assert_eq!(kinds(json!({ "type": ["null", "integer"] })), expected);
+    }
+
+    #[test]
+    fn test_schema_kind_boolean_schema_and_invalid_input() {
+        assert_eq!(kinds(json!(true)), [false; 4]);
+
+        let result = is_array_filter(&json!("array"), &HashMap::new());
+        assert!(result.unwrap_err().to_string().contains("is_array filter expects"));
+    }
+}
@@ -0,0 +1,143 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use serde_json::json;
+use std::collections::HashMap;
+use tera::{to_value, Result, Value};
+
+/// Tera filter to normalize the spec's `servers` into `{url, description, variables}` records.
+///
+/// `url` is kept as declared, so templated URLs such as `https://{region}.api.example.com`
+/// still carry their `{region}` placeholder. `variables` lists each server variable, by
+/// name, as `{name, default, enum, description}`, with `enum` empty when unrestricted.
+/// A missing or empty `servers` list yields the single server `/`, as OpenAPI specifies.
+///
+/// Usage in the template: {{ servers | default(value=[]) | servers }}
+pub fn servers_filter(value: &Value, _args: &HashMap<String, Value>) -> Result<Value> {
+    // 1. Check if the input is a servers array, treating null as absent
+    let servers = match value {
+        Value::Null => &Vec::new(),
+        Value::Array(servers) => servers,
+        _ => {
+            return Err(tera::Error::msg(
+                "servers filter expects an array of server objects.",
+            ));
+        }
+    };
+
+    // 2. Fall back to the default server
+    if servers.is_empty() {
+        return Ok(json!([{ "url": "/", "description": "", "variables": [] }]));
+    }
+
+    // 3. Normalize each server
+    let records = servers
+        .iter()
+        .map(|server| {
+            let url = server.get("url").and_then(|u| u.as_str()).ok_or_else(|| {
+                tera::Error::msg("servers filter expects every server to have a 'url' string.")
+            })?;
+            let description = server.get("description").and_then(|d| d.as_str());
+
+            let mut variables: Vec<Value> = server
+                .get("variables")
+                .and_then(|v| v.as_object())
+                .into_iter()
+                .flatten()
+                .map(|(name, variable)| {
+                    json!({
+                        "name": name,
+                        "default": variable.get("default").cloned().unwrap_or(json!("")),
+                        "enum": variable.get("enum").cloned().unwrap_or(json!([])),
+                        "description": variable.get("description").cloned().unwrap_or(json!("")),
+                    })
+                })
+                .collect();
+            variables.sort_by(|a, b| a["name"].as_str().cmp(&b["name"].as_str()));
+
+            Ok(json!({
+                "url": url,
+                "description": description.unwrap_or_default(),
+                "variables": variables,
+            }))
+        })
+        .collect::<Result<Vec<Value>>>()?;
+
+    to_value(records)
+        .map_err(|e| tera::Error::msg(format!("Failed to convert array to Value: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_servers_plain_server() {
+        let servers = json!([
+            { "url": "https://api.example.com/v1", "description": "Production" },
+            { "url": "/staging" }
+        ]);
+        let result = servers_filter(&servers, &HashMap::new()).unwrap();
+
+        assert_eq!(
+            result,
+            json!([
+                {
+                    "url": "https://api.example.com/v1",
+                    "description": "Production",
+                    "variables": []
+                },
+                { "url": "/staging", "description": "", "variables": [] }
+            ])
+        );
+    }
+
+    #[test]
+    fn test_servers_templated_server() {
+        let servers = json!([{
+            "url": "https://{region}.api.example.com:{port}",
+            "variables": {
+                "region": {
+                    "default": "eu",
+                    "enum": ["eu", "us"],
+                    "description": "Deployment region"
+                },
+                "port": { "default": "443" }
+            }
+        }]);
+        let result = servers_filter(&servers, &HashMap::new()).unwrap();
+
+        assert_eq!(result[0]["url"], "https://{region}.api.example.com:{port}");
+        assert_eq!(
+            result[0]["variables"],
+            json!([
+                { "name": "port", "default": "443", "enum": [], "description": "" },
+                {
+                    "name": "region",
+                    "default": "eu",
+                    "enum": ["eu", "us"],
+                    "description": "Deployment region"
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn test_servers_absent() {
+        let expected = json!([{ "url": "/", "description": "", "variables": [] }]);
+
+        for absent in [json!([]), Value::Null] {
+            assert_eq!(servers_filter(&absent, &HashMap::new()).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_servers_invalid_input() {
+        let result = servers_filter(&json!("https://api.example.com"), &HashMap::new());
+        assert!(result.unwrap_err().to_string().contains("expects an array"));
+
+        let result = servers_filter(&json!([{ "description": "No URL" }]), &HashMap::new());
+        assert!(result.unwrap_err().to_string().contains("'url' string"));
+    }
+}
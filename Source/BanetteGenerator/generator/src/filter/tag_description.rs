@@ -0,0 +1,76 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use std::collections::HashMap;
+use tera::{to_value, Result, Value};
+
+/// Tera filter to look up a tag's `description` in the spec's top-level `tags`.
+///
+/// Takes the tag name as input and the `tags` array as an argument, and returns the
+/// matching tag's description, or an empty string if the tag is not listed or has none.
+///
+/// Usage in the template: {{ tag | tag_description(tags=tags | default(value=[])) }}
+pub fn tag_description_filter(value: &Value, args: &HashMap<String, Value>) -> Result<Value> {
+    // 1. Check if the input is a tag name
+    let name = value.as_str().ok_or_else(|| {
+        tera::Error::msg("tag_description filter expects a tag name as input string.")
+    })?;
+
+    // 2. Get the tags declared by the spec
+    let tags = args.get("tags").and_then(|v| v.as_array()).ok_or_else(|| {
+        tera::Error::msg("tag_description filter requires a 'tags' array argument.")
+    })?;
+
+    // 3. Find the tag and its description
+    let description = tags
+        .iter()
+        .find(|tag| tag.get("name").and_then(|n| n.as_str()) == Some(name))
+        .and_then(|tag| tag.get("description"))
+        .and_then(|d| d.as_str())
+        .unwrap_or_default();
+
+    to_value(description)
+        .map_err(|e| tera::Error::msg(format!("Failed to convert string to Value: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn describe(name: &str) -> String {
+        let mut args = HashMap::new();
+        args.insert(
+            "tags".to_string(),
+            json!([
+                { "name": "Character", "description": "Player characters." },
+                { "name": "Inventory" }
+            ]),
+        );
+        let result = tag_description_filter(&json!(name), &args).unwrap();
+        result.as_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_tag_description_matching_tag() {
+        assert_eq!(describe("Character"), "Player characters.");
+    }
+
+    #[test]
+    fn test_tag_description_without_description() {
+        assert_eq!(describe("Inventory"), "");
+    }
+
+    #[test]
+    fn test_tag_description_unknown_tag() {
+        assert_eq!(describe("Guild"), "");
+    }
+
+    #[test]
+    fn test_tag_description_missing_tags_argument() {
+        let result = tag_description_filter(&json!("Character"), &HashMap::new());
+
+        assert!(result.unwrap_err().to_string().contains("'tags' array argument"));
+    }
+}
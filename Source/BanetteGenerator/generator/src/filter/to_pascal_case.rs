@@ -0,0 +1,48 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use crate::filter::path_to_func_name::convert_to_pascal_case_with;
+use std::collections::HashMap;
+use tera::{to_value, Result, Value};
+
+/// Tera filter to convert an identifier to PascalCase.
+///
+/// Underscores and hyphens separate words and the first letter of every word is
+/// capitalized, e.g. `user_id` -> `UserId` and `userId` -> `UserId`.
+///
+/// Usage in the template: Get{{ field_name | to_pascal_case }}
+pub fn to_pascal_case_filter(value: &Value, _args: &HashMap<String, Value>) -> Result<Value> {
+    let input = value
+        .as_str()
+        .ok_or_else(|| tera::Error::msg("to_pascal_case filter expects a string as input."))?;
+
+    to_value(convert_to_pascal_case_with(input, &[]))
+        .map_err(|e| tera::Error::msg(format!("Failed to convert string to Value: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn pascal_of(input: &str) -> String {
+        let result = to_pascal_case_filter(&json!(input), &HashMap::new()).unwrap();
+        result.as_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn test_to_pascal_case() {
+        assert_eq!(pascal_of("user_id"), "UserId");
+        assert_eq!(pascal_of("userId"), "UserId");
+        assert_eq!(pascal_of("class_"), "Class");
+        assert_eq!(pascal_of("Name"), "Name");
+    }
+
+    #[test]
+    fn test_to_pascal_case_invalid_input() {
+        let result = to_pascal_case_filter(&json!(1), &HashMap::new());
+
+        assert!(result.unwrap_err().to_string().contains("expects a string"));
+    }
+}
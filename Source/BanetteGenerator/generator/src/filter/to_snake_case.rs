@@ -0,0 +1,118 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use std::collections::HashMap;
+use tera::{to_value, Result, Value};
+
+/// Tera filter to convert an identifier to snake_case.
+///
+/// Accepts camelCase, PascalCase, kebab-case, and snake_case input. A run of capitals is
+/// kept together as one word, ending before a capital that starts a lowercase word.
+///
+/// Examples:
+/// - `userId` -> `user_id`
+/// - `HTTPServerID` -> `http_server_id`
+/// - `resource-type` -> `resource_type`
+///
+/// Usage in the template: {{ prop_name | to_snake_case }}
+pub fn to_snake_case_filter(value: &Value, _args: &HashMap<String, Value>) -> Result<Value> {
+    let input = value
+        .as_str()
+        .ok_or_else(|| tera::Error::msg("to_snake_case filter expects a string as input."))?;
+
+    to_value(to_snake_case(input))
+        .map_err(|e| tera::Error::msg(format!("Failed to convert string to Value: {}", e)))
+}
+
+/// Converts `input` to snake_case. See [`to_snake_case_filter`].
+pub fn to_snake_case(input: &str) -> String {
+    split_words(input)
+        .iter()
+        .map(|word| word.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// Splits an identifier into its words, keeping their original casing.
+///
+/// Non-alphanumeric characters separate words, and so does a capital following a
+/// lowercase letter or digit. A run of capitals stays one word up to the capital that
+/// starts a lowercase word, e.g. `HTTPServerID` -> `HTTP`, `Server`, `ID`.
+pub fn split_words(input: &str) -> Vec<String> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut words: Vec<String> = Vec::new();
+    let mut current = String::new();
+
+    for (i, &ch) in chars.iter().enumerate() {
+        // 1. Separators end the current word
+        if !ch.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+
+        // 2. A capital starts a new word after a lowercase letter or digit (`userId`),
+        //    or ends an acronym run when a lowercase letter follows (`HTTPServer`)
+        if ch.is_uppercase() && !current.is_empty() {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|c| c.is_lowercase());
+            if !prev.is_uppercase() || next_is_lower {
+                words.push(std::mem::take(&mut current));
+            }
+        }
+
+        current.push(ch);
+    }
+
+    if !current.is_empty() {
+        words.push(current);
+    }
+
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_to_snake_case_styles() {
+        assert_eq!(to_snake_case("userId"), "user_id");
+        assert_eq!(to_snake_case("UserProfile"), "user_profile");
+        assert_eq!(to_snake_case("resource-type-id"), "resource_type_id");
+        assert_eq!(to_snake_case("v2Items"), "v2_items");
+    }
+
+    #[test]
+    fn test_to_snake_case_acronyms() {
+        assert_eq!(to_snake_case("HTTPServerID"), "http_server_id");
+        assert_eq!(to_snake_case("userID"), "user_id");
+        assert_eq!(to_snake_case("XMLHttpRequest"), "xml_http_request");
+        assert_eq!(to_snake_case("URL"), "url");
+    }
+
+    #[test]
+    fn test_to_snake_case_already_snake() {
+        assert_eq!(to_snake_case("user_id"), "user_id");
+        assert_eq!(to_snake_case("http_server_id"), "http_server_id");
+        assert_eq!(to_snake_case(""), "");
+    }
+
+    #[test]
+    fn test_split_words() {
+        assert_eq!(split_words("HTTPServerID"), vec!["HTTP", "Server", "ID"]);
+        assert_eq!(split_words("api_key"), vec!["api", "key"]);
+    }
+
+    #[test]
+    fn test_to_snake_case_filter() {
+        let result = to_snake_case_filter(&json!("createdAt"), &HashMap::new()).unwrap();
+        assert_eq!(result, json!("created_at"));
+
+        let result = to_snake_case_filter(&json!(1), &HashMap::new());
+        assert!(result.unwrap_err().to_string().contains("expects a string"));
+    }
+}
@@ -2,11 +2,51 @@
  * Copyright 2019-Present tarnishablec. All Rights Reserved.
  */
 
+use crate::filter::integer_enum::integer_enum_storage;
 use std::collections::HashMap;
-use tera::{to_value, Result, Value};
+use tera::{to_value, Map, Result, Value};
 
-pub fn to_ue_type_filter(value: &Value, _args: &HashMap<String, Value>) -> Result<Value> {
-    fn get_cpp_type(schema: &Value) -> String {
+/// Set next to a `$ref` whose target is generated as a `UENUM` rather than a `USTRUCT`.
+pub const ENUM_REF_KEY: &str = "x-banette-enum";
+
+/// Set next to a `$ref` to an integer enum without a `0` value, naming the enumerator its
+/// holders start out as, e.g. `EVersion::Current` (see
+/// [`integer_enum_default`](crate::filter::integer_enum::integer_enum_default)).
+pub const ENUM_DEFAULT_KEY: &str = "x-banette-enum-default";
+
+/// Set next to a `$ref` that closes a reference cycle, which is held through a `TSharedPtr`.
+pub const CYCLE_REF_KEY: &str = "x-banette-cycle-ref";
+
+/// Set on a schema to override the type it maps to, such as the type chosen for its
+/// `format` with [`GenerateOptions::format_types`](crate::openapi::GenerateOptions::format_types).
+pub const UE_TYPE_KEY: &str = "x-banette-ue-type";
+
+/// Set on a schema inlined in place of a `$ref` (see
+/// [`dereference`](crate::openapi::deref::dereference)), holding that `$ref`.
+pub const INLINED_REF_KEY: &str = "x-banette-inlined-ref";
+
+/// The reference `schema` stands for: its `$ref`, or the one it was inlined from.
+pub fn schema_ref(schema: &Value) -> Option<&str> {
+    schema
+        .get("$ref")
+        .or_else(|| schema.get(INLINED_REF_KEY))
+        .and_then(|v| v.as_str())
+}
+
+/// Tera filter to map a schema to the Unreal C++ type that holds it.
+///
+/// A schema carrying [`UE_TYPE_KEY`] maps to that type. Otherwise a `$ref`, or a schema
+/// inlined from one ([`INLINED_REF_KEY`]), is named
+/// `E<Name>` when it carries [`ENUM_REF_KEY`], is a `TSharedPtr<F<Name>>` when it carries
+/// [`CYCLE_REF_KEY`] and `F<Name>` when neither, except that one the
+/// optional `components` argument (the spec's `components` object) resolves to an integer
+/// enum maps to the integer type the enum is stored in, like
+/// [`mark_enum_refs`](crate::openapi::enums::mark_enum_refs) marks it. A `string` is an
+/// `FString` whatever its `format`, so `decimal` amounts stay exact text.
+///
+/// Usage in the template: {{ prop_schema | to_ue_type(components=components) }}
+pub fn to_ue_type_filter(value: &Value, args: &HashMap<String, Value>) -> Result<Value> {
+    fn get_cpp_type(schema: &Value, schemas: Option<&Map<String, Value>>) -> String {
         // 1. Handle boolean Schema (true/false)
         if let Some(is_any) = schema.as_bool() {
             return if is_any {
@@ -16,14 +56,30 @@ pub fn to_ue_type_filter(value: &Value, _args: &HashMap<String, Value>) -> Resul
             };
         }
 
-        // 2. Handle $ref references
+        // 2. Use the type chosen for the schema, such as for its format, if any
+        if let Some(ue_type) = schema.get(UE_TYPE_KEY).and_then(|v| v.as_str()) {
+            return ue_type.to_string();
+        }
+
+        // 3. Handle $ref references
         // If $ref exists, return the corresponding struct name directly; no need to recurse further
-        if let Some(ref_path) = schema.get("$ref").and_then(|v| v.as_str()) {
+        if let Some(ref_path) = schema_ref(schema) {
             let struct_name = ref_path.split('/').last().unwrap_or("Unknown");
-            return format!("F{}", struct_name);
+            let storage = ref_path
+                .strip_prefix("#/components/schemas/")
+                .and_then(|name| schemas?.get(name))
+                .and_then(integer_enum_storage);
+            if let Some(storage) = storage {
+                return storage.to_string();
+            }
+            if schema.get(CYCLE_REF_KEY).and_then(|v| v.as_bool()) == Some(true) {
+                return format!("TSharedPtr<F{}>", struct_name);
+            }
+            let is_enum = schema.get(ENUM_REF_KEY).and_then(|v| v.as_bool()) == Some(true);
+            return format!("{}{}", if is_enum { "E" } else { "F" }, struct_name);
         }
 
-        // 3. Get the type string, handling nullable types (arrays with "null")
+        // 4. Get the type string, handling nullable types (arrays with "null")
         let type_str = get_effective_type(schema);
 
         match type_str.as_str() {
@@ -44,7 +100,7 @@ pub fn to_ue_type_filter(value: &Value, _args: &HashMap<String, Value>) -> Resul
                 // Get the 'items' field
                 if let Some(items) = schema.get("items") {
                     // Recursively call itself to get the inner type
-                    let inner_type = get_cpp_type(items);
+                    let inner_type = get_cpp_type(items, schemas);
                     format!("TArray<{}>", inner_type)
                 } else {
                     // If it's an array without 'items' defined, assume an array of any type
@@ -56,40 +112,58 @@ pub fn to_ue_type_filter(value: &Value, _args: &HashMap<String, Value>) -> Resul
         }
     }
 
-    /// Extracts the effective type string from the schema.
-    /// Handles nullable types where `type` is an array containing a concrete type and "null".
-    /// Returns the non-null concrete type, or falls back to "object" if none is found.
-    fn get_effective_type(schema: &Value) -> String {
-        if let Some(type_value) = schema.get("type") {
-            // Handle case where the type is a simple string
-            if let Some(type_str) = type_value.as_str() {
-                return type_str.to_string();
-            }
+    let schemas = args
+        .get("components")
+        .and_then(|components| components.get("schemas"))
+        .and_then(|schemas| schemas.as_object());
+    let result = get_cpp_type(value, schemas);
+    Ok(to_value(result)?)
+}
+
+/// Extracts the effective type string from the schema.
+/// Handles nullable types where `type` is an array containing a concrete type and "null".
+/// Returns the non-null concrete type, else the type of the `const` value if the schema
+/// has one, or falls back to "object" if none is found.
+pub fn get_effective_type(schema: &Value) -> String {
+    if let Some(type_value) = schema.get("type") {
+        // Handle case where the type is a simple string
+        if let Some(type_str) = type_value.as_str() {
+            return type_str.to_string();
+        }
 
-            // Handle case where the type is an array (nullable types like ["integer", "null"])
-            if let Some(type_array) = type_value.as_array() {
-                // Filter out "null" and find the concrete type(s)
-                let mut concrete_types = type_array
-                    .iter()
-                    .filter_map(|v| v.as_str())
-                    .filter(|t| *t != "null");
-
-                // Get the first concrete type
-                if let Some(first) = concrete_types.next() {
-                    // If there's exactly one concrete type (no more after the first), use it
-                    if concrete_types.next().is_none() {
-                        return first.to_string();
-                    }
+        // Handle case where the type is an array (nullable types like ["integer", "null"])
+        if let Some(type_array) = type_value.as_array() {
+            // Filter out "null" and find the concrete type(s)
+            let mut concrete_types = type_array
+                .iter()
+                .filter_map(|v| v.as_str())
+                .filter(|t| *t != "null");
+
+            // Get the first concrete type
+            if let Some(first) = concrete_types.next() {
+                // If there's exactly one concrete type (no more after the first), use it
+                if concrete_types.next().is_none() {
+                    return first.to_string();
                 }
             }
         }
+    }
 
-        // Default to "object" if no valid type is found
-        "object".to_string()
+    // Infer the type from a `const` value
+    let const_type = match schema.get("const") {
+        Some(Value::String(_)) => Some("string"),
+        Some(Value::Number(number)) if number.is_f64() => Some("number"),
+        Some(Value::Number(_)) => Some("integer"),
+        Some(Value::Bool(_)) => Some("boolean"),
+        Some(Value::Array(_)) => Some("array"),
+        _ => None,
+    };
+    if let Some(const_type) = const_type {
+        return const_type.to_string();
     }
 
-    let result = get_cpp_type(value);
-    Ok(to_value(result)?)
+    // Default to "object" if no valid type is found
+    "object".to_string()
 }
 
 #[cfg(test)]
@@ -98,6 +172,17 @@ mod tests {
     use serde_json::json;
     use tera::to_value;
 
+    #[test]
+    fn test_to_ue_type_decimal() {
+        let schema = json!({"type": "string", "format": "decimal"});
+        let result = to_ue_type_filter(&schema, &HashMap::new()).unwrap();
+        assert_eq!(result, to_value("FString").unwrap());
+
+        let schema = json!({"type": "string", "format": "decimal", UE_TYPE_KEY: "FDecimal"});
+        let result = to_ue_type_filter(&schema, &HashMap::new()).unwrap();
+        assert_eq!(result, to_value("FDecimal").unwrap());
+    }
+
     #[test]
     fn test_to_ue_type_string() {
         let schema = json!({"type": "string"});
@@ -189,6 +274,32 @@ mod tests {
         assert_eq!(result.as_str().unwrap(), "FUser");
     }
 
+    #[test]
+    fn test_to_ue_type_enum_ref() {
+        let schema = json!({"$ref": "#/components/schemas/Priority", "x-banette-enum": true});
+        let value = to_value(&schema).unwrap();
+        let result = to_ue_type_filter(&value, &HashMap::new()).unwrap();
+        assert_eq!(result.as_str().unwrap(), "EPriority");
+    }
+
+    #[test]
+    fn test_to_ue_type_integer_enum_ref_storage() {
+        let schema = json!({
+            "$ref": "#/components/schemas/Priority",
+            "x-banette-enum": true,
+            "x-banette-ue-type": "int32"
+        });
+        let result = to_ue_type_filter(&schema, &HashMap::new()).unwrap();
+        assert_eq!(result.as_str().unwrap(), "int32");
+    }
+
+    #[test]
+    fn test_to_ue_type_cycle_ref() {
+        let schema = json!({"$ref": "#/components/schemas/Node", "x-banette-cycle-ref": true});
+        let result = to_ue_type_filter(&schema, &HashMap::new()).unwrap();
+        assert_eq!(result.as_str().unwrap(), "TSharedPtr<FNode>");
+    }
+
     #[test]
     fn test_to_ue_type_boolean_schema_true() {
         let value = to_value(true).unwrap();
@@ -284,6 +395,95 @@ mod tests {
         assert_eq!(result.as_str().unwrap(), "TArray<FString>");
     }
 
+    #[test]
+    fn test_to_ue_type_triple_nested_array() {
+        let schema = json!({
+            "type": "array",
+            "items": {
+                "type": "array",
+                "items": {"type": "array", "items": {"type": "integer"}}
+            }
+        });
+        let value = to_value(&schema).unwrap();
+        let result = to_ue_type_filter(&value, &HashMap::new()).unwrap();
+        assert_eq!(result.as_str().unwrap(), "TArray<TArray<TArray<int32>>>");
+    }
+
+    #[test]
+    fn test_to_ue_type_nullable_array_of_arrays() {
+        // Both the outer and the inner array are nullable
+        let schema = json!({
+            "type": ["array", "null"],
+            "items": {
+                "type": ["null", "array"],
+                "items": {"type": ["string", "null"]}
+            }
+        });
+        let value = to_value(&schema).unwrap();
+        let result = to_ue_type_filter(&value, &HashMap::new()).unwrap();
+        assert_eq!(result.as_str().unwrap(), "TArray<TArray<FString>>");
+    }
+
+    #[test]
+    fn test_to_ue_type_ref_resolved_through_components() {
+        let components = json!({
+            "schemas": {
+                "Color": {"type": "integer", "enum": [0, 1, 2]},
+                "Palette": {"type": "object", "properties": {"name": {"type": "string"}}}
+            }
+        });
+        let args = HashMap::from([("components".to_string(), components)]);
+
+        let enum_items = json!({"type": "array", "items": {"$ref": "#/components/schemas/Color"}});
+        let result = to_ue_type_filter(&enum_items, &args).unwrap();
+        assert_eq!(result.as_str().unwrap(), "TArray<uint8>");
+
+        let object_ref = json!({"$ref": "#/components/schemas/Palette"});
+        let result = to_ue_type_filter(&object_ref, &args).unwrap();
+        assert_eq!(result.as_str().unwrap(), "FPalette");
+
+        // Without components the ref is assumed to be a struct
+        let result = to_ue_type_filter(&enum_items, &HashMap::new()).unwrap();
+        assert_eq!(result.as_str().unwrap(), "TArray<FColor>");
+    }
+
+    #[test]
+    fn test_to_ue_type_string_const() {
+        let schema = json!({"const": "fixed"});
+        let result = to_ue_type_filter(&schema, &HashMap::new()).unwrap();
+        assert_eq!(result.as_str().unwrap(), "FString");
+    }
+
+    #[test]
+    fn test_to_ue_type_integer_const() {
+        let schema = json!({"const": 3});
+        let result = to_ue_type_filter(&schema, &HashMap::new()).unwrap();
+        assert_eq!(result.as_str().unwrap(), "int32");
+
+        let schema = json!({"const": 0.5});
+        let result = to_ue_type_filter(&schema, &HashMap::new()).unwrap();
+        assert_eq!(result.as_str().unwrap(), "float");
+    }
+
+    #[test]
+    fn test_to_ue_type_const_with_type() {
+        // An explicit type wins over the const's own
+        let schema = json!({"type": "integer", "format": "int64", "const": 7});
+        let result = to_ue_type_filter(&schema, &HashMap::new()).unwrap();
+        assert_eq!(result.as_str().unwrap(), "int64");
+
+        let schema = json!({"type": "string", "const": 7});
+        let result = to_ue_type_filter(&schema, &HashMap::new()).unwrap();
+        assert_eq!(result.as_str().unwrap(), "FString");
+
+        // A component integer const is a one-variant enum, stored as its number
+        let components = json!({"schemas": {"Version": {"type": "integer", "const": 2}}});
+        let args = HashMap::from([("components".to_string(), components)]);
+        let schema = json!({"$ref": "#/components/schemas/Version"});
+        let result = to_ue_type_filter(&schema, &args).unwrap();
+        assert_eq!(result.as_str().unwrap(), "uint8");
+    }
+
     #[test]
     fn test_to_ue_type_null_first_in_array() {
         // OpenAPI nullable type with null first: ["null", "integer"]
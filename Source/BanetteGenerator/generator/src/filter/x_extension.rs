@@ -0,0 +1,83 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use std::collections::HashMap;
+use tera::{Result, Value};
+
+/// Tera filter to read a vendor extension (`x-*` field) of any spec object.
+///
+/// Takes the extension name as the `key` argument, with or without its `x-` prefix, and
+/// returns the extension's value as is, e.g. a string or bool. When the object does not
+/// have the extension (or it is `null`), an empty string is returned, so the result can be
+/// tested with `if`.
+///
+/// Usage in the template: {% set category = property | x_extension(key="x-ue-category") %}
+pub fn x_extension_filter(value: &Value, args: &HashMap<String, Value>) -> Result<Value> {
+    // 1. Check the object and the extension name
+    let object = value
+        .as_object()
+        .ok_or_else(|| tera::Error::msg("x_extension filter expects an object as input."))?;
+    let key = args
+        .get("key")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|key| !key.is_empty())
+        .ok_or_else(|| tera::Error::msg("x_extension filter expects a 'key' string argument."))?;
+
+    // 2. Look the extension up under its full name
+    let name = if key.starts_with("x-") {
+        key.to_string()
+    } else {
+        format!("x-{}", key)
+    };
+    match object.get(&name) {
+        Some(Value::Null) | None => Ok(Value::String(String::new())),
+        Some(extension) => Ok(extension.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn extension_of(value: Value, key: &str) -> Value {
+        let args = HashMap::from([("key".to_string(), json!(key))]);
+        x_extension_filter(&value, &args).unwrap()
+    }
+
+    #[test]
+    fn test_x_extension_string() {
+        let property = json!({ "type": "string", "x-ue-category": "Stats|Combat" });
+
+        assert_eq!(extension_of(property.clone(), "x-ue-category"), json!("Stats|Combat"));
+        assert_eq!(extension_of(property, "ue-category"), json!("Stats|Combat"));
+    }
+
+    #[test]
+    fn test_x_extension_bool() {
+        let property = json!({ "type": "integer", "x-ue-editable": false });
+
+        assert_eq!(extension_of(property, "x-ue-editable"), json!(false));
+        assert_eq!(extension_of(json!({ "x-ue-editable": true }), "x-ue-editable"), json!(true));
+    }
+
+    #[test]
+    fn test_x_extension_missing_key() {
+        let property = json!({ "type": "string", "ue-category": "not an extension" });
+
+        assert_eq!(extension_of(property, "x-ue-category"), json!(""));
+        assert_eq!(extension_of(json!({ "x-ue-category": null }), "x-ue-category"), json!(""));
+    }
+
+    #[test]
+    fn test_x_extension_invalid_input() {
+        let args = HashMap::from([("key".to_string(), json!("x-ue-category"))]);
+        let result = x_extension_filter(&json!("property"), &args);
+        assert!(result.unwrap_err().to_string().contains("expects an object"));
+
+        let result = x_extension_filter(&json!({}), &HashMap::new());
+        assert!(result.unwrap_err().to_string().contains("expects a 'key' string"));
+    }
+}
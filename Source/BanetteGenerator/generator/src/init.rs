@@ -0,0 +1,115 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use crate::config::CONFIG_FILE_NAME;
+use anyhow::{bail, Context, Result};
+use generator::openapi::engine::{
+    BUILTIN_SOURCE_TEMPLATE, BUILTIN_SOURCE_TEMPLATE_NAME, BUILTIN_TEMPLATE, BUILTIN_TEMPLATE_NAME,
+};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory, relative to the config, that `init` copies the built-in templates into.
+pub const TEMPLATES_DIR: &str = "templates";
+
+/// Starter `banette.toml`, its values being placeholders to replace.
+const STARTER_CONFIG: &str = r#"# Defaults for the generator's inputs, overridden by command line flags.
+
+# Spec file path or HTTP(S) URL
+path = "openapi.json"
+output_dir = "Source/MyModule/Public/Generated"
+file_name = "Api.h"
+# Export macro of the Unreal module the header is generated into
+module_name = "MYMODULE_API"
+# Extra `#include`s for the generated header, separated by `;`
+extra_headers = ""
+# Header template, copied from the built-in one by `init` to be customized
+template = "templates/openapi.h.tera"
+# `.cpp` template used with --source, copied the same way
+source_template = "templates/openapi.cpp.tera"
+"#;
+
+/// Writes a starter `banette.toml` and copies of the built-in header and `.cpp` templates
+/// under `templates/` into `dir`, which the config's `template` and `source_template`
+/// point generation at.
+///
+/// Fails without writing anything if any of the files exists, unless `force` is set. Returns
+/// the files written.
+pub fn init(dir: &Path, force: bool) -> Result<Vec<PathBuf>> {
+    let config_path = dir.join(CONFIG_FILE_NAME);
+    let template_path = dir.join(TEMPLATES_DIR).join(BUILTIN_TEMPLATE_NAME);
+    let source_template_path = dir.join(TEMPLATES_DIR).join(BUILTIN_SOURCE_TEMPLATE_NAME);
+
+    let files = [
+        (config_path, STARTER_CONFIG),
+        (template_path, BUILTIN_TEMPLATE),
+        (source_template_path, BUILTIN_SOURCE_TEMPLATE),
+    ];
+    if !force && let Some((existing, _)) = files.iter().find(|(path, _)| path.exists()) {
+        bail!("{} already exists; pass --force to overwrite it", existing.display());
+    }
+
+    files
+        .into_iter()
+        .map(|(path, contents)| {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create {}", parent.display()))?;
+            }
+            fs::write(&path, contents)
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            Ok(path)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    fn init_dir(dir_name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(dir_name);
+        fs::remove_dir_all(&dir).ok();
+        dir
+    }
+
+    #[test]
+    fn test_init_writes_config_and_template() {
+        let dir = init_dir("banette_init");
+
+        let files = init(&dir, false).unwrap();
+
+        let template_path = dir.join("templates/openapi.h.tera");
+        let source_template_path = dir.join("templates/openapi.cpp.tera");
+        assert_eq!(
+            files,
+            [dir.join("banette.toml"), template_path.clone(), source_template_path.clone()]
+        );
+        assert_eq!(fs::read_to_string(&template_path).unwrap(), BUILTIN_TEMPLATE);
+        assert_eq!(fs::read_to_string(&source_template_path).unwrap(), BUILTIN_SOURCE_TEMPLATE);
+
+        // The config points at the copies wherever it is loaded from
+        let config = Config::load(&dir.join("banette.toml")).unwrap();
+        let spec_path = dir.join("openapi.json");
+        assert_eq!(config.path.as_deref(), Some(spec_path.to_string_lossy().as_ref()));
+        assert_eq!(config.module_name.as_deref(), Some("MYMODULE_API"));
+        assert_eq!(config.template, Some(template_path));
+        assert_eq!(config.source_template, Some(source_template_path));
+    }
+
+    #[test]
+    fn test_init_keeps_existing_files() {
+        let dir = init_dir("banette_init_existing");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("banette.toml"), "path = \"mine.json\"\n").unwrap();
+
+        let error = init(&dir, false).unwrap_err();
+        assert!(error.to_string().contains("already exists; pass --force"));
+        assert!(!dir.join("templates").exists());
+
+        init(&dir, true).unwrap();
+        assert_eq!(fs::read_to_string(dir.join("banette.toml")).unwrap(), STARTER_CONFIG);
+    }
+}
@@ -4,6 +4,10 @@
 
 pub mod openapi;
 pub mod filter;
+pub mod logging;
+
+pub use openapi::batch::{generate_dir, DirReport};
+pub use openapi::generate_to_string;
 // #[unsafe(no_mangle)]
 // pub extern "C" fn test(a: *const char) {
 //     println!("{:?}", a);
@@ -0,0 +1,137 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use std::ffi::{c_char, CString};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::RwLock;
+
+/// Receives generator diagnostics; `msg` is only valid for the duration of the call.
+pub type LogCallback = extern "C" fn(level: i32, msg: *const c_char);
+
+/// Severity passed to a [`LogCallback`].
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogLevel {
+    Info = 0,
+    Warning = 1,
+    Error = 2,
+    /// Detail for troubleshooting, only emitted at [`Verbosity::Verbose`].
+    Debug = 3,
+}
+
+/// Which diagnostics are emitted; errors always are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum Verbosity {
+    /// Errors only, so a successful run is silent.
+    Quiet,
+    /// Errors, warnings, and info.
+    #[default]
+    Normal,
+    /// Everything, including [`LogLevel::Debug`] messages.
+    Verbose,
+}
+
+impl Verbosity {
+    /// Whether messages of `level` are emitted at this verbosity.
+    pub fn allows(self, level: LogLevel) -> bool {
+        match level {
+            LogLevel::Error => true,
+            LogLevel::Warning | LogLevel::Info => self >= Verbosity::Normal,
+            LogLevel::Debug => self >= Verbosity::Verbose,
+        }
+    }
+}
+
+static CALLBACK: RwLock<Option<LogCallback>> = RwLock::new(None);
+static VERBOSITY: AtomicU8 = AtomicU8::new(Verbosity::Normal as u8);
+
+/// Routes all generator diagnostics through `callback`, or back to stdio when null.
+#[cbindgen_macro::namespace("banette::ffi::generator")]
+#[unsafe(no_mangle)]
+pub extern "C" fn banette_set_log_callback(callback: Option<LogCallback>) {
+    *CALLBACK.write().unwrap_or_else(|e| e.into_inner()) = callback;
+}
+
+/// Sets which diagnostics [`log`] emits from now on.
+pub fn set_verbosity(verbosity: Verbosity) {
+    VERBOSITY.store(verbosity as u8, Ordering::Relaxed);
+}
+
+/// The verbosity set by [`set_verbosity`], [`Verbosity::Normal`] by default.
+pub fn verbosity() -> Verbosity {
+    match VERBOSITY.load(Ordering::Relaxed) {
+        0 => Verbosity::Quiet,
+        2 => Verbosity::Verbose,
+        _ => Verbosity::Normal,
+    }
+}
+
+/// Whether messages of `level` are emitted, to skip building ones that would not be.
+pub fn enabled(level: LogLevel) -> bool {
+    verbosity().allows(level)
+}
+
+/// Emits a diagnostic to the registered callback, falling back to stdout/stderr, unless
+/// the [verbosity](set_verbosity) leaves it out.
+pub fn log(level: LogLevel, message: &str) {
+    if !enabled(level) {
+        return;
+    }
+    let callback = *CALLBACK.read().unwrap_or_else(|e| e.into_inner());
+
+    match callback {
+        Some(callback) => {
+            let message = CString::new(message.replace('\0', "")).unwrap_or_default();
+            callback(level as i32, message.as_ptr());
+        }
+        None if matches!(level, LogLevel::Info | LogLevel::Debug) => println!("[Rust] {}", message),
+        None => eprintln!("[Rust] {}", message),
+    }
+}
+
+pub fn debug(message: &str) {
+    log(LogLevel::Debug, message);
+}
+
+pub fn info(message: &str) {
+    log(LogLevel::Info, message);
+}
+
+pub fn warning(message: &str) {
+    log(LogLevel::Warning, message);
+}
+
+pub fn error(message: &str) {
+    log(LogLevel::Error, message);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verbosity_gates_levels() {
+        let emitted = |verbosity: Verbosity| {
+            [LogLevel::Debug, LogLevel::Info, LogLevel::Warning, LogLevel::Error]
+                .into_iter()
+                .filter(|&level| verbosity.allows(level))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(emitted(Verbosity::Quiet), [LogLevel::Error]);
+        assert_eq!(
+            emitted(Verbosity::Normal),
+            [LogLevel::Info, LogLevel::Warning, LogLevel::Error]
+        );
+        assert_eq!(
+            emitted(Verbosity::Verbose),
+            [LogLevel::Debug, LogLevel::Info, LogLevel::Warning, LogLevel::Error]
+        );
+    }
+
+    #[test]
+    fn test_verbosity_defaults_to_normal() {
+        assert_eq!(Verbosity::default(), Verbosity::Normal);
+    }
+}
@@ -2,42 +2,676 @@
  * Copyright 2019-Present tarnishablec. All Rights Reserved.
  */
 
-use clap::{Parser, ValueEnum};
+mod config;
+mod exit_status;
+mod init;
+
+use anyhow::anyhow;
+use clap::{Parser, Subcommand, ValueEnum};
+use config::Config;
+use exit_status::{ExitStatus, LintFailed, OutOfDate};
+use generator::filter::path_to_func_name::MIN_CAPPED_LEN;
+use generator::logging::{self, banette_set_log_callback, LogLevel, Verbosity};
+use generator::openapi::cache::{CacheOptions, DEFAULT_CACHE_TTL};
+use generator::openapi::format::DEFAULT_CLANG_FORMAT;
+use generator::openapi::loader::{LoadOptions, DEFAULT_MAX_REDIRECTS, DEFAULT_RETRIES};
+use generator::openapi::batch::{expand_glob, generate_glob, is_glob_pattern};
+use generator::openapi::engine::GeneratorEngine;
+use generator::openapi::output::{GeneratedFile, Generation, WriteOutcome};
+use generator::openapi::parser::{
+    parse_context_entry, parse_format_type, parse_include_headers_with, parse_spec_header,
+    IncludeOptions,
+};
+use generator::openapi::proxy::ProxyOptions;
+use generator::openapi::watch::{watch, WatchOptions, DEFAULT_POLL_INTERVAL};
+use generator::openapi::{
+    generate_with_engine, lint_spec_at, FieldAccessors, GenerateOptions, IncludeGuard,
+    LineEnding,
+};
+use std::ffi::{c_char, CStr};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::atomic::AtomicBool;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 enum Mode {
     Openapi,
     GraphQL,
 }
+#[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true, after_help = "\
+Exit codes:
+  0  Success, or a dry run found everything up to date
+  1  Any other failure
+  2  Invalid command line
+  3  The spec could not be read, fetched, or parsed
+  4  The template could not be rendered
+  5  The output could not be written
+  6  A dry run found generated files that would change
+  7  Lint found generation hazards (validate, --lint-strict)")]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Arguments of `generate`, which runs when no subcommand is given.
+    #[command(flatten)]
+    generate: Args,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Generate the header from the spec (the default).
+    Generate(Args),
+    /// Write a starter `banette.toml` and copies of the built-in templates to edit.
+    Init(InitArgs),
+    /// Check that the spec loads and has no generation hazards, without rendering or
+    /// writing anything.
+    Validate(Args),
+}
+
+#[derive(clap::Args)]
+struct InitArgs {
+    /// Directory to write `banette.toml` and `templates/` into.
+    #[arg(long, default_value = ".")]
+    dir: PathBuf,
+    /// Overwrite files that already exist.
+    #[arg(long)]
+    force: bool,
+}
+
 #[derive(Parser)]
 struct Args {
     #[arg(short, long, value_enum, default_value_t = Mode::Openapi)]
     mode: Mode,
+    /// Config file supplying the inputs below (default: `./banette.toml` if present).
+    #[arg(long, value_name = "PATH")]
+    config: Option<PathBuf>,
+    /// Spec file path, HTTP(S) URL, or `-` for standard input. Repeat to merge several
+    /// specs into one output. A glob such as `specs/**/*.yaml` generates a header per
+    /// matching file instead, named after the file.
+    #[arg(long)]
+    path: Vec<String>,
+    #[arg(long)]
+    output_dir: Option<String>,
+    #[arg(long)]
+    file_name: Option<String>,
+    #[arg(long)]
+    module_name: Option<String>,
+    #[arg(long)]
+    extra_headers: Option<String>,
+    /// Drop repeated extra header includes, keeping the first occurrence.
+    #[arg(long)]
+    dedupe_headers: bool,
+    /// Order extra header includes: system `<...>` first, then local, each alphabetically.
+    #[arg(long)]
+    sort_headers: bool,
+    /// Request header sent when downloading the spec, as `Name: Value`. Repeatable.
+    #[arg(
+        long = "spec-header",
+        value_name = "NAME: VALUE",
+        value_parser = parse_spec_header
+    )]
+    spec_headers: Vec<(String, String)>,
+    /// Connect and read timeout for spec downloads, in seconds.
+    #[arg(long, value_name = "SECONDS")]
+    spec_timeout: Option<u64>,
+    /// Retries after a transient spec download failure.
+    #[arg(long, default_value_t = DEFAULT_RETRIES)]
+    spec_retries: u32,
+    /// Redirects followed for spec downloads before giving up; 0 fails on any redirect.
+    #[arg(long, default_value_t = DEFAULT_MAX_REDIRECTS)]
+    spec_max_redirects: u32,
+    /// Proxy for spec downloads, overriding HTTP_PROXY/HTTPS_PROXY/NO_PROXY; `none` disables it.
+    #[arg(long, value_name = "URL")]
+    proxy: Option<String>,
+    /// Download the spec every time instead of reusing a recent download from the per-user
+    /// cache.
+    #[arg(long)]
+    no_cache: bool,
+    /// How long a downloaded spec is reused from the cache, in seconds.
+    #[arg(long, value_name = "SECONDS", default_value_t = DEFAULT_CACHE_TTL.as_secs())]
+    cache_ttl: u64,
+    /// Inline internal `$ref`s into their usage sites before rendering.
+    #[arg(long)]
+    deref: bool,
+    /// Generate `<Name>Request` structs without `readOnly` fields for request bodies, and
+    /// leave `writeOnly` fields out of the response structs.
+    #[arg(long)]
+    split_read_write: bool,
+    /// Also emit a `.cpp` with the function bodies, keeping only declarations in the header.
+    #[arg(long)]
+    source: bool,
+    /// Also emit a Blueprint async action with `OnSuccess`/`OnFailure` pins per operation.
+    #[arg(long)]
+    blueprint_async: bool,
+    /// Also emit a Project Settings class whose `BaseUrl` is prepended to every request URL.
+    #[arg(long)]
+    settings_class: bool,
+    /// Getters and setters to generate for struct fields.
+    #[arg(long, value_enum, default_value_t = FieldAccessors::None)]
+    field_accessors: FieldAccessors,
+    /// Also emit an `_AllPages` helper for list operations taking a page number or cursor.
+    #[arg(long)]
+    pagination: bool,
+    /// Query parameter name marking a page number or cursor, e.g.
+    /// `--pagination-param page`. Repeatable.
+    #[arg(long = "pagination-param", value_name = "NAME")]
+    pagination_params: Vec<String>,
+    /// Also declare an `FOn<Function>Response` dynamic delegate per operation.
+    #[arg(long)]
+    response_delegates: bool,
+    /// Have each function take an `F<Function>Request` struct instead of its parameters.
+    #[arg(long)]
+    request_structs: bool,
+    /// Also declare a `<Field>Pattern` constant with the regex of each `pattern` property.
+    #[arg(long)]
+    pattern_constants: bool,
+    /// Generate a UENUM for two-value string enums such as `["on", "off"]`. They then
+    /// serialize by enumerator name rather than the spec's value.
+    #[arg(long)]
+    flag_enums: bool,
+    /// Unreal type for schemas of a `format`, e.g. `--format-type decimal=FDecimal`
+    /// (default: `FString` for string formats). Repeatable.
+    #[arg(long = "format-type", value_name = "FORMAT=TYPE", value_parser = parse_format_type)]
+    format_types: Vec<(String, String)>,
+    /// Report what would change without writing; exits with code 6 if anything would.
+    #[arg(long)]
+    dry_run: bool,
+    /// Render straight into the output files to save memory; unchanged files are rewritten.
+    #[arg(long)]
+    stream: bool,
+    /// Also write a `<name>.manifest.json` with the spec's source and hash, for drift checks.
+    #[arg(long)]
+    manifest: bool,
+    /// Header template to use instead of the built-in one.
+    #[arg(long, value_name = "PATH")]
+    template: Option<PathBuf>,
+    /// `.cpp` template to use with `--source` instead of the built-in one.
+    #[arg(long, value_name = "PATH")]
+    source_template: Option<PathBuf>,
+    /// Extra template variable (repeatable); the generator's own variables and the spec's
+    /// top-level fields are reserved.
+    #[arg(long = "set", value_name = "KEY=VALUE", value_parser = parse_context_entry)]
+    extra_context: Vec<(String, String)>,
+    /// Run the output through clang-format (optionally at PATH) before writing.
+    #[arg(
+        long,
+        value_name = "PATH",
+        num_args = 0..=1,
+        default_missing_value = DEFAULT_CLANG_FORMAT
+    )]
+    clang_format: Option<PathBuf>,
+    /// Include guard style of the generated header.
+    #[arg(long, value_enum, default_value_t = IncludeGuard::Pragma)]
+    include_guard: IncludeGuard,
+    /// Line ending of the generated files.
+    #[arg(long, value_enum, default_value_t = LineEnding::Lf)]
+    line_ending: LineEnding,
+    /// Start the generated files with a UTF-8 byte order mark.
+    #[arg(long)]
+    bom: bool,
+    /// Fail on the first operation that cannot be rendered instead of skipping it.
     #[arg(long)]
-    path: String,
+    strict: bool,
+    /// Check the rendered C++ for unbalanced brackets and empty type names before writing.
     #[arg(long)]
-    output_dir: String,
+    validate: bool,
+    /// Warn about spec constructs that generate poor C++ before generating.
     #[arg(long)]
-    file_name: String,
+    lint: bool,
+    /// Like `--lint`, but fail instead of generating if there is any warning.
     #[arg(long)]
-    module_name: String,
-    #[arg(long, default_value = "")]
-    extra_headers: String,
+    lint_strict: bool,
+    /// Word kept in this casing in function names, e.g. `--acronym API`. Repeatable.
+    #[arg(long = "acronym", value_name = "WORD")]
+    acronyms: Vec<String>,
+    /// Truncate longer function names, appending a short hash to keep them unique.
+    #[arg(
+        long,
+        value_name = "LEN",
+        value_parser = clap::value_parser!(u64).range(MIN_CAPPED_LEN as u64..)
+    )]
+    max_func_name_len: Option<u64>,
+    /// Name functions on the root path `/` with this segment, e.g. `Root` for `GET_Root`.
+    #[arg(long, value_name = "NAME")]
+    root_segment: Option<String>,
+    /// Leave leading path segments out of function names when they match, e.g. `v1`.
+    #[arg(long, value_name = "PREFIX")]
+    strip_path_prefix: Option<String>,
+    /// Keep running and regenerate whenever the spec changes.
+    #[arg(long, conflicts_with = "dry_run")]
+    watch: bool,
+    /// How often a spec URL is checked for changes in watch mode, in seconds.
+    #[arg(long, value_name = "SECONDS", default_value_t = DEFAULT_POLL_INTERVAL.as_secs())]
+    poll_interval: u64,
+    /// Also log each operation processed and each file written.
+    #[arg(short, long, action = clap::ArgAction::Count, conflicts_with = "quiet")]
+    verbose: u8,
+    /// Log only errors, printing nothing on success.
+    #[arg(short, long)]
+    quiet: bool,
 }
 
-fn main() -> anyhow::Result<()> {
-    let args = Args::parse();
+impl Args {
+    /// Fills inputs not given on the command line from `config`.
+    fn apply_config(&mut self, config: Config) {
+        if self.path.is_empty() {
+            self.path.extend(config.path);
+        }
+        self.output_dir = self.output_dir.take().or(config.output_dir);
+        self.file_name = self.file_name.take().or(config.file_name);
+        self.module_name = self.module_name.take().or(config.module_name);
+        self.extra_headers = self.extra_headers.take().or(config.extra_headers);
+        self.template = self.template.take().or(config.template);
+        self.source_template = self.source_template.take().or(config.source_template);
+    }
+
+    fn verbosity(&self) -> Verbosity {
+        if self.quiet {
+            Verbosity::Quiet
+        } else if self.verbose > 0 {
+            Verbosity::Verbose
+        } else {
+            Verbosity::Normal
+        }
+    }
+}
+
+fn required<'a>(value: &'a Option<String>, name: &str) -> anyhow::Result<&'a str> {
+    value.as_deref().ok_or_else(|| {
+        anyhow!("Missing --{} (or `{}` in the config file)", name.replace('_', "-"), name)
+    })
+}
+
+/// Runs the generator, exiting with an [`ExitStatus`] code.
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    banette_set_log_callback(Some(print_log));
+
+    let result = match cli.command {
+        None => run(cli.generate),
+        Some(Command::Generate(args)) => run(args),
+        Some(Command::Init(init_args)) => run_init(&init_args),
+        Some(Command::Validate(args)) => run_validate(args),
+    };
+    match result {
+        Ok(()) => ExitStatus::Success.into(),
+        Err(e) => {
+            logging::error(&format!("Error: {:?}", e));
+            ExitStatus::of(&e).into()
+        }
+    }
+}
+
+fn run(mut args: Args) -> anyhow::Result<()> {
+    logging::set_verbosity(args.verbosity());
+    args.apply_config(Config::discover(args.config.as_deref())?);
 
     match args.mode {
-        Mode::Openapi => generator::openapi::generate_safe(
-            args.path.as_str(),
-            args.output_dir.as_str(),
-            args.file_name.as_str(),
-            args.module_name.as_str(),
-            generator::openapi::parser::parse_include_headers(&args.extra_headers),
-        ),
+        Mode::Openapi => run_openapi(args),
         Mode::GraphQL => {
             unimplemented!();
         }
     }
 }
+
+fn run_openapi(args: Args) -> anyhow::Result<()> {
+    let (path, merge) = args
+        .path
+        .split_first()
+        .ok_or_else(|| anyhow!("Missing --path (or `path` in the config file)"))?;
+    let glob = is_glob_pattern(path);
+    if glob && !merge.is_empty() {
+        anyhow::bail!("A glob --path cannot be merged with other --path values");
+    }
+    if glob && args.watch {
+        anyhow::bail!("--watch needs a single spec, not a glob --path");
+    }
+    let output_dir = required(&args.output_dir, "output_dir")?;
+    let include_headers = parse_include_headers_with(
+        args.extra_headers.as_deref().unwrap_or_default(),
+        IncludeOptions {
+            dedupe: args.dedupe_headers,
+            sort: args.sort_headers,
+        },
+    );
+    let options = generate_options(&args, merge);
+
+    if glob {
+        if args.lint || args.lint_strict {
+            for spec in expand_glob(path)? {
+                lint(&spec.to_string_lossy(), &options, args.lint_strict)?;
+            }
+        }
+        let module_name = args.module_name.as_deref();
+        let report = generate_glob(path, output_dir, module_name, &include_headers, &options)?;
+        for (_, generation) in &report.generated {
+            print_generation(generation, args.dry_run);
+        }
+        let files: Vec<GeneratedFile> = report
+            .generated
+            .iter()
+            .flat_map(|(_, generation)| generation.files.clone())
+            .collect();
+        report.into_result()?;
+        return check_dry_run(&files, args.dry_run);
+    }
+
+    let file_name = required(&args.file_name, "file_name")?;
+    let module_name = required(&args.module_name, "module_name")?;
+    if args.lint || args.lint_strict {
+        lint(path, &options, args.lint_strict)?;
+    }
+
+    let engine = GeneratorEngine::new(&options)?;
+    let (headers, module) = (&include_headers, module_name);
+    let generate = || generate_with_engine(&engine, path, output_dir, file_name, module, headers);
+
+    if args.watch {
+        let regenerate = || match generate() {
+            Ok(generation) => {
+                logging::info(&format!("[{}] Regenerated", timestamp()));
+                print_generation(&generation, false);
+            }
+            Err(e) => logging::error(&format!("[{}] Generation failed: {:#}", timestamp(), e)),
+        };
+
+        regenerate();
+        let watch_options = WatchOptions {
+            poll_interval: Duration::from_secs(args.poll_interval),
+            ..Default::default()
+        };
+        return watch(path, &options.load, &watch_options, regenerate, &AtomicBool::new(false));
+    }
+
+    let generation = generate()?;
+    print_generation(&generation, args.dry_run);
+    check_dry_run(&generation.files, args.dry_run)
+}
+
+/// The generation options selected by `args`, merging `merge` into the spec.
+fn generate_options(args: &Args, merge: &[String]) -> GenerateOptions {
+    GenerateOptions {
+        load: LoadOptions {
+            headers: args.spec_headers.clone(),
+            timeout: args.spec_timeout.map(Duration::from_secs),
+            retries: args.spec_retries,
+            max_redirects: args.spec_max_redirects,
+            proxy: match args.proxy.as_deref() {
+                Some("none") => ProxyOptions::default(),
+                Some(url) => ProxyOptions::all(url),
+                None => ProxyOptions::from_env(),
+            },
+            cache: (!args.no_cache)
+                .then(|| CacheOptions::in_temp_dir(Duration::from_secs(args.cache_ttl))),
+            ..Default::default()
+        },
+        deref: args.deref,
+        split_read_write: args.split_read_write,
+        source: args.source,
+        blueprint_async: args.blueprint_async,
+        settings_class: args.settings_class,
+        field_accessors: args.field_accessors,
+        pagination: args.pagination,
+        pagination_params: args.pagination_params.clone(),
+        response_delegates: args.response_delegates,
+        request_structs: args.request_structs,
+        pattern_constants: args.pattern_constants,
+        flag_enums: args.flag_enums,
+        dry_run: args.dry_run,
+        stream: args.stream,
+        manifest: args.manifest,
+        template: args.template.clone(),
+        source_template: args.source_template.clone(),
+        extra_context: args.extra_context.iter().cloned().collect(),
+        format_types: args.format_types.iter().cloned().collect(),
+        clang_format: args.clang_format.clone(),
+        include_guard: args.include_guard,
+        line_ending: args.line_ending,
+        bom: args.bom,
+        strict: args.strict,
+        validate: args.validate,
+        merge: merge.to_vec(),
+        acronyms: args.acronyms.clone(),
+        max_func_name_len: args.max_func_name_len.map(|len| len as usize),
+        root_func_segment: args.root_segment.clone(),
+        strip_path_prefix: args.strip_path_prefix.clone(),
+    }
+}
+
+/// Lints the spec, or each spec matching a glob `--path`, failing on any warning.
+fn run_validate(mut args: Args) -> anyhow::Result<()> {
+    logging::set_verbosity(args.verbosity());
+    args.apply_config(Config::discover(args.config.as_deref())?);
+
+    let (path, merge) = args
+        .path
+        .split_first()
+        .ok_or_else(|| anyhow!("Missing --path (or `path` in the config file)"))?;
+    let options = generate_options(&args, merge);
+    let specs = if is_glob_pattern(path) {
+        expand_glob(path)?.iter().map(|spec| spec.to_string_lossy().into_owned()).collect()
+    } else {
+        vec![path.clone()]
+    };
+
+    for spec in specs {
+        lint(&spec, &options, true)?;
+        logging::info(&format!("{}: OK", spec));
+    }
+    Ok(())
+}
+
+fn run_init(args: &InitArgs) -> anyhow::Result<()> {
+    for path in init::init(&args.dir, args.force)? {
+        logging::info(&format!("{}: created", path.display()));
+    }
+    Ok(())
+}
+
+/// Prints the lint warnings for the spec at `path`, failing on any with `strict`.
+fn lint(path: &str, options: &GenerateOptions, strict: bool) -> anyhow::Result<()> {
+    let warnings = lint_spec_at(path, options)?;
+    for warning in &warnings {
+        logging::warning(&format!("warning: {}", warning));
+    }
+    if !warnings.is_empty() {
+        logging::warning(&format!("{} lint warning(s)", warnings.len()));
+        if strict {
+            return Err(LintFailed.into());
+        }
+    }
+    Ok(())
+}
+
+/// Fails a dry run that found files out of date.
+fn check_dry_run(files: &[GeneratedFile], dry_run: bool) -> anyhow::Result<()> {
+    if dry_run && files.iter().any(|f| f.outcome != WriteOutcome::Unchanged) {
+        return Err(OutOfDate.into());
+    }
+    Ok(())
+}
+
+fn print_generation(generation: &Generation, dry_run: bool) {
+    for file in &generation.files {
+        if dry_run && file.outcome != WriteOutcome::Unchanged {
+            logging::info(&format!("{}: would be {}", file.path.display(), file.outcome));
+        } else {
+            logging::info(&format!("{}: {}", file.path.display(), file.outcome));
+        }
+    }
+    for skipped in &generation.skipped {
+        logging::warning(&format!(
+            "Skipped {} {}: {}",
+            skipped.method.to_uppercase(),
+            skipped.path,
+            skipped.error
+        ));
+    }
+}
+
+/// Prints diagnostics as they are, without the `[Rust]` prefix meant for the plugin's log.
+extern "C" fn print_log(level: i32, message: *const c_char) {
+    let message = unsafe { CStr::from_ptr(message) }.to_string_lossy();
+    if level == LogLevel::Info as i32 || level == LogLevel::Debug as i32 {
+        println!("{}", message);
+    } else {
+        eprintln!("{}", message);
+    }
+}
+
+/// Current UTC time of day as `HH:MM:SS`.
+fn timestamp() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+        % 86_400;
+    format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn full_config() -> Config {
+        Config {
+            path: Some("config.json".to_string()),
+            output_dir: Some("ConfigOut".to_string()),
+            file_name: Some("Config.h".to_string()),
+            module_name: Some("CONFIG_API".to_string()),
+            extra_headers: Some("Config.h".to_string()),
+            template: Some(PathBuf::from("config.h.tera")),
+            source_template: Some(PathBuf::from("config.cpp.tera")),
+        }
+    }
+
+    #[test]
+    fn test_config_only() {
+        let mut args = Args::try_parse_from(["generator"]).unwrap();
+        args.apply_config(full_config());
+
+        assert_eq!(args.path, vec!["config.json"]);
+        assert_eq!(required(&args.output_dir, "output_dir").unwrap(), "ConfigOut");
+        assert_eq!(required(&args.file_name, "file_name").unwrap(), "Config.h");
+        assert_eq!(required(&args.module_name, "module_name").unwrap(), "CONFIG_API");
+        assert_eq!(args.extra_headers.as_deref(), Some("Config.h"));
+        assert_eq!(args.template, Some(PathBuf::from("config.h.tera")));
+        assert_eq!(args.source_template, Some(PathBuf::from("config.cpp.tera")));
+    }
+
+    #[test]
+    fn test_cli_only() {
+        let mut args = Args::try_parse_from([
+            "generator",
+            "--path",
+            "cli.json",
+            "--output-dir",
+            "CliOut",
+            "--file-name",
+            "Cli.h",
+            "--module-name",
+            "CLI_API",
+        ])
+        .unwrap();
+        args.apply_config(Config::default());
+
+        assert_eq!(args.path, vec!["cli.json"]);
+        assert_eq!(required(&args.module_name, "module_name").unwrap(), "CLI_API");
+        assert_eq!(args.extra_headers, None);
+
+        let error = required(&None, "output_dir").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Missing --output-dir (or `output_dir` in the config file)"
+        );
+    }
+
+    #[test]
+    fn test_cli_overrides_config() {
+        let mut args =
+            Args::try_parse_from(["generator", "--path", "cli.json", "--extra-headers", ""])
+                .unwrap();
+        args.apply_config(full_config());
+
+        assert_eq!(args.path, vec!["cli.json"]);
+        assert_eq!(args.extra_headers.as_deref(), Some(""));
+        assert_eq!(args.output_dir.as_deref(), Some("ConfigOut"));
+    }
+
+    #[test]
+    fn test_cli_multiple_paths() {
+        let mut args =
+            Args::try_parse_from(["generator", "--path", "public.json", "--path", "internal.json"])
+                .unwrap();
+        args.apply_config(full_config());
+
+        assert_eq!(args.path, vec!["public.json", "internal.json"]);
+    }
+
+    #[test]
+    fn test_glob_path_rejects_merge_and_watch() {
+        let args = Args::try_parse_from([
+            "generator",
+            "--path",
+            "specs/*.json",
+            "--path",
+            "shared.json",
+        ])
+        .unwrap();
+        let error = run_openapi(args).unwrap_err();
+        assert_eq!(error.to_string(), "A glob --path cannot be merged with other --path values");
+
+        let args =
+            Args::try_parse_from(["generator", "--path", "specs/*.json", "--watch"]).unwrap();
+        let error = run_openapi(args).unwrap_err();
+        assert_eq!(error.to_string(), "--watch needs a single spec, not a glob --path");
+    }
+
+    #[test]
+    fn test_spec_cache_flags() {
+        let cache_of = |flags: &[&str]| {
+            let args = Args::try_parse_from(["generator"].iter().chain(flags)).unwrap();
+            generate_options(&args, &[]).load.cache.map(|cache| cache.ttl)
+        };
+
+        assert_eq!(cache_of(&[]), Some(DEFAULT_CACHE_TTL));
+        assert_eq!(cache_of(&["--cache-ttl", "5"]), Some(Duration::from_secs(5)));
+        assert_eq!(cache_of(&["--no-cache"]), None);
+    }
+
+    #[test]
+    fn test_verbosity_flags() {
+        let verbosity_of = |flags: &[&str]| {
+            let args = Args::try_parse_from(["generator"].iter().chain(flags)).unwrap();
+            args.verbosity()
+        };
+
+        assert_eq!(verbosity_of(&[]), Verbosity::Normal);
+        assert_eq!(verbosity_of(&["-q"]), Verbosity::Quiet);
+        assert_eq!(verbosity_of(&["--verbose"]), Verbosity::Verbose);
+        assert_eq!(verbosity_of(&["-vv"]), Verbosity::Verbose);
+        assert!(Args::try_parse_from(["generator", "-v", "--quiet"]).is_err());
+    }
+
+    #[test]
+    fn test_subcommands() {
+        let cli = Cli::try_parse_from(["generator", "--path", "api.json"]).unwrap();
+        assert!(cli.command.is_none());
+        assert_eq!(cli.generate.path, vec!["api.json"]);
+
+        let cli = Cli::try_parse_from(["generator", "generate", "--path", "api.json"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::Generate(args)) if args.path == ["api.json"]));
+
+        let cli = Cli::try_parse_from(["generator", "validate", "--path", "api.json"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::Validate(_))));
+
+        let cli = Cli::try_parse_from(["generator", "init", "--dir", "Plugin", "--force"]).unwrap();
+        let Some(Command::Init(init)) = cli.command else {
+            panic!("expected the init subcommand");
+        };
+        assert_eq!(init.dir, PathBuf::from("Plugin"));
+        assert!(init.force);
+        assert!(Cli::try_parse_from(["generator", "--path", "api.json", "init"]).is_err());
+    }
+}
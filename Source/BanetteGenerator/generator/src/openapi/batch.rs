@@ -0,0 +1,644 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use super::engine::GeneratorEngine;
+use super::error::ErrorKind;
+use super::loader::STDIN_PATH;
+use super::output::Generation;
+use super::{generate_with_engine, GenerateOptions};
+use crate::filter::path_to_func_name::convert_to_pascal_case_with;
+use crate::filter::sanitize_identifier::sanitize_identifier;
+use anyhow::{anyhow, Context};
+use rayon::prelude::*;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Extensions of the files [`generate_dir`] treats as specs, compared ignoring case.
+pub const SPEC_EXTENSIONS: &[&str] = &["json", "yaml", "yml"];
+
+/// The outcome of [`generate_dir`]: what each spec generated, and the specs that failed.
+#[derive(Debug, Default)]
+pub struct DirReport {
+    /// Each spec generated, with what it generated.
+    pub generated: Vec<(PathBuf, Generation)>,
+    /// Each spec, or subdirectory, that could not be generated, with why.
+    pub failures: Vec<SpecFailure>,
+}
+
+impl DirReport {
+    /// Whether every spec was generated.
+    pub fn is_ok(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Fails with every failure of the run, or returns the generated specs.
+    ///
+    /// The error carries the most severe [`ErrorKind`] among the failures, the one with the
+    /// highest [`ErrorKind::code`], so a caller can still tell why the run failed.
+    pub fn into_result(self) -> anyhow::Result<Vec<(PathBuf, Generation)>> {
+        if self.failures.is_empty() {
+            return Ok(self.generated);
+        }
+
+        let failures: Vec<String> = self.failures.iter().map(ToString::to_string).collect();
+        let error = anyhow!(
+            "{} of {} specs failed to generate:\n{}",
+            self.failures.len(),
+            self.failures.len() + self.generated.len(),
+            failures.join("\n")
+        );
+        let kind = self
+            .failures
+            .iter()
+            .filter_map(|failure| ErrorKind::of(&failure.error))
+            .max_by_key(|kind| kind.code());
+        Err(match kind {
+            Some(kind) => error.context(kind),
+            None => error,
+        })
+    }
+}
+
+/// A spec, or a subdirectory being searched for specs, that [`generate_dir`] gave up on.
+#[derive(Debug)]
+pub struct SpecFailure {
+    pub path: PathBuf,
+    pub error: anyhow::Error,
+}
+
+impl fmt::Display for SpecFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {:#}", self.path.display(), self.error)
+    }
+}
+
+/// Generates one header for every spec in `input_dir`, as [`generate_safe`] would.
+///
+/// Specs are the files with a [`SPEC_EXTENSIONS`] extension, visited in name order. With
+/// `recursive`, subdirectories are searched too (symlinked ones excepted, as they may loop)
+/// and their headers are written to the same subdirectory of `output_dir`. Each spec is
+/// named after its file stem (see [`spec_names`]), so `pet-store.yaml` becomes `PetStore.h`
+/// with the `PETSTORE_API` module name. All specs share one [`GeneratorEngine`] and `options`,
+/// and are generated in parallel on rayon's thread pool.
+///
+/// A spec that fails to generate, or a subdirectory that cannot be read, is recorded in
+/// [`DirReport::failures`] and the run goes on; see [`DirReport::into_result`] to fail
+/// with all of them at once.
+///
+/// # Errors
+/// Fails only if the engine cannot be prepared or `input_dir` itself cannot be read.
+///
+/// [`generate_safe`]: super::generate_safe
+pub fn generate_dir(
+    input_dir: &str,
+    output_dir: &str,
+    recursive: bool,
+    include_headers: &[String],
+    options: &GenerateOptions,
+) -> anyhow::Result<DirReport> {
+    let engine = GeneratorEngine::new(options)?;
+    let input_dir = Path::new(input_dir);
+    let mut report = DirReport::default();
+
+    let specs = find_specs(input_dir, recursive, &mut report.failures)
+        .with_context(|| format!("Failed to read {}", input_dir.display()))
+        .context(ErrorKind::Load)?;
+
+    let targets = SpecTargets {
+        base: input_dir,
+        output_dir: Path::new(output_dir),
+        module_name: None,
+    };
+    generate_specs(&engine, specs, &targets, include_headers, &mut report);
+    Ok(report)
+}
+
+/// Generates one header for every local file matching the glob `pattern`, such as
+/// `specs/**/*.yaml`, as [`generate_dir`] does for the specs of a directory.
+///
+/// Headers are written to the subdirectory of `output_dir` matching the spec's directory
+/// under the pattern's base (see [`expand_glob`]), and named after the spec's file stem.
+/// The module name is `module_name` if set, or else derived from the file stem too.
+///
+/// # Errors
+/// Fails if the engine cannot be prepared or nothing matches `pattern`; a spec that fails
+/// to generate is recorded in [`DirReport::failures`] instead.
+pub fn generate_glob(
+    pattern: &str,
+    output_dir: &str,
+    module_name: Option<&str>,
+    include_headers: &[String],
+    options: &GenerateOptions,
+) -> anyhow::Result<DirReport> {
+    let engine = GeneratorEngine::new(options)?;
+    let specs = expand_glob(pattern)?;
+    let mut report = DirReport::default();
+
+    let base = glob_base(pattern);
+    let targets = SpecTargets { base: &base, output_dir: Path::new(output_dir), module_name };
+    generate_specs(&engine, specs, &targets, include_headers, &mut report);
+    Ok(report)
+}
+
+/// Where [`generate_specs`] writes the headers of specs found under `base`.
+struct SpecTargets<'a> {
+    base: &'a Path,
+    output_dir: &'a Path,
+    module_name: Option<&'a str>,
+}
+
+fn generate_specs(
+    engine: &GeneratorEngine,
+    specs: Vec<PathBuf>,
+    targets: &SpecTargets,
+    include_headers: &[String],
+    report: &mut DirReport,
+) {
+    // Each spec writes its own files through the shared read-only engine, so rayon
+    // generates them in parallel; the report still lists them in order
+    let results: Vec<_> = specs
+        .into_par_iter()
+        .map(|spec| {
+            let relative = spec.parent().and_then(|dir| dir.strip_prefix(targets.base).ok());
+            let out_dir = targets.output_dir.join(relative.unwrap_or(Path::new("")));
+            let (file_name, derived_module_name) = spec_names(&spec);
+
+            let generated = generate_with_engine(
+                engine,
+                &spec.to_string_lossy(),
+                &out_dir.to_string_lossy(),
+                &file_name,
+                targets.module_name.unwrap_or(&derived_module_name),
+                include_headers,
+            );
+            (spec, generated)
+        })
+        .collect();
+
+    for (spec, generated) in results {
+        match generated {
+            Ok(generation) => report.generated.push((spec, generation)),
+            Err(error) => report.failures.push(SpecFailure { path: spec, error }),
+        }
+    }
+}
+
+/// Whether `path` is a glob pattern over local files rather than a single spec: it
+/// contains `*` or `?` and is neither a URL nor `-` for standard input.
+pub fn is_glob_pattern(path: &str) -> bool {
+    let is_url = ["http://", "https://", "file://"].iter().any(|scheme| {
+        path.get(..scheme.len()).is_some_and(|prefix| prefix.eq_ignore_ascii_case(scheme))
+    });
+    path != STDIN_PATH && !is_url && path.contains(['*', '?'])
+}
+
+/// The files matching the glob `pattern`, sorted.
+///
+/// In each path component, `*` matches any run of characters and `?` any one character;
+/// a `**` component matches any number of directories, including none, but never descends
+/// into a symlinked directory, which may loop back on itself. The pattern's
+/// leading components without wildcards form its base directory, which is where the
+/// search starts.
+///
+/// # Errors
+/// Fails with "No specs matched" if no file matches.
+pub fn expand_glob(pattern: &str) -> anyhow::Result<Vec<PathBuf>> {
+    let base = glob_base(pattern);
+    let rest: Vec<String> = Path::new(pattern)
+        .components()
+        .skip(base.components().count())
+        .map(|component| component.as_os_str().to_string_lossy().into_owned())
+        .collect();
+
+    let mut matches = Vec::new();
+    if base.as_os_str().is_empty() {
+        match_components(Path::new("."), &rest, &mut matches);
+        // Searching from the current directory puts `./` in front of every match
+        for path in &mut matches {
+            if let Ok(relative) = path.strip_prefix(".") {
+                *path = relative.to_path_buf();
+            }
+        }
+    } else if rest.is_empty() {
+        matches.extend(base.is_file().then_some(base));
+    } else {
+        match_components(&base, &rest, &mut matches);
+    }
+    matches.sort();
+    matches.dedup();
+
+    if matches.is_empty() {
+        return Err(anyhow!("No specs matched `{}`", pattern)).context(ErrorKind::Load);
+    }
+    Ok(matches)
+}
+
+/// The leading components of `pattern` that hold no wildcard.
+fn glob_base(pattern: &str) -> PathBuf {
+    Path::new(pattern)
+        .components()
+        .take_while(|component| !component.as_os_str().to_string_lossy().contains(['*', '?']))
+        .collect()
+}
+
+/// Adds the files under `dir` matching the glob components `rest` to `matches`.
+fn match_components(dir: &Path, rest: &[String], matches: &mut Vec<PathBuf>) {
+    let Some((component, rest_after)) = rest.split_first() else {
+        return;
+    };
+
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let entries: Vec<PathBuf> = entries.filter_map(|entry| entry.ok()).map(|e| e.path()).collect();
+
+    if component == "**" {
+        match_components(dir, rest_after, matches);
+        for sub_dir in entries.iter().filter(|path| is_real_dir(path)) {
+            match_components(sub_dir, rest, matches);
+        }
+        return;
+    }
+
+    for path in entries {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        if !wildcard_match(component, &name) {
+            continue;
+        }
+        if rest_after.is_empty() {
+            if path.is_file() {
+                matches.push(path);
+            }
+        } else if path.is_dir() {
+            match_components(&path, rest_after, matches);
+        }
+    }
+}
+
+/// Whether `path` is a directory itself rather than a symlink to one.
+fn is_real_dir(path: &Path) -> bool {
+    fs::symlink_metadata(path).is_ok_and(|metadata| metadata.is_dir())
+}
+
+/// Whether `name` matches the glob component `pattern`, where `*` matches any run of
+/// characters and `?` any one character.
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // Position after the last `*`, and the name position it currently matches up to
+    let mut backtrack = None;
+
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p + 1, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((after_star, matched)) => {
+                    p = after_star;
+                    n = matched + 1;
+                    backtrack = Some((after_star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// The header file name and module name for the spec at `path`: its file stem in
+/// PascalCase, with `.h` appended, and upper-cased with `_API` appended.
+pub fn spec_names(path: &Path) -> (String, String) {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = sanitize_identifier(&convert_to_pascal_case_with(&sanitize_identifier(&stem), &[]));
+
+    (format!("{}.h", name), format!("{}_API", name.to_uppercase()))
+}
+
+/// The specs in `dir`, sorted, and with `recursive` those of its subdirectories after
+/// them, leaving out symlinked subdirectories. Subdirectories that cannot be read are
+/// added to `failures`.
+fn find_specs(
+    dir: &Path,
+    recursive: bool,
+    failures: &mut Vec<SpecFailure>,
+) -> std::io::Result<Vec<PathBuf>> {
+    let mut entries = fs::read_dir(dir)?
+        .map(|entry| entry.map(|entry| entry.path()))
+        .collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort();
+
+    let is_spec = |path: &Path| {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| SPEC_EXTENSIONS.iter().any(|known| known.eq_ignore_ascii_case(ext)))
+    };
+    let (dirs, files): (Vec<PathBuf>, Vec<PathBuf>) =
+        entries.into_iter().partition(|path| path.is_dir());
+    let mut specs: Vec<PathBuf> = files.into_iter().filter(|path| is_spec(path)).collect();
+
+    if recursive {
+        for sub_dir in dirs.into_iter().filter(|path| is_real_dir(path)) {
+            match find_specs(&sub_dir, true, failures) {
+                Ok(found) => specs.extend(found),
+                Err(e) => failures.push(SpecFailure {
+                    error: anyhow::Error::new(e).context(ErrorKind::Load),
+                    path: sub_dir,
+                }),
+            }
+        }
+    }
+    Ok(specs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::openapi::tests::SHARED_REF_SPEC;
+
+    /// Creates a fresh temp directory holding `files`, given as `(relative path, contents)`.
+    fn write_spec_dir(dir_name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir = std::env::temp_dir().join(dir_name);
+        fs::remove_dir_all(&dir).ok();
+        for (name, contents) in files {
+            let path = dir.join("specs").join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+        dir
+    }
+
+    fn generate(dir: &Path, recursive: bool) -> DirReport {
+        let input = dir.join("specs");
+        let output = dir.join("out");
+        generate_dir(
+            input.to_str().unwrap(),
+            output.to_str().unwrap(),
+            recursive,
+            &[],
+            &GenerateOptions::default(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_spec_names() {
+        assert_eq!(
+            spec_names(Path::new("specs/pet-store.yaml")),
+            ("PetStore.h".to_string(), "PETSTORE_API".to_string())
+        );
+        assert_eq!(
+            spec_names(Path::new("users_v2.json")),
+            ("UsersV2.h".to_string(), "USERSV2_API".to_string())
+        );
+    }
+
+    #[test]
+    fn test_generate_dir_writes_header_per_spec() {
+        let dir = write_spec_dir(
+            "banette_generate_dir",
+            &[
+                ("pet-store.json", SHARED_REF_SPEC),
+                ("users.yml", SHARED_REF_SPEC),
+                ("notes.txt", "not a spec"),
+                ("nested/orders.json", SHARED_REF_SPEC),
+            ],
+        );
+
+        let report = generate(&dir, false);
+
+        assert!(report.is_ok());
+        let specs: Vec<&Path> = report.generated.iter().map(|(spec, _)| spec.as_path()).collect();
+        assert_eq!(specs, [dir.join("specs/pet-store.json"), dir.join("specs/users.yml")]);
+        let header = fs::read_to_string(dir.join("out/PetStore.h")).unwrap();
+        assert!(header.contains("struct PETSTORE_API FUser"));
+        assert!(header.contains("class PETSTORE_API UPetStoreLibrary"));
+        assert!(dir.join("out/Users.h").exists());
+        assert!(!dir.join("out/nested").exists());
+    }
+
+    #[test]
+    fn test_generate_dir_parallel_matches_sequential() {
+        let specs: Vec<(String, &str)> =
+            (0..6).map(|i| (format!("spec{}.json", i), SHARED_REF_SPEC)).collect();
+        let specs: Vec<(&str, &str)> = specs.iter().map(|(n, c)| (n.as_str(), *c)).collect();
+        let dir = write_spec_dir("banette_generate_dir_parallel", &specs);
+        let read_out = || {
+            let mut files: Vec<(PathBuf, String)> = fs::read_dir(dir.join("out"))
+                .unwrap()
+                .map(|entry| {
+                    let path = entry.unwrap().path();
+                    let contents = fs::read_to_string(&path).unwrap();
+                    (path, contents)
+                })
+                .collect();
+            files.sort();
+            files
+        };
+
+        let parallel = generate(&dir, false);
+        let parallel_files = read_out();
+        fs::remove_dir_all(dir.join("out")).unwrap();
+
+        let single_thread = rayon::ThreadPoolBuilder::new().num_threads(1).build().unwrap();
+        let sequential = single_thread.install(|| generate(&dir, false));
+
+        let names = |report: &DirReport| -> Vec<PathBuf> {
+            report.generated.iter().map(|(spec, _)| spec.clone()).collect()
+        };
+        assert_eq!(names(&parallel), names(&sequential));
+        assert_eq!(parallel_files.len(), 6);
+        assert_eq!(parallel_files, read_out());
+    }
+
+    #[test]
+    fn test_generate_dir_recursive_mirrors_subdirectories() {
+        let dir = write_spec_dir(
+            "banette_generate_dir_recursive",
+            &[("pets.json", SHARED_REF_SPEC), ("nested/orders.yaml", SHARED_REF_SPEC)],
+        );
+
+        let report = generate(&dir, true);
+
+        assert_eq!(report.generated.len(), 2);
+        assert!(dir.join("out/Pets.h").exists());
+        assert!(dir.join("out/nested/Orders.h").exists());
+    }
+
+    #[test]
+    fn test_generate_dir_collects_failures() {
+        let dir = write_spec_dir(
+            "banette_generate_dir_failures",
+            &[("broken.json", "{ not json"), ("pets.json", SHARED_REF_SPEC)],
+        );
+
+        let report = generate(&dir, false);
+
+        assert_eq!(report.generated.len(), 1);
+        assert!(dir.join("out/Pets.h").exists());
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].path, dir.join("specs/broken.json"));
+        assert_eq!(ErrorKind::of(&report.failures[0].error), Some(ErrorKind::Load));
+
+        let error = report.into_result().unwrap_err();
+        assert_eq!(ErrorKind::of(&error), Some(ErrorKind::Load));
+        let message = format!("{:#}", error);
+        assert!(message.contains("1 of 2 specs failed to generate:"));
+        assert!(message.contains("broken.json"));
+    }
+
+    #[test]
+    fn test_dir_report_keeps_most_severe_error_kind() {
+        let failure = |name: &str, kind: Option<ErrorKind>| SpecFailure {
+            path: PathBuf::from(name),
+            error: match kind {
+                Some(kind) => anyhow!("cause").context(kind),
+                None => anyhow!("cause"),
+            },
+        };
+        let report = DirReport {
+            generated: Vec::new(),
+            failures: vec![
+                failure("a.json", Some(ErrorKind::Load)),
+                failure("b.json", Some(ErrorKind::Write)),
+                failure("c.json", None),
+            ],
+        };
+        assert_eq!(ErrorKind::of(&report.into_result().unwrap_err()), Some(ErrorKind::Write));
+
+        let report = DirReport { generated: Vec::new(), failures: vec![failure("d.json", None)] };
+        assert_eq!(ErrorKind::of(&report.into_result().unwrap_err()), None);
+    }
+
+    #[test]
+    fn test_generate_dir_missing_input_dir() {
+        let missing = std::env::temp_dir().join("banette_generate_dir_missing");
+        fs::remove_dir_all(&missing).ok();
+
+        let result = generate_dir(
+            missing.to_str().unwrap(),
+            missing.join("out").to_str().unwrap(),
+            false,
+            &[],
+            &GenerateOptions::default(),
+        );
+        assert_eq!(ErrorKind::of(&result.unwrap_err()), Some(ErrorKind::Load));
+    }
+
+    #[test]
+    fn test_is_glob_pattern() {
+        assert!(is_glob_pattern("specs/**/*.yaml"));
+        assert!(is_glob_pattern("specs/v?.json"));
+        assert!(!is_glob_pattern("specs/api.json"));
+        assert!(!is_glob_pattern("-"));
+        assert!(!is_glob_pattern("https://example.com/openapi.json?version=*"));
+        assert!(!is_glob_pattern("FILE:///specs/*.json"));
+    }
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("*.yaml", "pets.yaml"));
+        assert!(wildcard_match("*.yaml", ".yaml"));
+        assert!(wildcard_match("v?-*-api.json", "v2-pets-users-api.json"));
+        assert!(!wildcard_match("*.yaml", "pets.yml"));
+        assert!(!wildcard_match("v?.json", "v10.json"));
+    }
+
+    #[test]
+    fn test_expand_glob() {
+        let dir = write_spec_dir(
+            "banette_expand_glob",
+            &[
+                ("pets.yaml", SHARED_REF_SPEC),
+                ("v1/users.yaml", SHARED_REF_SPEC),
+                ("v1/deep/orders.yaml", SHARED_REF_SPEC),
+                ("v1/notes.json", SHARED_REF_SPEC),
+            ],
+        );
+        let specs = dir.join("specs");
+        let expand = |pattern: &str| expand_glob(specs.join(pattern).to_str().unwrap()).unwrap();
+
+        assert_eq!(
+            expand("**/*.yaml"),
+            [
+                specs.join("pets.yaml"),
+                specs.join("v1/deep/orders.yaml"),
+                specs.join("v1/users.yaml"),
+            ]
+        );
+        assert_eq!(expand("v?/*"), [specs.join("v1/notes.json"), specs.join("v1/users.yaml")]);
+        assert_eq!(expand("*.yaml"), [specs.join("pets.yaml")]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlinked_dirs_are_not_followed() {
+        let dir = write_spec_dir(
+            "banette_glob_symlink_loop",
+            &[("pets.yaml", SHARED_REF_SPEC), ("v1/users.yaml", SHARED_REF_SPEC)],
+        );
+        let specs = dir.join("specs");
+        std::os::unix::fs::symlink(&specs, specs.join("v1/loop")).unwrap();
+
+        let pattern = specs.join("**/*.yaml");
+        assert_eq!(
+            expand_glob(pattern.to_str().unwrap()).unwrap(),
+            [specs.join("pets.yaml"), specs.join("v1/users.yaml")]
+        );
+
+        let report = generate(&dir, true);
+        assert!(report.is_ok());
+        assert_eq!(report.generated.len(), 2);
+    }
+
+    #[test]
+    fn test_generate_glob_generates_each_match() {
+        let dir = write_spec_dir(
+            "banette_generate_glob",
+            &[
+                ("pets.yaml", SHARED_REF_SPEC),
+                ("v1/users.yaml", SHARED_REF_SPEC),
+                ("v1/notes.json", SHARED_REF_SPEC),
+            ],
+        );
+        let pattern = dir.join("specs/**/*.yaml");
+
+        let report = generate_glob(
+            pattern.to_str().unwrap(),
+            dir.join("out").to_str().unwrap(),
+            Some("TESTMODULE_API"),
+            &[],
+            &GenerateOptions::default(),
+        )
+        .unwrap();
+
+        assert!(report.is_ok());
+        assert_eq!(report.generated.len(), 2);
+        let header = fs::read_to_string(dir.join("out/Pets.h")).unwrap();
+        assert!(header.contains("class TESTMODULE_API UPetsLibrary"));
+        assert!(dir.join("out/v1/Users.h").exists());
+        assert!(!dir.join("out/v1/Notes.h").exists());
+    }
+
+    #[test]
+    fn test_generate_glob_no_match() {
+        let dir =
+            write_spec_dir("banette_generate_glob_no_match", &[("pets.json", SHARED_REF_SPEC)]);
+        let pattern = dir.join("specs/*.yaml");
+        let pattern = pattern.to_str().unwrap();
+
+        let result = generate_glob(pattern, "out", None, &[], &GenerateOptions::default());
+
+        let error = result.unwrap_err();
+        assert!(format!("{:#}", error).contains(&format!("No specs matched `{}`", pattern)));
+        assert_eq!(ErrorKind::of(&error), Some(ErrorKind::Load));
+    }
+}
@@ -0,0 +1,150 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// Default lifetime of a cached spec download.
+pub const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(60);
+
+/// On-disk cache for downloaded specs, keyed by a hash of the URL and request headers.
+#[derive(Debug, Clone)]
+pub struct CacheOptions {
+    /// Directory holding the cached bodies.
+    pub dir: PathBuf,
+    /// How long a cached body is reused before it is downloaded again.
+    pub ttl: Duration,
+}
+
+impl CacheOptions {
+    /// Caches under a per-user `banette-spec-cache-<user>` in the system temp directory.
+    pub fn in_temp_dir(ttl: Duration) -> Self {
+        let user = std::env::var("USER")
+            .or_else(|_| std::env::var("USERNAME"))
+            .unwrap_or_default();
+        Self {
+            dir: std::env::temp_dir().join(format!("banette-spec-cache-{}", user)),
+            ttl,
+        }
+    }
+
+    /// The entry for `url`, so a body fetched with other headers (such as another
+    /// user's credentials) is never served.
+    fn entry_path(&self, url: &str, headers: &[(String, String)]) -> PathBuf {
+        let mut key = Sha256::new();
+        key.update(url.as_bytes());
+        for (name, value) in headers {
+            key.update(b"\n");
+            key.update(name.to_ascii_lowercase().as_bytes());
+            key.update(b":");
+            key.update(value.as_bytes());
+        }
+        self.dir.join(format!("{:x}.spec", key.finalize()))
+    }
+
+    /// Returns the cached body for `url` fetched with `headers`, if one exists and is
+    /// younger than the TTL.
+    pub fn read_fresh(&self, url: &str, headers: &[(String, String)]) -> Option<String> {
+        let path = self.entry_path(url, headers);
+        let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+        let age = SystemTime::now().duration_since(modified).unwrap_or_default();
+
+        if age < self.ttl {
+            fs::read_to_string(path).ok()
+        } else {
+            None
+        }
+    }
+
+    /// Stores a body downloaded from `url` with `headers`, replacing any previous entry.
+    pub fn store(&self, url: &str, headers: &[(String, String)], body: &str) -> Result<()> {
+        create_private_dir(&self.dir)
+            .with_context(|| format!("Failed to create cache directory: {}", self.dir.display()))?;
+        fs::write(self.entry_path(url, headers), body).context("Failed to write cached spec")
+    }
+}
+
+/// Creates `dir` readable only by the current user, tightening it if it already exists.
+#[cfg(unix)]
+fn create_private_dir(dir: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+
+    fs::DirBuilder::new().recursive(true).mode(0o700).create(dir)?;
+    fs::set_permissions(dir, fs::Permissions::from_mode(0o700))
+}
+
+#[cfg(not(unix))]
+fn create_private_dir(dir: &Path) -> std::io::Result<()> {
+    fs::create_dir_all(dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_cache(name: &str, ttl: Duration) -> CacheOptions {
+        let dir = std::env::temp_dir().join(name);
+        fs::remove_dir_all(&dir).ok();
+        CacheOptions { dir, ttl }
+    }
+
+    #[test]
+    fn test_cache_round_trip() {
+        let cache = temp_cache("banette_cache_round_trip", Duration::from_secs(60));
+
+        assert_eq!(cache.read_fresh("http://example.com/api.json", &[]), None);
+        cache.store("http://example.com/api.json", &[], "{}").unwrap();
+        assert_eq!(
+            cache.read_fresh("http://example.com/api.json", &[]),
+            Some("{}".to_string())
+        );
+        assert_eq!(cache.read_fresh("http://example.com/other.json", &[]), None);
+
+        fs::remove_dir_all(&cache.dir).ok();
+    }
+
+    #[test]
+    fn test_cache_keyed_by_headers() {
+        let cache = temp_cache("banette_cache_keyed_by_headers", Duration::from_secs(60));
+        let alice = [("Authorization".to_string(), "Bearer alice".to_string())];
+        let bob = [("Authorization".to_string(), "Bearer bob".to_string())];
+
+        cache.store("http://example.com/api.json", &alice, "{}").unwrap();
+        assert_eq!(
+            cache.read_fresh("http://example.com/api.json", &alice),
+            Some("{}".to_string())
+        );
+        assert_eq!(cache.read_fresh("http://example.com/api.json", &bob), None);
+        assert_eq!(cache.read_fresh("http://example.com/api.json", &[]), None);
+
+        fs::remove_dir_all(&cache.dir).ok();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_cache_dir_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let cache = temp_cache("banette_cache_dir_is_private", Duration::from_secs(60));
+        cache.store("http://example.com/api.json", &[], "{}").unwrap();
+
+        let mode = fs::metadata(&cache.dir).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o700);
+
+        fs::remove_dir_all(&cache.dir).ok();
+    }
+
+    #[test]
+    fn test_cache_expired_entry() {
+        let cache = temp_cache("banette_cache_expired", Duration::ZERO);
+
+        cache.store("http://example.com/api.json", &[], "{}").unwrap();
+        assert_eq!(cache.read_fresh("http://example.com/api.json", &[]), None);
+
+        fs::remove_dir_all(&cache.dir).ok();
+    }
+}
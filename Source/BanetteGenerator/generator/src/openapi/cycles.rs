@@ -0,0 +1,93 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use crate::filter::component_includes::forward_declared_components;
+use crate::filter::to_ue_type::CYCLE_REF_KEY;
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+const SCHEMA_REF_PREFIX: &str = "#/components/schemas/";
+
+/// Flags with [`CYCLE_REF_KEY`] every property [`cycle_refs`] finds, so `f_to_ue_type`
+/// holds it through a `TSharedPtr` instead of by value, which no struct in the cycle could
+/// have.
+///
+/// Such a member is not a `UPROPERTY`, so JSON (de)serialization skips it. Returns the
+/// flagged properties as `(component, property)`, for callers to warn about.
+pub fn mark_cycle_refs(document: &mut Value) -> Vec<(String, String)> {
+    let cycle_refs = cycle_refs(document);
+    let Some(schemas) = document
+        .pointer_mut("/components/schemas")
+        .and_then(Value::as_object_mut)
+    else {
+        return cycle_refs;
+    };
+
+    for (name, property) in &cycle_refs {
+        let property = schemas
+            .get_mut(name)
+            .and_then(|schema| schema.get_mut("properties"))
+            .and_then(|properties| properties.get_mut(property))
+            .and_then(Value::as_object_mut);
+        if let Some(property) = property {
+            property.insert(CYCLE_REF_KEY.to_string(), Value::Bool(true));
+        }
+    }
+    cycle_refs
+}
+
+/// Every property of a component schema that refers to a component across a reference
+/// cycle (see [`forward_declared_components`]), as `(component, property)` in name order.
+pub fn cycle_refs(document: &Value) -> Vec<(String, String)> {
+    let Some(schemas) = document.pointer("/components/schemas").and_then(Value::as_object)
+    else {
+        return Vec::new();
+    };
+
+    let mut cycle_refs = Vec::new();
+    for name in schemas.keys() {
+        let forward_declared: BTreeSet<String> = forward_declared_components(name, schemas);
+        let properties = schemas[name].get("properties").and_then(Value::as_object);
+        for (property, schema) in properties.into_iter().flatten() {
+            let closes_cycle = schema
+                .get("$ref")
+                .and_then(Value::as_str)
+                .and_then(|r| r.strip_prefix(SCHEMA_REF_PREFIX))
+                .is_some_and(|target| forward_declared.contains(target));
+            if closes_cycle {
+                cycle_refs.push((name.clone(), property.clone()));
+            }
+        }
+    }
+    cycle_refs.sort();
+    cycle_refs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_mark_cycle_refs() {
+        let mut document = json!({ "components": { "schemas": {
+            "Alpha": { "properties": {
+                "beta": { "$ref": "#/components/schemas/Beta" },
+                "betas": { "type": "array", "items": { "$ref": "#/components/schemas/Beta" } }
+            } },
+            "Beta": { "properties": {
+                "alpha": { "$ref": "#/components/schemas/Alpha" },
+                "gamma": { "$ref": "#/components/schemas/Gamma" }
+            } },
+            "Gamma": { "type": "object" }
+        } } });
+        assert_eq!(mark_cycle_refs(&mut document), [("Beta".to_string(), "alpha".to_string())]);
+
+        let schemas = &document["components"]["schemas"];
+        assert_eq!(schemas["Beta"]["properties"]["alpha"][CYCLE_REF_KEY], true);
+        assert!(schemas["Beta"]["properties"]["gamma"].get(CYCLE_REF_KEY).is_none());
+        assert!(schemas["Alpha"]["properties"]["beta"].get(CYCLE_REF_KEY).is_none());
+        assert!(schemas["Alpha"]["properties"]["betas"]["items"].get(CYCLE_REF_KEY).is_none());
+    }
+}
@@ -0,0 +1,164 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use crate::filter::to_ue_type::INLINED_REF_KEY;
+use serde_json::Value;
+
+/// Inlines every internal `$ref` (`#/...`) outside `components` into its usage site.
+///
+/// Each `$ref` object is replaced by a copy of its target, so filters see the schema
+/// itself where the reference was. The copy keeps the `$ref` under [`INLINED_REF_KEY`],
+/// so `f_to_ue_type` still names the referenced struct and the rendered output is the
+/// same as without inlining. A reference that would recurse into itself is left as a
+/// bare `$ref`, which acts as a named placeholder for the cycle.
+///
+/// `components` is not modified, so generated component structs are unaffected.
+pub fn dereference(document: &mut Value) {
+    let root = document.clone();
+    let Some(map) = document.as_object_mut() else {
+        return;
+    };
+
+    for (key, value) in map.iter_mut() {
+        if key != "components" {
+            inline_refs(value, &root, &mut Vec::new());
+        }
+    }
+}
+
+fn inline_refs(value: &mut Value, root: &Value, stack: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            let reference = map
+                .get("$ref")
+                .and_then(|v| v.as_str())
+                .filter(|r| r.starts_with("#/"))
+                .map(String::from);
+
+            if let Some(reference) = &reference {
+                if stack.contains(reference) {
+                    return;
+                }
+
+                if let Some(Value::Object(target)) = root.pointer(&reference[1..]) {
+                    let mut inlined = target.clone();
+                    inlined.insert(INLINED_REF_KEY.to_string(), Value::String(reference.clone()));
+                    *value = Value::Object(inlined);
+                }
+            }
+
+            // The inlined target is walked with its own reference on the stack, so a
+            // self-reference inside it stays a bare `$ref`.
+            let depth = stack.len();
+            stack.extend(reference);
+            if let Value::Object(map) = value {
+                for child in map.values_mut() {
+                    inline_refs(child, root, stack);
+                }
+            }
+            stack.truncate(depth);
+        }
+        Value::Array(list) => {
+            for item in list {
+                inline_refs(item, root, stack);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_dereference_inlines_shared_schema() {
+        let mut document = json!({
+            "paths": {
+                "/users": {
+                    "get": {
+                        "responses": {
+                            "200": {
+                                "content": {
+                                    "application/json": {
+                                        "schema": { "$ref": "#/components/schemas/User" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "User": {
+                        "type": "object",
+                        "properties": { "address": { "$ref": "#/components/schemas/Address" } }
+                    },
+                    "Address": { "type": "object", "properties": { "city": { "type": "string" } } }
+                }
+            }
+        });
+
+        dereference(&mut document);
+
+        let schema = &document["paths"]["/users"]["get"]["responses"]["200"]["content"]
+            ["application/json"]["schema"];
+        assert!(schema.get("$ref").is_none());
+        assert_eq!(schema[INLINED_REF_KEY], "#/components/schemas/User");
+        assert_eq!(schema["type"], "object");
+        assert_eq!(
+            schema["properties"]["address"]["properties"]["city"]["type"],
+            "string"
+        );
+
+        // Components keep their references
+        assert!(
+            document["components"]["schemas"]["User"]["properties"]["address"]
+                .get("type")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_dereference_leaves_cycle_as_named_ref() {
+        let mut document = json!({
+            "paths": {
+                "/nodes": { "get": { "schema": { "$ref": "#/components/schemas/Node" } } }
+            },
+            "components": {
+                "schemas": {
+                    "Node": {
+                        "type": "object",
+                        "properties": { "next": { "$ref": "#/components/schemas/Node" } }
+                    }
+                }
+            }
+        });
+
+        dereference(&mut document);
+
+        let schema = &document["paths"]["/nodes"]["get"]["schema"];
+        assert_eq!(schema["type"], "object");
+        assert_eq!(
+            schema["properties"]["next"],
+            json!({ "$ref": "#/components/schemas/Node" })
+        );
+    }
+
+    #[test]
+    fn test_dereference_ignores_external_and_missing_refs() {
+        let mut document = json!({
+            "paths": {
+                "/a": { "schema": { "$ref": "other.yaml#/Thing" } },
+                "/b": { "schema": { "$ref": "#/components/schemas/Missing" } }
+            }
+        });
+        let expected = document.clone();
+
+        dereference(&mut document);
+        assert_eq!(document, expected);
+    }
+}
@@ -0,0 +1,264 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+
+const SCHEMA_REF_PREFIX: &str = "#/components/schemas/";
+
+/// Appended to a schema's name for its request variant, e.g. `UserRequest` for `User`.
+pub const REQUEST_VARIANT_SUFFIX: &str = "Request";
+
+/// Which way a struct carries data: to the server, or back from it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Request,
+    Response,
+}
+
+/// Whether a property belongs in the struct for `direction`: `readOnly` properties are
+/// left out of requests and `writeOnly` properties out of responses.
+pub fn is_in_direction(property: &Value, direction: Direction) -> bool {
+    let flag = match direction {
+        Direction::Request => "readOnly",
+        Direction::Response => "writeOnly",
+    };
+    property.get(flag).and_then(Value::as_bool) != Some(true)
+}
+
+/// Copy of `schema` with only the properties that belong in `direction`, and its
+/// `required` list trimmed to match.
+pub fn schema_for_direction(schema: &Value, direction: Direction) -> Value {
+    let mut schema = schema.clone();
+    let Some(properties) = schema.get_mut("properties").and_then(Value::as_object_mut) else {
+        return schema;
+    };
+    properties.retain(|_, property| is_in_direction(property, direction));
+    let kept: HashSet<String> = properties.keys().cloned().collect();
+
+    if let Some(required) = schema.get_mut("required").and_then(Value::as_array_mut) {
+        required.retain(|name| name.as_str().is_some_and(|name| kept.contains(name)));
+    }
+    schema
+}
+
+/// Splits component schemas into request and response variants.
+///
+/// Every schema with a `readOnly` property, or referring to such a schema, gets a request
+/// variant `<Name>Request` without its `readOnly` properties, whose refs point at request
+/// variants too, and request bodies are pointed at these variants. The schemas themselves
+/// become the response variants and lose their `writeOnly` properties. A schema whose
+/// variant name is already taken by another schema is not split.
+pub fn split_read_write(document: &mut Value) {
+    let Some(schemas) = document
+        .pointer_mut("/components/schemas")
+        .and_then(Value::as_object_mut)
+    else {
+        return;
+    };
+
+    // 1. Schemas whose request variant differs from the response one
+    let can_split = |name: &str| !schemas.contains_key(&variant_name(name));
+    let mut split: HashSet<String> = schemas
+        .iter()
+        .filter(|(name, schema)| can_split(name) && !keeps_all(schema, Direction::Request))
+        .map(|(name, _)| name.clone())
+        .collect();
+    loop {
+        let referring: Vec<String> = schemas
+            .iter()
+            .filter(|(name, schema)| {
+                !split.contains(*name) && can_split(name) && refers_to(schema, &split)
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+        if referring.is_empty() {
+            break;
+        }
+        split.extend(referring);
+    }
+
+    // 2. Request variants, taken before the response variants drop `writeOnly` properties
+    let variants: Vec<(String, Value)> = split
+        .iter()
+        .map(|name| {
+            let mut variant = schema_for_direction(&schemas[name], Direction::Request);
+            redirect_refs(&mut variant, &split);
+            (variant_name(name), variant)
+        })
+        .collect();
+    for schema in schemas.values_mut() {
+        if !keeps_all(schema, Direction::Response) {
+            *schema = schema_for_direction(schema, Direction::Response);
+        }
+    }
+    schemas.extend(variants);
+
+    // 3. Request bodies use the request variants
+    if split.is_empty() {
+        return;
+    }
+    if let Some(bodies) = document
+        .pointer_mut("/components/requestBodies")
+        .and_then(Value::as_object_mut)
+    {
+        bodies.values_mut().for_each(|body| redirect_refs(body, &split));
+    }
+    let operations = document
+        .get_mut("paths")
+        .and_then(Value::as_object_mut)
+        .into_iter()
+        .flat_map(Map::values_mut)
+        .filter_map(Value::as_object_mut)
+        .flat_map(Map::values_mut);
+    for operation in operations {
+        if let Some(body) = operation.get_mut("requestBody") {
+            redirect_refs(body, &split);
+        }
+    }
+}
+
+fn variant_name(name: &str) -> String {
+    format!("{}{}", name, REQUEST_VARIANT_SUFFIX)
+}
+
+/// Whether every property of `schema` belongs in `direction`, so it needs no variant.
+fn keeps_all(schema: &Value, direction: Direction) -> bool {
+    schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .is_none_or(|properties| properties.values().all(|p| is_in_direction(p, direction)))
+}
+
+fn schema_ref(map: &Map<String, Value>) -> Option<&str> {
+    map.get("$ref")?.as_str()?.strip_prefix(SCHEMA_REF_PREFIX)
+}
+
+fn refers_to(value: &Value, names: &HashSet<String>) -> bool {
+    match value {
+        Value::Object(map) => {
+            schema_ref(map).is_some_and(|name| names.contains(name))
+                || map.values().any(|child| refers_to(child, names))
+        }
+        Value::Array(items) => items.iter().any(|item| refers_to(item, names)),
+        _ => false,
+    }
+}
+
+/// Points every `$ref` to a schema in `split` at its request variant.
+fn redirect_refs(value: &mut Value, split: &HashSet<String>) {
+    match value {
+        Value::Object(map) => {
+            if let Some(name) = schema_ref(map).filter(|name| split.contains(*name)) {
+                let target = format!("{}{}", SCHEMA_REF_PREFIX, variant_name(name));
+                map.insert("$ref".to_string(), Value::String(target));
+            }
+            map.values_mut().for_each(|child| redirect_refs(child, split));
+        }
+        Value::Array(items) => items.iter_mut().for_each(|item| redirect_refs(item, split)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn user_schema() -> Value {
+        json!({
+            "type": "object",
+            "required": ["id", "name"],
+            "properties": {
+                "id": { "type": "integer", "readOnly": true },
+                "name": { "type": "string" },
+                "password": { "type": "string", "writeOnly": true }
+            }
+        })
+    }
+
+    fn property_names(schema: &Value) -> Vec<&str> {
+        schema["properties"].as_object().unwrap().keys().map(String::as_str).collect()
+    }
+
+    #[test]
+    fn test_is_in_direction() {
+        let id = json!({ "type": "integer", "readOnly": true });
+        let password = json!({ "type": "string", "writeOnly": true });
+        let name = json!({ "type": "string", "readOnly": false });
+
+        assert!(!is_in_direction(&id, Direction::Request));
+        assert!(is_in_direction(&id, Direction::Response));
+        assert!(is_in_direction(&password, Direction::Request));
+        assert!(!is_in_direction(&password, Direction::Response));
+        assert!(is_in_direction(&name, Direction::Request));
+        assert!(is_in_direction(&name, Direction::Response));
+    }
+
+    #[test]
+    fn test_schema_for_direction() {
+        let request = schema_for_direction(&user_schema(), Direction::Request);
+        assert_eq!(property_names(&request), ["name", "password"]);
+        assert_eq!(request["required"], json!(["name"]));
+
+        let response = schema_for_direction(&user_schema(), Direction::Response);
+        assert_eq!(property_names(&response), ["id", "name"]);
+        assert_eq!(response["required"], json!(["id", "name"]));
+    }
+
+    #[test]
+    fn test_split_read_write() {
+        let mut document = json!({
+            "paths": { "/teams": { "post": {
+                "requestBody": { "content": { "application/json": {
+                    "schema": { "$ref": "#/components/schemas/Team" }
+                } } },
+                "responses": { "200": { "content": { "application/json": {
+                    "schema": { "$ref": "#/components/schemas/Team" }
+                } } } }
+            } } },
+            "components": { "schemas": {
+                "Team": { "type": "object", "properties": {
+                    "members": {
+                        "type": "array",
+                        "items": { "$ref": "#/components/schemas/User" }
+                    },
+                    "tag": { "$ref": "#/components/schemas/Tag" }
+                } },
+                "Tag": { "type": "object", "properties": { "label": { "type": "string" } } },
+                "User": user_schema()
+            } }
+        });
+        split_read_write(&mut document);
+
+        let schemas = &document["components"]["schemas"];
+        assert_eq!(property_names(&schemas["User"]), ["id", "name"]);
+        assert_eq!(property_names(&schemas["UserRequest"]), ["name", "password"]);
+        let team_request = &schemas["TeamRequest"]["properties"];
+        assert_eq!(team_request["members"]["items"]["$ref"], "#/components/schemas/UserRequest");
+        assert_eq!(team_request["tag"]["$ref"], "#/components/schemas/Tag");
+        let team = &schemas["Team"]["properties"];
+        assert_eq!(team["members"]["items"]["$ref"], "#/components/schemas/User");
+        assert!(schemas.get("TagRequest").is_none());
+
+        let operation = &document["paths"]["/teams"]["post"];
+        let body = &operation["requestBody"]["content"]["application/json"];
+        assert_eq!(body["schema"]["$ref"], "#/components/schemas/TeamRequest");
+        let response = &operation["responses"]["200"]["content"]["application/json"];
+        assert_eq!(response["schema"]["$ref"], "#/components/schemas/Team");
+    }
+
+    #[test]
+    fn test_split_read_write_skips_taken_variant_name() {
+        let mut document = json!({ "components": { "schemas": {
+            "User": user_schema(),
+            "UserRequest": { "type": "object", "properties": { "email": { "type": "string" } } }
+        } } });
+        split_read_write(&mut document);
+
+        let schemas = &document["components"]["schemas"];
+        assert_eq!(property_names(&schemas["UserRequest"]), ["email"]);
+        assert_eq!(property_names(&schemas["User"]), ["id", "name"]);
+    }
+}
@@ -0,0 +1,802 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use super::cycles::mark_cycle_refs;
+use super::deref::dereference;
+use super::directions::split_read_write;
+use super::enums::mark_enum_refs;
+use super::error::ErrorKind;
+use super::formats::apply_format_types;
+use super::loader::LoadedSpec;
+use super::naming::{assign_func_names, FUNC_NAME_KEY, HTTP_METHODS};
+use super::output::{write_streamed, SkippedOperation, WriteOutcome};
+use super::template_error::describe_template_error;
+use super::GenerateOptions;
+use crate::filter::pagination::DEFAULT_PAGINATION_PARAMS;
+use crate::filter::register_all_filters;
+use crate::filter::sanitize_identifier::sanitize_identifier;
+use crate::logging::{self, LogLevel};
+use anyhow::{anyhow, Context, Result};
+use oas3::Info;
+use rayon::prelude::*;
+use serde_json::Value;
+use std::fs;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use tera::Tera;
+
+/// File name of the built-in header template.
+pub const BUILTIN_TEMPLATE_NAME: &str = "openapi.h.tera";
+
+/// Contents of the built-in header template, for users to start their own from.
+pub const BUILTIN_TEMPLATE: &str =
+    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/templates/openapi.h.tera"));
+
+/// File name of the built-in `.cpp` template.
+pub const BUILTIN_SOURCE_TEMPLATE_NAME: &str = "openapi.cpp.tera";
+
+/// Contents of the built-in `.cpp` template, for users to start their own from.
+pub const BUILTIN_SOURCE_TEMPLATE: &str =
+    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/templates/openapi.cpp.tera"));
+
+/// Variables the engine adds to every template context, which
+/// [`GenerateOptions::extra_context`] entries cannot override.
+pub const RESERVED_CONTEXT_KEYS: &[&str] = &[
+    "module_name",
+    "file_name",
+    "api_title",
+    "api_version",
+    "include_headers",
+    "extra_headers",
+    "split_source",
+    "blueprint_async",
+    "settings_class",
+    "field_accessors",
+    "pagination",
+    "pagination_params",
+    "response_delegates",
+    "request_structs",
+    "pattern_constants",
+    "flag_enums",
+    "include_guard_style",
+    "include_guard_macro",
+    "skipped_operations",
+];
+
+/// Adds a template from `templates/`, read from disk in debug builds and embedded in release.
+macro_rules! add_builtin_template {
+    ($tera:expr, $name:expr, $file:literal) => {{
+        #[cfg(debug_assertions)]
+        let result = $tera.add_template_file(
+            concat!(env!("CARGO_MANIFEST_DIR"), "/templates/", $file),
+            Some($name),
+        );
+        #[cfg(not(debug_assertions))]
+        let result = $tera.add_raw_template(
+            $name,
+            include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/templates/", $file)),
+        );
+        result
+    }};
+}
+
+/// Where and under which names a spec's files are rendered.
+#[derive(Debug, Clone, Copy)]
+pub struct RenderTarget<'a> {
+    /// Name of the header; the `.cpp` file takes its stem.
+    pub file_name: &'a str,
+    /// The `*_API` export macro of the module the files belong to.
+    pub module_name: &'a str,
+    /// Parsed `#include` lines added to the header.
+    pub include_headers: &'a [String],
+    /// Directory the files are written to. Only [`GeneratorEngine::render_to_dir`] writes
+    /// there; [`GeneratorEngine::render`] keeps the files in memory.
+    pub output_dir: &'a Path,
+}
+
+/// A Tera instance with all filters registered and the templates compiled, reusable
+/// across many renders.
+pub struct GeneratorEngine {
+    tera: Tera,
+    options: GenerateOptions,
+}
+
+impl GeneratorEngine {
+    /// Compiles the header template (`options.template` or the built-in one) and, with
+    /// `options.source`, the `.cpp` template (`options.source_template` or the built-in
+    /// one). The settings class partial is always
+    /// available to `{% include "openapi_settings_template" %}`, and the `_AllPages` macros
+    /// to `{% import "openapi_pagination_template" as all_pages %}`.
+    pub fn new(options: &GenerateOptions) -> Result<Self> {
+        let mut tera = Tera::default();
+
+        register_all_filters(&mut tera);
+
+        add_builtin_template!(tera, "openapi_settings_template", "openapi.settings.h.tera")
+            .context(ErrorKind::Render)?;
+        add_builtin_template!(tera, "openapi_pagination_template", "openapi.pagination.tera")
+            .context(ErrorKind::Render)?;
+
+        match &options.template {
+            Some(template_path) => add_user_template(&mut tera, "openapi_template", template_path)?,
+            None => add_builtin_template!(tera, "openapi_template", "openapi.h.tera")
+                .context(ErrorKind::Render)?,
+        }
+        if options.source {
+            match &options.source_template {
+                Some(template_path) => {
+                    add_user_template(&mut tera, "openapi_source_template", template_path)?
+                }
+                None => add_builtin_template!(tera, "openapi_source_template", "openapi.cpp.tera")
+                    .context(ErrorKind::Render)?,
+            }
+        }
+
+        Ok(Self {
+            tera,
+            options: options.clone(),
+        })
+    }
+
+    /// The options the engine was built with, which every render follows.
+    pub fn options(&self) -> &GenerateOptions {
+        &self.options
+    }
+
+    /// Renders the header and, if the engine was built with `source`, the `.cpp` file.
+    ///
+    /// Unless the engine was built with `strict`, a render failure is traced back to the
+    /// operations that cause it: each operation is rendered on its own, the failing ones are
+    /// left out and listed in `skipped` (and in the `skipped_operations` template variable),
+    /// and the rest is rendered again. Failures outside any operation are returned as errors.
+    pub fn render(&self, spec: &LoadedSpec, target: &RenderTarget) -> Result<Rendered> {
+        let options = &self.options;
+        let spec_value = prepare_spec(spec, options);
+
+        let render = |spec_value: &Value, skipped: &[SkippedOperation]| {
+            self.render_files(spec_value, skipped, &spec.spec.info, target)
+        };
+
+        let error = match render(&spec_value, &[]) {
+            Ok(files) => return Ok(Rendered { files, skipped: Vec::new() }),
+            Err(e) if options.strict || ErrorKind::of(&e) != Some(ErrorKind::Render) => {
+                return Err(e);
+            }
+            Err(e) => e,
+        };
+
+        // Only operations can be skipped, so give up if the rest fails by itself
+        if render(&with_operations(&spec_value, &[]), &[]).is_err() {
+            return Err(error);
+        }
+
+        // Every probe is independent, so rayon spreads them over the available cores
+        let probed = operations(&spec_value)
+            .par_iter()
+            .map(|(path, method)| {
+                catch_render_panic(|| {
+                    let operation = [(path.clone(), method.clone())];
+                    let Err(error) = render(&with_operations(&spec_value, &operation), &[])
+                    else {
+                        return Ok(None);
+                    };
+                    Ok(Some(SkippedOperation {
+                        path: path.clone(),
+                        method: method.clone(),
+                        error: render_error_message(&error),
+                    }))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let skipped: Vec<SkippedOperation> = probed.into_iter().flatten().collect();
+        if skipped.is_empty() {
+            return Err(error);
+        }
+
+        let kept: Vec<(String, String)> = operations(&spec_value)
+            .into_iter()
+            .filter(|(path, method)| {
+                !skipped.iter().any(|s| &s.path == path && &s.method == method)
+            })
+            .collect();
+        let files = render(&with_operations(&spec_value, &kept), &skipped)?;
+
+        Ok(Rendered { files, skipped })
+    }
+
+    /// Renders the same files as [`render`](Self::render), but streams each one through a
+    /// buffered writer into `target.output_dir` instead of holding it in memory (see
+    /// [`write_streamed`]).
+    ///
+    /// Operations are never skipped here: any failure fails the render, and a file that was
+    /// being written is left untouched. Callers wanting lenient rendering fall back to
+    /// [`render`](Self::render) on a [`ErrorKind::Render`] error.
+    pub fn render_to_dir(
+        &self,
+        spec: &LoadedSpec,
+        target: &RenderTarget,
+    ) -> Result<Vec<(PathBuf, WriteOutcome)>> {
+        let spec_value = prepare_spec(spec, &self.options);
+        let context = self.render_context(&spec_value, &[], &spec.spec.info, target)?;
+
+        self.outputs(target.file_name)
+            .into_iter()
+            .map(|(template, name)| {
+                let path = target.output_dir.join(name);
+                logging::debug(&format!("Writing {}", path.display()));
+                let outcome = write_streamed(&path, |writer| {
+                    self.tera
+                        .render_to(template, &context, writer)
+                        .map_err(|e| self.describe_error(template, &e))
+                })
+                .map_err(|e| match ErrorKind::of(&e) {
+                    Some(_) => e,
+                    None => e.context(ErrorKind::Write),
+                })?;
+                Ok((path, outcome))
+            })
+            .collect()
+    }
+
+    /// Renders `spec_value` into `(file name, contents)` pairs, header first.
+    fn render_files(
+        &self,
+        spec_value: &Value,
+        skipped: &[SkippedOperation],
+        info: &Info,
+        target: &RenderTarget,
+    ) -> Result<Vec<(String, String)>> {
+        let context = self.render_context(spec_value, skipped, info, target)?;
+
+        self.outputs(target.file_name)
+            .into_iter()
+            .map(|(template, name)| Ok((name, self.render_template(template, &context)?)))
+            .collect()
+    }
+
+    /// `(template name, output file name)` of every file rendered for `file_name`, header
+    /// first.
+    fn outputs(&self, file_name: &str) -> Vec<(&'static str, String)> {
+        let mut outputs = vec![("openapi_template", file_name.to_string())];
+        if self.options.source {
+            let file_name_base = Path::new(file_name).file_stem().unwrap_or_default();
+            outputs.push((
+                "openapi_source_template",
+                format!("{}.cpp", file_name_base.to_string_lossy()),
+            ));
+        }
+        outputs
+    }
+
+    /// Builds the template context: the spec itself plus the generator's own variables.
+    ///
+    /// `api_title` and `api_version` come from the parsed `info`, so templates need not
+    /// rely on how the spec serializes. The parsed `#include` lines are available as both
+    /// `include_headers` and `extra_headers`.
+    fn render_context(
+        &self,
+        spec_value: &Value,
+        skipped: &[SkippedOperation],
+        info: &Info,
+        target: &RenderTarget,
+    ) -> Result<tera::Context> {
+        let options = &self.options;
+        let file_name = Path::new(target.file_name);
+        let file_name_base = file_name.file_stem().unwrap_or_default().to_string_lossy();
+
+        let mut context =
+            tera::Context::from_value(spec_value.clone()).context(ErrorKind::Render)?;
+        context.insert("module_name", target.module_name);
+        context.insert("file_name", &file_name_base);
+        context.insert("api_title", &info.title);
+        context.insert("api_version", &info.version);
+        context.insert("include_headers", target.include_headers);
+        context.insert("extra_headers", target.include_headers);
+        context.insert("split_source", &options.source);
+        context.insert("blueprint_async", &options.blueprint_async);
+        context.insert("settings_class", &options.settings_class);
+        context.insert("field_accessors", options.field_accessors.as_str());
+        context.insert("pagination", &options.pagination);
+        if options.pagination_params.is_empty() {
+            context.insert("pagination_params", DEFAULT_PAGINATION_PARAMS);
+        } else {
+            context.insert("pagination_params", &options.pagination_params);
+        }
+        context.insert("response_delegates", &options.response_delegates);
+        context.insert("request_structs", &options.request_structs);
+        context.insert("pattern_constants", &options.pattern_constants);
+        context.insert("flag_enums", &options.flag_enums);
+        context.insert("include_guard_style", options.include_guard.as_str());
+        context.insert("include_guard_macro", &include_guard_macro(&file_name_base));
+        context.insert("skipped_operations", skipped);
+
+        // The spec's own top-level fields are taken too
+        for (key, value) in &options.extra_context {
+            if RESERVED_CONTEXT_KEYS.contains(&key.as_str()) || context.contains_key(key) {
+                return Err(anyhow!("Context key '{}' is reserved and cannot be set", key))
+                    .context(ErrorKind::InvalidArgument);
+            }
+            context.insert(key, value);
+        }
+
+        Ok(context)
+    }
+
+    /// Renders the template registered as `name` (see [`describe_error`](Self::describe_error)).
+    fn render_template(&self, name: &str, context: &tera::Context) -> Result<String> {
+        self.tera.render(name, context).map_err(|e| self.describe_error(name, &e))
+    }
+
+    /// Describes a failure to render the template registered as `name` with the template's
+    /// file and the position of the problem where known (see [`describe_template_error`]).
+    fn describe_error(&self, name: &str, error: &tera::Error) -> anyhow::Error {
+        let path = self.tera.get_template(name).ok().and_then(|t| t.path.clone());
+        let source = path.as_ref().and_then(|path| fs::read_to_string(path).ok());
+        let template = path
+            .as_ref()
+            .and_then(|path| Path::new(path).file_name())
+            .map_or(name.to_string(), |file| file.to_string_lossy().into_owned());
+        anyhow!(describe_template_error(error, &template, source.as_deref()))
+            .context(ErrorKind::Render)
+    }
+}
+
+/// Adds the template file at `path` as `name`, describing a syntax error with its position
+/// (see [`describe_template_error`]).
+fn add_user_template(tera: &mut Tera, name: &str, path: &Path) -> Result<()> {
+    tera.add_template_file(path, Some(name))
+        .map_err(|e| {
+            let source = fs::read_to_string(path).ok();
+            let template = path.display().to_string();
+            anyhow!(describe_template_error(&e, &template, source.as_deref()))
+        })
+        .context(ErrorKind::Render)
+}
+
+/// Copies the document of `spec` into the value templates see, with func names assigned,
+/// enum and cycle refs marked, with `options.split_read_write`, request and response
+/// variants split, and, with `options.deref`, internal `$ref`s inlined.
+///
+/// The document rather than the typed spec is used so vendor extensions reach templates.
+fn prepare_spec(spec: &LoadedSpec, options: &GenerateOptions) -> Value {
+    let mut spec_value = spec.document.clone();
+    if options.split_read_write {
+        split_read_write(&mut spec_value);
+    }
+    if options.deref {
+        dereference(&mut spec_value);
+    }
+    assign_func_names(&mut spec_value, options);
+    mark_enum_refs(&mut spec_value, options.flag_enums);
+    for (name, property) in mark_cycle_refs(&mut spec_value) {
+        logging::warning(&format!(
+            "F{}::{} closes a reference cycle, so it is held through a TSharedPtr that JSON \
+             (de)serialization skips",
+            name,
+            sanitize_identifier(&property)
+        ));
+    }
+    apply_format_types(&mut spec_value, &options.format_types);
+
+    if logging::enabled(LogLevel::Debug) {
+        for (path, method) in operations(&spec_value) {
+            let func_name = &spec_value["paths"][&path][&method][FUNC_NAME_KEY];
+            logging::debug(&format!(
+                "Processing {} {} as {}",
+                method.to_uppercase(),
+                path,
+                func_name.as_str().unwrap_or_default()
+            ));
+        }
+    }
+    spec_value
+}
+
+/// The output of [`GeneratorEngine::render`].
+#[derive(Debug, Clone, Default)]
+pub struct Rendered {
+    /// `(file name, contents)` pairs, header first.
+    pub files: Vec<(String, String)>,
+    /// Operations left out of `files` because they failed to render.
+    pub skipped: Vec<SkippedOperation>,
+}
+
+/// Lists the `(path, method)` of every operation under `paths`.
+fn operations(spec_value: &Value) -> Vec<(String, String)> {
+    let Some(paths) = spec_value.get("paths").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+
+    paths
+        .iter()
+        .filter_map(|(path, path_item)| Some((path, path_item.as_object()?)))
+        .flat_map(|(path, path_item)| {
+            path_item
+                .keys()
+                .filter(|method| HTTP_METHODS.contains(&method.as_str()))
+                .map(move |method| (path.clone(), method.clone()))
+        })
+        .collect()
+}
+
+/// Copies `spec_value` keeping only the `keep` operations, and only the paths that still
+/// have one.
+fn with_operations(spec_value: &Value, keep: &[(String, String)]) -> Value {
+    let mut spec_value = spec_value.clone();
+    if let Some(paths) = spec_value.get_mut("paths").and_then(Value::as_object_mut) {
+        paths.retain(|path, path_item| {
+            let Some(path_item) = path_item.as_object_mut() else {
+                return false;
+            };
+            path_item.retain(|method, _| {
+                !HTTP_METHODS.contains(&method.as_str())
+                    || keep.iter().any(|(p, m)| p == path && m == method)
+            });
+            path_item.keys().any(|method| HTTP_METHODS.contains(&method.as_str()))
+        });
+    }
+    spec_value
+}
+
+/// Describes a render failure on one line, without the [`ErrorKind`] prefix.
+fn render_error_message(error: &anyhow::Error) -> String {
+    let causes: Vec<String> = error.chain().skip(1).map(|cause| cause.to_string()).collect();
+    causes.join(": ").split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Runs a render job on a worker thread, turning a panic in it into an
+/// [`ErrorKind::Render`] error carrying the panic message, so one bad operation does not
+/// bring the whole process down.
+fn catch_render_panic<T>(job: impl FnOnce() -> Result<T>) -> Result<T> {
+    panic::catch_unwind(AssertUnwindSafe(job)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        Err(anyhow!("Render worker panicked: {}", message).context(ErrorKind::Render))
+    })
+}
+
+/// Derives an `#ifndef` guard macro from a file name base, e.g. `AnxApi` -> `ANXAPI_H`.
+///
+/// Characters that cannot appear in a macro name become `_`, and a leading digit is
+/// prefixed with `_`.
+pub fn include_guard_macro(file_name_base: &str) -> String {
+    let mut guard: String = file_name_base
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    if guard.starts_with(|c: char| c.is_ascii_digit()) {
+        guard.insert(0, '_');
+    }
+    guard.push_str("_H");
+    guard
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::openapi::IncludeGuard;
+    use oas3::from_json;
+    use std::thread;
+
+    fn spec(title: &str) -> LoadedSpec {
+        from_json(format!(
+            r#"{{
+                "openapi": "3.1.0",
+                "info": {{ "title": "{}", "version": "1.0.0" }},
+                "paths": {{}},
+                "components": {{ "schemas": {{}} }}
+            }}"#,
+            title
+        ))
+        .unwrap()
+        .into()
+    }
+
+    /// Renders `file_name` for `module_name` in memory, without extra includes.
+    fn target<'a>(file_name: &'a str, module_name: &'a str) -> RenderTarget<'a> {
+        RenderTarget { file_name, module_name, include_headers: &[], output_dir: Path::new("") }
+    }
+
+    #[test]
+    fn test_engine_renders_many_contexts() {
+        let options = GenerateOptions::default();
+        let engine = GeneratorEngine::new(&options).unwrap();
+
+        for (title, module_name) in [("First API", "FIRST_API"), ("Second API", "SECOND_API")] {
+            let rendered = engine
+                .render(&spec(title), &target("Api.h", module_name))
+                .unwrap()
+                .files;
+
+            assert_eq!(rendered.len(), 1);
+            assert_eq!(rendered[0].0, "Api.h");
+            assert!(rendered[0].1.contains(&format!("Title: {}", title)));
+            assert!(rendered[0].1.contains(&format!("class {} UApiLibrary", module_name)));
+        }
+    }
+
+    #[test]
+    fn test_reserved_context_keys_cover_engine_variables() {
+        let options = GenerateOptions::default();
+        let engine = GeneratorEngine::new(&options).unwrap();
+        let spec = spec("Reserved API");
+        let spec_value = prepare_spec(&spec, &options);
+
+        let context = engine
+            .render_context(&spec_value, &[], &spec.spec.info, &target("Api.h", "API"))
+            .unwrap()
+            .into_json();
+        let mut added: Vec<&str> = context
+            .as_object()
+            .unwrap()
+            .keys()
+            .map(String::as_str)
+            .filter(|key| spec_value.get(key).is_none())
+            .collect();
+        added.sort_unstable();
+        let mut reserved = RESERVED_CONTEXT_KEYS.to_vec();
+        reserved.sort_unstable();
+        assert_eq!(added, reserved);
+    }
+
+    #[test]
+    fn test_engine_parallel_renders_match() {
+        let options = GenerateOptions {
+            source: true,
+            ..Default::default()
+        };
+        let engine = GeneratorEngine::new(&options).unwrap();
+        let spec: LoadedSpec = from_json(
+            r#"{
+                "openapi": "3.1.0",
+                "info": { "title": "Parallel API", "version": "1.0.0" },
+                "paths": {
+                    "/items": { "get": { "tags": ["Items"], "responses": {} } },
+                    "/items/{id}": { "delete": { "tags": ["Items"], "responses": {} } }
+                },
+                "components": { "schemas": {} }
+            }"#,
+        )
+        .unwrap()
+        .into();
+        let render = || engine.render(&spec, &target("Api.h", "API")).unwrap().files;
+
+        // render_to_dir streams the same files to disk
+        let output_dir = std::env::temp_dir().join("banette_engine_sequential");
+        let _ = fs::remove_dir_all(&output_dir);
+        fs::create_dir_all(&output_dir).unwrap();
+        let to_dir = RenderTarget { output_dir: &output_dir, ..target("Api.h", "API") };
+        let sequential: Vec<(String, String)> = engine
+            .render_to_dir(&spec, &to_dir)
+            .unwrap()
+            .into_iter()
+            .map(|(path, _)| {
+                let name = path.file_name().unwrap().to_string_lossy().into_owned();
+                (name, fs::read_to_string(&path).unwrap())
+            })
+            .collect();
+        fs::remove_dir_all(&output_dir).unwrap();
+        assert_eq!(sequential.len(), 2);
+        assert_eq!(sequential[0].0, "Api.h");
+        assert_eq!(sequential[1].0, "Api.cpp");
+        assert_eq!(render(), sequential);
+
+        // One shared engine renders the same files from several threads at once
+        thread::scope(|scope| {
+            let handles: Vec<_> = (0..4).map(|_| scope.spawn(render)).collect();
+            for handle in handles {
+                assert_eq!(handle.join().unwrap(), sequential);
+            }
+        });
+    }
+
+    #[test]
+    fn test_catch_render_panic_turns_panics_into_render_errors() {
+        let value = catch_render_panic(|| Ok(42)).unwrap();
+        let error = catch_render_panic(|| -> Result<()> { panic!("template exploded") })
+            .unwrap_err();
+
+        assert_eq!(value, 42);
+        assert_eq!(ErrorKind::of(&error), Some(ErrorKind::Render));
+        assert!(format!("{:#}", error).contains("template exploded"));
+    }
+
+    #[test]
+    fn test_include_guard_macro() {
+        assert_eq!(include_guard_macro("AnxApi"), "ANXAPI_H");
+        assert_eq!(include_guard_macro("anx-api.v2"), "ANX_API_V2_H");
+        assert_eq!(include_guard_macro("2fa"), "_2FA_H");
+    }
+
+    #[test]
+    fn test_engine_include_guard_style() {
+        let render = |include_guard| {
+            let options = GenerateOptions {
+                include_guard,
+                ..Default::default()
+            };
+            let engine = GeneratorEngine::new(&options).unwrap();
+            engine
+                .render(&spec("Guarded API"), &target("Anx-Api.h", ""))
+                .unwrap()
+                .files
+                .swap_remove(0)
+                .1
+        };
+
+        let pragma = render(IncludeGuard::default());
+        assert!(pragma.contains("#pragma once\n"));
+        assert!(!pragma.contains("#ifndef"));
+
+        let ifndef = render(IncludeGuard::Ifndef);
+        assert!(ifndef.contains("#ifndef ANX_API_H\n#define ANX_API_H\n"));
+        assert!(ifndef.trim_end().ends_with("#endif // ANX_API_H"));
+        assert!(!ifndef.contains("#pragma once"));
+    }
+
+    #[test]
+    fn test_engine_disambiguates_function_names() {
+        let spec: LoadedSpec = from_json(
+            r#"{
+                "openapi": "3.1.0",
+                "info": { "title": "Colliding API", "version": "1.0.0" },
+                "paths": {
+                    "/api/resource": { "get": { "tags": [], "responses": {} } },
+                    "/api//resource": { "get": { "tags": [], "responses": {} } }
+                },
+                "components": { "schemas": {} }
+            }"#,
+        )
+        .unwrap()
+        .into();
+        let options = GenerateOptions::default();
+        let engine = GeneratorEngine::new(&options).unwrap();
+        let header =
+            engine.render(&spec, &target("Api.h", "")).unwrap().files.swap_remove(0).1;
+
+        assert!(header.contains("static FVoidCoroutine GET_Api_Resource("));
+        assert!(header.contains("static FVoidCoroutine GET_Api_Resource_2("));
+    }
+
+    #[test]
+    fn test_engine_renders_integer_enums() {
+        let spec: LoadedSpec = from_json(
+            r##"{
+                "openapi": "3.1.0",
+                "info": { "title": "Enum API", "version": "1.0.0" },
+                "paths": {},
+                "components": { "schemas": {
+                    "Priority": {
+                        "type": "integer",
+                        "enum": [0, 1, 5],
+                        "x-enum-varnames": ["None", "Low", "High"]
+                    },
+                    "Code": { "type": "integer", "enum": [-1, 300] },
+                    "Version": { "type": "integer", "const": 2, "x-enum-varnames": ["Current"] },
+                    "Task": { "type": "object", "properties": {
+                        "priority": { "$ref": "#/components/schemas/Priority" },
+                        "version": { "$ref": "#/components/schemas/Version" },
+                        "count": { "type": "integer" }
+                    } }
+                } }
+            }"##,
+        )
+        .unwrap()
+        .into();
+        let options = GenerateOptions::default();
+        let engine = GeneratorEngine::new(&options).unwrap();
+        let header =
+            engine.render(&spec, &target("Api.h", "")).unwrap().files.swap_remove(0).1;
+
+        assert!(header.contains(
+            "UENUM(BlueprintType)\nenum class EPriority : uint8\n{\n    None = 0,\n    Low = 1,\n    High = 5,\n};"
+        ));
+        assert!(header.contains(
+            "UENUM()\nenum class ECode : int32\n{\n    ValueMinus1 = -1,\n    Value300 = 300,\n};"
+        ));
+        assert!(!header.contains("struct FPriority"));
+        // Held as the number the JSON carries, with a typed accessor
+        assert!(header.contains("uint8 priority = 0;"));
+        // Without a 0 value, the field starts out as the first enumerator instead
+        assert!(header.contains(
+            "UENUM(BlueprintType)\nenum class EVersion : uint8\n{\n    Current = 2,\n};"
+        ));
+        assert!(header.contains("uint8 version = static_cast<uint8>(EVersion::Current);"));
+        assert!(header.contains(
+            "EPriority GetPriorityEnum() const { return static_cast<EPriority>(priority); }\n    \
+             void SetPriorityEnum(const EPriority InValue) \
+             { priority = static_cast<uint8>(InValue); }"
+        ));
+        // Other numbers keep their plain zero initializer and get no accessor
+        assert!(header.contains("int32 count = 0;"));
+        assert!(!header.contains("GetCountEnum"));
+        // Enums come before the structs using them
+        assert!(header.find("enum class EPriority") < header.find("struct FTask"));
+    }
+
+    #[test]
+    fn test_engine_breaks_reference_cycles() {
+        let spec: LoadedSpec = from_json(
+            r##"{
+                "openapi": "3.1.0",
+                "info": { "title": "Cycle API", "version": "1.0.0" },
+                "paths": {},
+                "components": { "schemas": {
+                    "Alpha": { "type": "object", "properties": {
+                        "beta": { "$ref": "#/components/schemas/Beta" }
+                    } },
+                    "Beta": { "type": "object", "properties": {
+                        "alpha": { "$ref": "#/components/schemas/Alpha" }
+                    } }
+                } }
+            }"##,
+        )
+        .unwrap()
+        .into();
+        let options = GenerateOptions::default();
+        let engine = GeneratorEngine::new(&options).unwrap();
+        let header =
+            engine.render(&spec, &target("Api.h", "")).unwrap().files.swap_remove(0).1;
+
+        let declaration = header.find("struct FAlpha;").unwrap();
+        let beta = header.find("struct FBeta\n").unwrap();
+        let alpha = header.find("struct FAlpha\n").unwrap();
+        assert!(declaration < beta && beta < alpha, "{}", header);
+        assert!(header.contains("    TSharedPtr<FAlpha> alpha;"));
+        assert!(header.contains("UPROPERTY(EditAnywhere, BlueprintReadWrite)\n    FBeta beta;"));
+    }
+
+    #[test]
+    fn test_engine_renders_flag_enums() {
+        let spec: LoadedSpec = from_json(
+            r##"{
+                "openapi": "3.1.0",
+                "info": { "title": "Flag API", "version": "1.0.0" },
+                "paths": {},
+                "components": { "schemas": {
+                    "Power": { "type": "string", "enum": ["on", "off"] },
+                    "Answer": { "type": "string", "enum": ["Yes", "yes"] },
+                    "Light": { "type": "object", "properties": {
+                        "power": { "$ref": "#/components/schemas/Power" }
+                    } }
+                } }
+            }"##,
+        )
+        .unwrap()
+        .into();
+        let options = GenerateOptions { flag_enums: true, ..Default::default() };
+        let engine = GeneratorEngine::new(&options).unwrap();
+        let header =
+            engine.render(&spec, &target("Api.h", "")).unwrap().files.swap_remove(0).1;
+
+        assert!(header.contains(
+            "UENUM(BlueprintType)\nenum class EPower : uint8\n{\n    On UMETA(DisplayName=\"on\"),\n    \
+             Off UMETA(DisplayName=\"off\"),\n};\n\ninline bool ToBool(const EPower Value)\n{\n    \
+             return Value == EPower::On;\n}"
+        ));
+        assert!(header.contains(
+            "enum class EAnswer : uint8\n{\n    Yes UMETA(DisplayName=\"Yes\"),\n    \
+             Yes_ UMETA(DisplayName=\"yes\"),\n};"
+        ));
+        assert!(!header.contains("struct FPower"));
+        assert!(header.contains("EPower power;"));
+
+        let options = GenerateOptions::default();
+        let engine = GeneratorEngine::new(&options).unwrap();
+        let header =
+            engine.render(&spec, &target("Api.h", "")).unwrap().files.swap_remove(0).1;
+        assert!(!header.contains("enum class EPower"));
+        assert!(!header.contains("EPower power;"));
+    }
+}
@@ -0,0 +1,130 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use crate::filter::flag_enum::is_flag_enum;
+use crate::filter::integer_enum::{integer_enum_default, integer_enum_storage};
+use crate::filter::to_ue_type::{ENUM_DEFAULT_KEY, ENUM_REF_KEY, INLINED_REF_KEY, UE_TYPE_KEY};
+use serde_json::Value;
+use std::collections::HashMap;
+
+const SCHEMA_REF_PREFIX: &str = "#/components/schemas/";
+
+/// Flags every `$ref` to a component schema rendered as a `UENUM` with [`ENUM_REF_KEY`],
+/// so `f_to_ue_type` names it `E<Name>` instead of `F<Name>`.
+///
+/// Integer enums are always `UENUM`s; two-value string enums only with `flag_enums`. A
+/// `$ref` to an integer enum also gets [`UE_TYPE_KEY`] set to the integer type the enum is
+/// stored in (see [`integer_enum_storage`]), so fields and parameters hold the number the
+/// JSON carries rather than an enumerator the JSON layer would write by name. When `0` is
+/// not one of the enum's values, it gets [`ENUM_DEFAULT_KEY`] too, so holders start out as
+/// a valid enumerator (see [`integer_enum_default`]).
+pub fn mark_enum_refs(document: &mut Value, flag_enums: bool) {
+    let enums: HashMap<String, Option<StoredEnum>> = document
+        .pointer("/components/schemas")
+        .and_then(Value::as_object)
+        .map(|schemas| {
+            schemas
+                .iter()
+                .filter_map(|(name, schema)| match integer_enum_storage(schema) {
+                    Some(storage) => {
+                        let default = integer_enum_default(schema)
+                            .map(|entry| format!("E{}::{}", name, entry));
+                        Some((name.clone(), Some(StoredEnum { storage, default })))
+                    }
+                    None if flag_enums && is_flag_enum(schema) => Some((name.clone(), None)),
+                    None => None,
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if !enums.is_empty() {
+        mark(document, &enums);
+    }
+}
+
+/// How the holders of an integer enum store it.
+struct StoredEnum {
+    /// The integer type, see [`integer_enum_storage`].
+    storage: &'static str,
+    /// The enumerator to start out as, see [`integer_enum_default`].
+    default: Option<String>,
+}
+
+fn mark(value: &mut Value, enums: &HashMap<String, Option<StoredEnum>>) {
+    match value {
+        Value::Object(map) => {
+            let target = map
+                .get("$ref")
+                .or_else(|| map.get(INLINED_REF_KEY))
+                .and_then(Value::as_str)
+                .and_then(|r| r.strip_prefix(SCHEMA_REF_PREFIX))
+                .and_then(|name| enums.get(name));
+            if let Some(stored) = target {
+                map.insert(ENUM_REF_KEY.to_string(), Value::Bool(true));
+                if let Some(stored) = stored {
+                    map.insert(UE_TYPE_KEY.to_string(), Value::String(stored.storage.to_string()));
+                    if let Some(default) = &stored.default {
+                        map.insert(ENUM_DEFAULT_KEY.to_string(), Value::String(default.clone()));
+                    }
+                }
+            }
+
+            for child in map.values_mut() {
+                mark(child, enums);
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                mark(item, enums);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_mark_enum_refs() {
+        let mut document = json!({
+            "paths": {
+                "/tasks": { "get": { "parameters": [
+                    { "name": "priority", "schema": { "$ref": "#/components/schemas/Priority" } }
+                ] } }
+            },
+            "components": { "schemas": {
+                "Priority": { "type": "integer", "enum": [0, 1, 2] },
+                "Version": { "type": "integer", "const": 2 },
+                "Power": { "type": "string", "enum": ["on", "off"] },
+                "Task": { "type": "object", "properties": {
+                    "priority": { "$ref": "#/components/schemas/Priority" },
+                    "version": { "$ref": "#/components/schemas/Version" },
+                    "power": { "$ref": "#/components/schemas/Power" },
+                    "owner": { "$ref": "#/components/schemas/User" }
+                } },
+                "User": { "type": "object" }
+            } }
+        });
+        let mut with_flags = document.clone();
+        mark_enum_refs(&mut document, false);
+        mark_enum_refs(&mut with_flags, true);
+
+        let param = &document["paths"]["/tasks"]["get"]["parameters"][0]["schema"];
+        assert_eq!(param[ENUM_REF_KEY], true);
+        let properties = &document["components"]["schemas"]["Task"]["properties"];
+        assert_eq!(properties["priority"][ENUM_REF_KEY], true);
+        assert_eq!(properties["priority"][UE_TYPE_KEY], "uint8");
+        assert!(properties["priority"].get(ENUM_DEFAULT_KEY).is_none());
+        assert_eq!(properties["version"][ENUM_DEFAULT_KEY], "EVersion::Value2");
+        assert!(properties["power"].get(ENUM_REF_KEY).is_none());
+        assert!(properties["owner"].get(ENUM_REF_KEY).is_none());
+        let properties = &with_flags["components"]["schemas"]["Task"]["properties"];
+        assert_eq!(properties["power"][ENUM_REF_KEY], true);
+        assert!(properties["power"].get(UE_TYPE_KEY).is_none());
+    }
+}
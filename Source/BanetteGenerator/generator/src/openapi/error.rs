@@ -0,0 +1,97 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use std::cell::RefCell;
+use std::fmt;
+
+/// Status code returned by the FFI `generate` when generation succeeds.
+pub const STATUS_OK: i32 = 0;
+
+/// Category of a generation failure.
+///
+/// Attached to errors as `anyhow` context so callers can recover it with
+/// [`ErrorKind::of`] and map it to a status code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// A required FFI argument was a null pointer.
+    NullArgument,
+    /// An argument was invalid, such as an FFI string that is not valid UTF-8.
+    InvalidArgument,
+    /// The spec could not be read, fetched, or parsed.
+    Load,
+    /// The template could not be prepared or rendered.
+    Render,
+    /// The output could not be written.
+    Write,
+}
+
+impl ErrorKind {
+    /// Nonzero status code reported across the FFI boundary.
+    pub fn code(self) -> i32 {
+        match self {
+            ErrorKind::NullArgument => 1,
+            ErrorKind::InvalidArgument => 2,
+            ErrorKind::Load => 3,
+            ErrorKind::Render => 4,
+            ErrorKind::Write => 5,
+        }
+    }
+
+    /// Returns the category attached to `error`, if any.
+    pub fn of(error: &anyhow::Error) -> Option<Self> {
+        error.downcast_ref::<ErrorKind>().copied()
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            ErrorKind::NullArgument => "Null argument",
+            ErrorKind::InvalidArgument => "Invalid argument",
+            ErrorKind::Load => "Failed to load OpenAPI spec",
+            ErrorKind::Render => "Failed to render template",
+            ErrorKind::Write => "Failed to write output",
+        };
+        f.write_str(message)
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Records the message of the most recent failure on this thread, or clears it.
+pub fn set_last_error(message: Option<String>) {
+    LAST_ERROR.with(|last| *last.borrow_mut() = message);
+}
+
+/// Returns the message of the most recent failure on this thread, if any.
+pub fn last_error() -> Option<String> {
+    LAST_ERROR.with(|last| last.borrow().clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_error_kind_survives_outer_context() {
+        let error = Err::<(), _>(anyhow::anyhow!("disk full"))
+            .context(ErrorKind::Write)
+            .context("while generating Foo.h")
+            .unwrap_err();
+
+        assert_eq!(ErrorKind::of(&error), Some(ErrorKind::Write));
+        assert_eq!(
+            format!("{:#}", error),
+            "while generating Foo.h: Failed to write output: disk full"
+        );
+    }
+
+    #[test]
+    fn test_error_kind_missing() {
+        assert_eq!(ErrorKind::of(&anyhow::anyhow!("plain")), None);
+    }
+}
@@ -0,0 +1,144 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use super::LineEnding;
+use crate::logging;
+use anyhow::{bail, Context, Result};
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Binary used by `--clang-format` when no path is given.
+pub const DEFAULT_CLANG_FORMAT: &str = "clang-format";
+
+/// Pipes `contents` through `binary`, which formats it as if it were the file at `path`
+/// so the nearest `.clang-format` applies. Anything the formatter prints to stderr is
+/// logged as a warning.
+pub fn clang_format(binary: &Path, path: &Path, contents: &str) -> Result<String> {
+    let mut child = Command::new(binary)
+        .arg(format!("--assume-filename={}", path.display()))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run {}", binary.display()))?;
+
+    // Write from another thread so a formatter that streams output cannot deadlock on a
+    // full stdout pipe.
+    let mut stdin = child.stdin.take().context("Formatter stdin unavailable")?;
+    let input = contents.to_string();
+    let writer = std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+
+    let output = child.wait_with_output().context("Failed to read formatter output")?;
+    writer
+        .join()
+        .map_err(|_| anyhow::anyhow!("Formatter input thread panicked"))?
+        .context("Failed to write formatter input")?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !stderr.trim().is_empty() {
+        logging::warning(&format!("{}: {}", binary.display(), stderr.trim()));
+    }
+
+    if !output.status.success() {
+        bail!("{} exited with {}", binary.display(), output.status);
+    }
+
+    String::from_utf8(output.stdout).context("Formatter produced invalid UTF-8")
+}
+
+/// Formats `contents` with [`clang_format`], keeping the unformatted text if it fails.
+pub fn clang_format_or_keep(binary: &Path, path: &Path, contents: String) -> String {
+    match clang_format(binary, path, &contents) {
+        Ok(formatted) => formatted,
+        Err(e) => {
+            logging::warning(&format!("Skipping clang-format: {:#}", e));
+            contents
+        }
+    }
+}
+
+/// Rewrites every line break in `contents` as `line_ending`.
+///
+/// Existing `\r\n` pairs are normalized first, so text that already has CRLF endings
+/// (e.g. from clang-format on Windows) is not converted twice.
+pub fn apply_line_ending(contents: &str, line_ending: LineEnding) -> String {
+    let normalized = contents.replace("\r\n", "\n");
+    match line_ending {
+        LineEnding::Lf => normalized,
+        LineEnding::Crlf => normalized.replace('\n', "\r\n"),
+    }
+}
+
+/// Prefixes `contents` with a UTF-8 byte order mark, unless it already starts with one.
+pub fn apply_bom(contents: String) -> String {
+    if contents.starts_with('\u{FEFF}') {
+        contents
+    } else {
+        format!("\u{FEFF}{}", contents)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_clang_format_missing_binary_keeps_contents() {
+        let binary = Path::new("/nonexistent/banette/clang-format");
+        let contents = "int  x;".to_string();
+
+        assert!(clang_format(binary, Path::new("Api.h"), &contents).is_err());
+        assert_eq!(clang_format_or_keep(binary, Path::new("Api.h"), contents), "int  x;");
+    }
+
+    #[test]
+    fn test_apply_line_ending() {
+        let contents = "#pragma once\n\nstruct FUser\r\n{\n};\n";
+
+        assert_eq!(
+            apply_line_ending(contents, LineEnding::Crlf),
+            "#pragma once\r\n\r\nstruct FUser\r\n{\r\n};\r\n"
+        );
+        assert_eq!(
+            apply_line_ending(contents, LineEnding::Lf),
+            "#pragma once\n\nstruct FUser\n{\n};\n"
+        );
+
+        // Converting again changes nothing
+        let crlf = apply_line_ending(contents, LineEnding::Crlf);
+        assert_eq!(apply_line_ending(&crlf, LineEnding::Crlf), crlf);
+        assert_eq!(crlf.matches("\r\n").count(), contents.matches('\n').count());
+        assert!(!crlf.contains("\r\r"));
+    }
+
+    #[test]
+    fn test_apply_bom() {
+        let with_bom = apply_bom("#pragma once\n".to_string());
+
+        assert_eq!(&with_bom.as_bytes()[..3], &[0xEF, 0xBB, 0xBF]);
+        assert_eq!(&with_bom[3..], "#pragma once\n");
+        assert_eq!(apply_bom(with_bom.clone()), with_bom);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_clang_format_uses_formatter_output() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join("banette_clang_format");
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir).unwrap();
+
+        let binary = dir.join("fake-clang-format");
+        fs::write(&binary, "#!/bin/sh\necho \"$1\" >&2\ntr a-z A-Z\n").unwrap();
+        fs::set_permissions(&binary, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let formatted = clang_format(&binary, Path::new("Api.h"), "int x;").unwrap();
+        assert_eq!(formatted, "INT X;");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
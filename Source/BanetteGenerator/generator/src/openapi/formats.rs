@@ -0,0 +1,64 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use super::schemas::schema_pointers;
+use crate::filter::to_ue_type::UE_TYPE_KEY;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Sets [`UE_TYPE_KEY`] on every schema whose `format` has an entry in `format_types`,
+/// so `f_to_ue_type` maps it to that type, e.g. `decimal` to a fixed-point `FDecimal`
+/// rather than `FString`.
+///
+/// Only schema positions are visited (see [`schema_pointers`]), so examples and
+/// extensions holding a `format` are left alone. Schemas with a `$ref` keep the type of
+/// their target.
+pub fn apply_format_types(document: &mut Value, format_types: &BTreeMap<String, String>) {
+    if format_types.is_empty() {
+        return;
+    }
+
+    for pointer in schema_pointers(document) {
+        let Some(Value::Object(schema)) = document.pointer_mut(&pointer) else {
+            continue;
+        };
+        let ue_type = match schema.get("format") {
+            Some(Value::String(format)) if !schema.contains_key("$ref") => format_types.get(format),
+            _ => None,
+        };
+        if let Some(ue_type) = ue_type {
+            schema.insert(UE_TYPE_KEY.to_string(), Value::String(ue_type.clone()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_apply_format_types() {
+        let mut document = json!({ "components": { "schemas": { "Invoice": {
+            "type": "object",
+            "properties": {
+                "total": { "type": "string", "format": "decimal" },
+                "format": { "type": "string", "format": "date-time" },
+                "lines": { "type": "array", "items": { "type": "string", "format": "decimal" } }
+            },
+            "example": { "total": { "format": "decimal" } },
+            "x-audit": { "format": "decimal" }
+        } } } });
+        let format_types = BTreeMap::from([("decimal".to_string(), "FDecimal".to_string())]);
+        apply_format_types(&mut document, &format_types);
+
+        let properties = &document["components"]["schemas"]["Invoice"]["properties"];
+        assert_eq!(properties["total"][UE_TYPE_KEY], "FDecimal");
+        assert_eq!(properties["lines"]["items"][UE_TYPE_KEY], "FDecimal");
+        assert!(properties["format"].get(UE_TYPE_KEY).is_none());
+        let invoice = &document["components"]["schemas"]["Invoice"];
+        assert!(invoice["example"]["total"].get(UE_TYPE_KEY).is_none());
+        assert!(invoice["x-audit"].get(UE_TYPE_KEY).is_none());
+    }
+}
@@ -0,0 +1,417 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use super::cycles::cycle_refs;
+use super::naming::HTTP_METHODS;
+use super::schemas::schema_pointers;
+use crate::filter::path_to_func_name::{func_name, FuncNaming};
+use crate::filter::sanitize_identifier::sanitize_identifier;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Kind of generation hazard reported by [`lint_spec`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LintKind {
+    /// The function name is derived from the path, so it changes when the path does and
+    /// may collide with another operation's.
+    MissingOperationId,
+    /// A parameter with neither `schema` nor `content` has no type to generate.
+    ParameterWithoutSchema,
+    /// A response media type without `schema` cannot be read into a typed body.
+    ResponseWithoutSchema,
+    /// `additionalProperties: true` or `{}` leaves the extra values untyped.
+    UntypedAdditionalProperties,
+    /// Different names that end up as the same C++ identifier.
+    IdentifierCollision,
+    /// A property closing a reference cycle is held through a `TSharedPtr`, which JSON
+    /// (de)serialization skips.
+    UnserializedCycleRef,
+}
+
+impl LintKind {
+    /// Short name used in the report.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LintKind::MissingOperationId => "missing-operation-id",
+            LintKind::ParameterWithoutSchema => "parameter-without-schema",
+            LintKind::ResponseWithoutSchema => "response-without-schema",
+            LintKind::UntypedAdditionalProperties => "untyped-additional-properties",
+            LintKind::IdentifierCollision => "identifier-collision",
+            LintKind::UnserializedCycleRef => "unserialized-cycle-ref",
+        }
+    }
+}
+
+/// Something in the spec that generates poor or surprising C++.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintWarning {
+    pub kind: LintKind,
+    /// Where it was found, e.g. `GET /users/{id}` or `#/components/schemas/User`.
+    pub location: String,
+    pub message: String,
+}
+
+impl fmt::Display for LintWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}: {}", self.kind.as_str(), self.location, self.message)
+    }
+}
+
+/// Scans a spec for things that generate poor C++, without changing it.
+///
+/// Reports operations without an `operationId`, parameters without a schema, response
+/// media types without a schema, untyped `additionalProperties`, names that become
+/// the same identifier (properties or parameters under [`sanitize_identifier`], and
+/// operations under the function naming of
+/// [`assign_func_names`](super::naming::assign_func_names) with the given `naming`, which
+/// would then get a numbered suffix), and properties closing a reference cycle, which are
+/// left out of JSON (see [`cycle_refs`]). Warnings are ordered by kind, then location.
+pub fn lint_spec(document: &Value, naming: &FuncNaming) -> Vec<LintWarning> {
+    let mut warnings = Vec::new();
+    let mut warn = |kind, location: &str, message: String| {
+        warnings.push(LintWarning {
+            kind,
+            location: location.to_string(),
+            message,
+        })
+    };
+
+    // 1. Operations, with their parameters and responses
+    let mut func_names: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (path, method, operation) in operations(document) {
+        let location = format!("{} {}", method.to_uppercase(), path);
+        let operation_id = operation
+            .get("operationId")
+            .and_then(Value::as_str)
+            .filter(|id| !id.trim().is_empty());
+        if operation_id.is_none() {
+            warn(
+                LintKind::MissingOperationId,
+                &location,
+                "no operationId, so the function name is derived from the path".to_string(),
+            );
+        }
+        func_names
+            .entry(func_name(path, method, operation_id, naming))
+            .or_default()
+            .push(location.clone());
+
+        let parameters = parameters(document, path, operation);
+        for parameter in &parameters {
+            if parameter.get("schema").is_none() && parameter.get("content").is_none() {
+                let name = parameter.get("name").and_then(Value::as_str).unwrap_or_default();
+                warn(
+                    LintKind::ParameterWithoutSchema,
+                    &location,
+                    format!("parameter '{}' has no schema", name),
+                );
+            }
+        }
+        let names = parameters
+            .iter()
+            .filter_map(|parameter| parameter.get("name").and_then(Value::as_str));
+        for (identifier, names) in colliding(names) {
+            warn(
+                LintKind::IdentifierCollision,
+                &location,
+                format!("parameters {} all become '{}'", names.join(", "), identifier),
+            );
+        }
+
+        let responses = operation.get("responses").and_then(Value::as_object);
+        for (status, response) in responses.into_iter().flatten() {
+            let content = response.get("content").and_then(Value::as_object);
+            for (media_type, media) in content.into_iter().flatten() {
+                if media.get("schema").is_none() {
+                    warn(
+                        LintKind::ResponseWithoutSchema,
+                        &location,
+                        format!("response {} '{}' has no schema", status, media_type),
+                    );
+                }
+            }
+        }
+    }
+    for (name, locations) in func_names {
+        if locations.len() > 1 {
+            warn(
+                LintKind::IdentifierCollision,
+                &locations.join(", "),
+                format!("operations share the function name '{}'", name),
+            );
+        }
+    }
+
+    // 2. Schemas, wherever the document has one
+    for pointer in schema_pointers(document) {
+        if let Some(schema) = document.pointer(&pointer).and_then(Value::as_object) {
+            check_schema(schema, &format!("#{}", pointer), &mut warn);
+        }
+    }
+
+    // 3. Reference cycles between component schemas
+    for (name, property) in cycle_refs(document) {
+        warn(
+            LintKind::UnserializedCycleRef,
+            &format!("#/components/schemas/{}", name),
+            format!(
+                "property '{}' closes a reference cycle, so it is held through a TSharedPtr \
+                 that JSON (de)serialization skips",
+                property
+            ),
+        );
+    }
+
+    warnings.sort_by(|a, b| (a.kind, &a.location).cmp(&(b.kind, &b.location)));
+    warnings
+}
+
+/// Every `(path, method, operation)` under `paths`.
+fn operations(document: &Value) -> Vec<(&str, &str, &Value)> {
+    let paths = document.get("paths").and_then(Value::as_object);
+    paths
+        .into_iter()
+        .flatten()
+        .filter_map(|(path, path_item)| Some((path, path_item.as_object()?)))
+        .flat_map(|(path, path_item)| {
+            path_item
+                .iter()
+                .filter(|(method, _)| HTTP_METHODS.contains(&method.as_str()))
+                .map(move |(method, operation)| (path.as_str(), method.as_str(), operation))
+        })
+        .collect()
+}
+
+/// The parameters of `operation` and its path item, with internal `$ref`s resolved.
+fn parameters<'a>(document: &'a Value, path: &str, operation: &'a Value) -> Vec<&'a Value> {
+    let shared = document["paths"][path].get("parameters");
+    [shared, operation.get("parameters")]
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_array)
+        .flatten()
+        .map(|parameter| {
+            parameter
+                .get("$ref")
+                .and_then(Value::as_str)
+                .and_then(|r| r.strip_prefix('#'))
+                .and_then(|pointer| document.pointer(pointer))
+                .unwrap_or(parameter)
+        })
+        .collect()
+}
+
+/// Groups `names` that sanitize to the same identifier, keeping only the groups of two
+/// or more, as `(identifier, quoted names)`.
+fn colliding<'a>(names: impl Iterator<Item = &'a str>) -> Vec<(String, Vec<String>)> {
+    let mut identifiers: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for name in names {
+        let group = identifiers.entry(sanitize_identifier(name)).or_default();
+        let quoted = format!("'{}'", name);
+        if !group.contains(&quoted) {
+            group.push(quoted);
+        }
+    }
+    identifiers.into_iter().filter(|(_, names)| names.len() > 1).collect()
+}
+
+/// Reports the hazards of one schema object found at `pointer`.
+fn check_schema(
+    schema: &Map<String, Value>,
+    pointer: &str,
+    warn: &mut impl FnMut(LintKind, &str, String),
+) {
+    let untyped = match schema.get("additionalProperties") {
+        Some(Value::Bool(allowed)) => *allowed,
+        Some(Value::Object(extra)) => extra.is_empty(),
+        _ => false,
+    };
+    if untyped {
+        warn(
+            LintKind::UntypedAdditionalProperties,
+            pointer,
+            "additionalProperties has no schema, so extra values are untyped".to_string(),
+        );
+    }
+
+    if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+        for (identifier, names) in colliding(properties.keys().map(String::as_str)) {
+            warn(
+                LintKind::IdentifierCollision,
+                pointer,
+                format!("properties {} all become '{}'", names.join(", "), identifier),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn crafted_spec() -> Value {
+        json!({
+            "openapi": "3.1.0",
+            "info": { "title": "Hazards", "version": "1.0.0" },
+            "paths": {
+                "/users": {
+                    "get": {
+                        "operationId": "listUsers",
+                        "parameters": [
+                            { "name": "page-size", "in": "query", "schema": {} },
+                            { "name": "page_size", "in": "header", "schema": {} },
+                            { "name": "filter", "in": "query" }
+                        ],
+                        "responses": {
+                            "200": {
+                                "content": {
+                                    "application/json": {
+                                        "schema": { "$ref": "#/components/schemas/User" }
+                                    },
+                                    "text/csv": {}
+                                }
+                            }
+                        }
+                    }
+                },
+                "/users/{id}": {
+                    "delete": { "responses": { "204": { "description": "Deleted" } } }
+                },
+                "/users//{id}": {
+                    "delete": { "responses": { "204": { "description": "Deleted" } } }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "User": {
+                        "type": "object",
+                        "properties": {
+                            "user-id": { "type": "string" },
+                            "user_id": { "type": "string" },
+                            "additionalProperties": { "type": "string" },
+                            "tags": { "type": "object", "additionalProperties": {} }
+                        },
+                        "additionalProperties": true,
+                        "example": { "additionalProperties": true },
+                        "x-extra": { "additionalProperties": {} }
+                    }
+                }
+            }
+        })
+    }
+
+    fn report(warnings: &[LintWarning], kind: LintKind) -> Vec<String> {
+        warnings
+            .iter()
+            .filter(|warning| warning.kind == kind)
+            .map(|warning| format!("{}: {}", warning.location, warning.message))
+            .collect()
+    }
+
+    #[test]
+    fn test_lint_missing_operation_id() {
+        let warnings = lint_spec(&crafted_spec(), &FuncNaming::default());
+        let message = "no operationId, so the function name is derived from the path";
+
+        assert_eq!(
+            report(&warnings, LintKind::MissingOperationId),
+            [
+                format!("DELETE /users//{{id}}: {}", message),
+                format!("DELETE /users/{{id}}: {}", message),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_missing_schemas() {
+        let warnings = lint_spec(&crafted_spec(), &FuncNaming::default());
+
+        assert_eq!(
+            report(&warnings, LintKind::ParameterWithoutSchema),
+            ["GET /users: parameter 'filter' has no schema"]
+        );
+        assert_eq!(
+            report(&warnings, LintKind::ResponseWithoutSchema),
+            ["GET /users: response 200 'text/csv' has no schema"]
+        );
+    }
+
+    #[test]
+    fn test_lint_untyped_additional_properties() {
+        let warnings = lint_spec(&crafted_spec(), &FuncNaming::default());
+
+        // A property named `additionalProperties` is not the keyword, and neither are
+        // keys of examples or extensions
+        assert_eq!(
+            report(&warnings, LintKind::UntypedAdditionalProperties)
+                .iter()
+                .map(|line| line.split(':').next().unwrap())
+                .collect::<Vec<_>>(),
+            [
+                "#/components/schemas/User",
+                "#/components/schemas/User/properties/tags",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_identifier_collisions() {
+        let warnings = lint_spec(&crafted_spec(), &FuncNaming::default());
+
+        assert_eq!(
+            report(&warnings, LintKind::IdentifierCollision),
+            [
+                "#/components/schemas/User: properties 'user-id', 'user_id' all become 'user_id'",
+                "DELETE /users//{id}, DELETE /users/{id}: \
+                 operations share the function name 'DELETE_Users_By_Id'",
+                "GET /users: parameters 'page-size', 'page_size' all become 'page_size'",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_unserialized_cycle_refs() {
+        let spec = json!({ "components": { "schemas": {
+            "Node": { "type": "object", "properties": {
+                "next": { "$ref": "#/components/schemas/Node" },
+                "label": { "type": "string" }
+            } }
+        } } });
+
+        assert_eq!(
+            report(&lint_spec(&spec, &FuncNaming::default()), LintKind::UnserializedCycleRef),
+            [
+                "#/components/schemas/Node: property 'next' closes a reference cycle, so it is \
+                 held through a TSharedPtr that JSON (de)serialization skips"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_clean_spec() {
+        let spec = json!({
+            "paths": {
+                "/users": {
+                    "get": {
+                        "operationId": "listUsers",
+                        "responses": { "200": { "description": "OK" } }
+                    }
+                }
+            }
+        });
+
+        assert!(lint_spec(&spec, &FuncNaming::default()).is_empty());
+        assert_eq!(
+            LintWarning {
+                kind: LintKind::MissingOperationId,
+                location: "GET /users".to_string(),
+                message: "no operationId".to_string(),
+            }
+            .to_string(),
+            "[missing-operation-id] GET /users: no operationId"
+        );
+    }
+}
@@ -2,9 +2,35 @@
  * Copyright 2019-Present tarnishablec. All Rights Reserved.
  */
 
+use super::cache::CacheOptions;
+use super::proxy::ProxyOptions;
+use super::swagger::{convert_swagger2, is_swagger2};
 use anyhow::{Context, Result};
+use flate2::read::GzDecoder;
 use oas3::{from_json, from_yaml, Spec};
+use std::fmt;
 use std::fs;
+use std::io::Read;
+use std::thread;
+use std::time::Duration;
+use ureq::http::Response;
+use ureq::Body;
+use url::Url;
+
+/// Leading bytes of every gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Number of retries after a transient download failure.
+pub const DEFAULT_RETRIES: u32 = 2;
+
+/// Number of redirects followed before a download gives up.
+pub const DEFAULT_MAX_REDIRECTS: u32 = 5;
+
+/// Delay before the first retry; each subsequent retry doubles it.
+pub const DEFAULT_RETRY_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Path that reads the spec from standard input.
+pub const STDIN_PATH: &str = "-";
 
 /// Format of the OpenAPI specification file.
 #[derive(Debug)]
@@ -13,11 +39,16 @@ pub enum Format {
     Yaml,
 }
 
-/// Infers the format from the path/URL suffix.
+/// Infers the format from the path/URL suffix. For URLs, any query or fragment is ignored.
 fn infer_format(path: &str) -> Result<Format> {
-    if path.ends_with(".json") {
+    let suffix_of = if path.contains("://") {
+        path.split(['?', '#']).next().unwrap_or(path)
+    } else {
+        path
+    };
+    if suffix_of.ends_with(".json") {
         Ok(Format::Json)
-    } else if path.ends_with(".yaml") || path.ends_with(".yml") {
+    } else if suffix_of.ends_with(".yaml") || suffix_of.ends_with(".yml") {
         Ok(Format::Yaml)
     } else {
         anyhow::bail!(
@@ -27,46 +58,574 @@ fn infer_format(path: &str) -> Result<Format> {
     }
 }
 
+/// Returns the local path a `file://` URI refers to, or `None` for anything else.
+///
+/// Percent-escapes are decoded and any query or fragment is dropped. An empty or
+/// `localhost` host refers to this machine; other hosts become a UNC-style `//host/...`
+/// path. Windows drive paths lose the slash before the drive letter, so
+/// `file:///C:/specs/api.yaml` yields `C:/specs/api.yaml`.
+pub fn file_uri_path(uri: &str) -> Option<String> {
+    let scheme = uri.get(..7)?;
+    if !scheme.eq_ignore_ascii_case("file://") {
+        return None;
+    }
+    let rest = uri[7..].split(['?', '#']).next().unwrap_or_default();
+
+    let (host, path) = match rest.find('/') {
+        Some(slash) => rest.split_at(slash),
+        None => (rest, ""),
+    };
+    let path = match host {
+        "" => path.to_string(),
+        _ if host.eq_ignore_ascii_case("localhost") => path.to_string(),
+        // `file://C:/...` puts the drive where the host belongs
+        _ if is_drive(host) => format!("{}{}", host, path),
+        _ => format!("//{}{}", host, path),
+    };
+    let path = percent_decode(&path);
+
+    match path.strip_prefix('/') {
+        Some(drive_path) if is_drive(drive_path.split('/').next().unwrap_or_default()) => {
+            Some(drive_path.replacen('|', ":", 1))
+        }
+        _ => Some(path),
+    }
+}
+
+/// Whether `segment` is a Windows drive such as `C:` (or the legacy `C|`).
+fn is_drive(segment: &str) -> bool {
+    let bytes = segment.as_bytes();
+    bytes.len() == 2 && bytes[0].is_ascii_alphabetic() && matches!(bytes[1], b':' | b'|')
+}
+
+/// Decodes `%XX` escapes, leaving malformed ones as they are.
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escape = (bytes[i] == b'%')
+            .then(|| input.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escape {
+            Some(byte) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Infers the format from the loaded content when the path suffix is inconclusive.
+///
+/// A body starting with `{` is JSON; anything else must parse as a YAML mapping.
+fn sniff_format(raw_spec: &str) -> Result<Format> {
+    let content = raw_spec.trim_start_matches('\u{feff}').trim_start();
+
+    if content.starts_with('{') {
+        return Ok(Format::Json);
+    }
+
+    match serde_yaml_bw::from_str::<serde_yaml_bw::Value>(content) {
+        Ok(serde_yaml_bw::Value::Mapping(_)) => Ok(Format::Yaml),
+        _ => anyhow::bail!(
+            "Failed to detect OpenAPI format from content. Expected a JSON object or a YAML mapping"
+        ),
+    }
+}
+
+/// Checks the document's `openapi`/`swagger` version field before the full parse, so
+/// unsupported documents fail with an actionable message instead of an `oas3` error.
+///
+/// OpenAPI 3.0.x and 3.1.x are accepted as-is; Swagger 2.0 is accepted and converted.
+fn check_version(document: &serde_json::Value) -> Result<()> {
+    const SUPPORTED: &str = "Banette supports OpenAPI 3.0.x and 3.1.x (Swagger 2.0 is converted)";
+
+    if let Some(version) = document.get("openapi") {
+        let Some(version) = version.as_str() else {
+            anyhow::bail!(
+                "{}; the `openapi` field must be a string such as \"3.1.0\", got {}",
+                SUPPORTED,
+                version
+            );
+        };
+        if version.starts_with("3.0.") || version.starts_with("3.1.") {
+            return Ok(());
+        }
+        anyhow::bail!("{}; got OpenAPI {}", SUPPORTED, version);
+    }
+
+    if let Some(version) = document.get("swagger") {
+        if is_swagger2(document) {
+            return Ok(());
+        }
+        anyhow::bail!(
+            "{}; got Swagger {}, upgrade the spec to Swagger 2.0 or OpenAPI 3.x",
+            SUPPORTED,
+            version
+        );
+    }
+
+    anyhow::bail!(
+        "{}; the document has no `openapi` or `swagger` version field",
+        SUPPORTED
+    )
+}
+
+/// Converts a Swagger 2.0 document to OpenAPI 3.x and parses the result.
+fn from_swagger2(document: &serde_json::Value) -> Result<LoadedSpec> {
+    let converted =
+        convert_swagger2(document).context("Failed to convert Swagger 2.0 document to OpenAPI 3")?;
+    let converted_str = serde_json::to_string_pretty(&converted)
+        .context("Failed to serialize converted Swagger 2.0 document")?;
+
+    let spec =
+        from_json(&converted_str).context("Failed to parse converted Swagger 2.0 document")?;
+    Ok(LoadedSpec { spec, document: converted })
+}
+
+/// A parsed spec together with the JSON document it was parsed from.
+///
+/// `oas3` only keeps the fields it models, so the document is what templates render: it
+/// still has every vendor extension (`x-*`) and other field exactly as the spec wrote it.
+#[derive(Debug, Clone)]
+pub struct LoadedSpec {
+    /// The typed spec, for the fields the generator reads itself.
+    pub spec: Spec,
+    /// The spec as JSON, YAML converted and Swagger 2.0 upgraded to OpenAPI 3.
+    pub document: serde_json::Value,
+}
+
+impl LoadedSpec {
+    /// Parses the typed spec out of `document`.
+    pub fn from_document(document: serde_json::Value) -> Result<Self> {
+        let spec = serde_json::from_value(document.clone())
+            .context("Failed to parse into OpenAPI Spec object")?;
+        Ok(Self { spec, document })
+    }
+}
+
+impl From<Spec> for LoadedSpec {
+    /// Wraps a spec built in code, whose document is just its serialization.
+    fn from(spec: Spec) -> Self {
+        let document = serde_json::to_value(&spec).expect("OpenAPI specs serialize to JSON");
+        Self { spec, document }
+    }
+}
+
+/// Options controlling how a remote spec is fetched.
+#[derive(Clone)]
+pub struct LoadOptions {
+    /// Extra request headers attached to HTTP(S) downloads, e.g. `Authorization`.
+    pub headers: Vec<(String, String)>,
+    /// Connect and read timeout; `None` keeps the `ureq` defaults.
+    pub timeout: Option<Duration>,
+    /// Retries after a transient failure (connection error, timeout, or 5xx status).
+    pub retries: u32,
+    /// Delay before the first retry, doubled for every further attempt.
+    pub retry_backoff: Duration,
+    /// Redirects followed before giving up; `0` fails on any redirect.
+    pub max_redirects: u32,
+    /// On-disk cache for HTTP(S) downloads; `None` always downloads.
+    pub cache: Option<CacheOptions>,
+    /// Proxies for HTTP(S) downloads; none by default. The CLI reads them from the
+    /// environment (see [`ProxyOptions::from_env`]).
+    pub proxy: ProxyOptions,
+}
+
+impl Default for LoadOptions {
+    fn default() -> Self {
+        Self {
+            headers: Vec::new(),
+            timeout: None,
+            retries: DEFAULT_RETRIES,
+            retry_backoff: DEFAULT_RETRY_BACKOFF,
+            max_redirects: DEFAULT_MAX_REDIRECTS,
+            cache: None,
+            proxy: ProxyOptions::default(),
+        }
+    }
+}
+
+impl fmt::Debug for LoadOptions {
+    // Header values usually carry credentials, so only the names are printed.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LoadOptions")
+            .field(
+                "headers",
+                &self
+                    .headers
+                    .iter()
+                    .map(|(name, _)| format!("{}: <redacted>", name))
+                    .collect::<Vec<_>>(),
+            )
+            .field("timeout", &self.timeout)
+            .field("retries", &self.retries)
+            .field("retry_backoff", &self.retry_backoff)
+            .field("max_redirects", &self.max_redirects)
+            .field("cache", &self.cache)
+            .field("proxy", &self.proxy)
+            .finish()
+    }
+}
+
 pub fn load_openapi_spec(path: &str) -> Result<Spec> {
-    let format = infer_format(path).context("Failed to detect OpenAPI format from path")?;
-
-    let raw_spec = if path.starts_with("http://") || path.starts_with("https://") {
-        ureq::get(path)
-            .call()
-            .context("Failed to make HTTP request")?
-            .into_body()
-            .read_to_string()
-            .context("Failed to read HTTP response body")?
+    load_openapi_spec_with(path, &LoadOptions::default())
+}
+
+/// Loads a spec like [`load_openapi_spec`], applying `options` to HTTP(S) downloads.
+///
+/// A redirected download takes its format from the URL it was finally served from.
+pub fn load_openapi_spec_with(path: &str, options: &LoadOptions) -> Result<Spec> {
+    Ok(load_openapi_document_with(path, options)?.spec)
+}
+
+/// Loads a spec like [`load_openapi_spec_with`], keeping the document it was parsed from.
+pub fn load_openapi_document_with(path: &str, options: &LoadOptions) -> Result<LoadedSpec> {
+    let mut source = path.to_string();
+    let raw_spec = if path == STDIN_PATH {
+        let mut raw_spec = String::new();
+        std::io::stdin()
+            .read_to_string(&mut raw_spec)
+            .context("Failed to read spec from standard input")?;
+        raw_spec
+    } else if path.starts_with("http://") || path.starts_with("https://") {
+        let (raw_spec, final_url) = fetch_cached_spec(path, options)?;
+        source = final_url;
+        raw_spec
+    } else if let Some(file_path) = file_uri_path(path) {
+        fs::read_to_string(&file_path)
+            .with_context(|| format!("Failed to read local file at: {}", file_path))?
     } else {
         fs::read_to_string(path)
             .with_context(|| format!("Failed to read local file at: {}", path))?
     };
 
+    // The suffix is the fast path; extensionless URLs fall back to sniffing the body.
+    let format = match infer_format(&source) {
+        Ok(format) => format,
+        Err(_) => sniff_format(&raw_spec)
+            .with_context(|| format!("Failed to detect OpenAPI format for: {}", path))?,
+    };
+
     match format {
         Format::Json => {
             let spec_json: serde_json::Value =
                 serde_json::from_str(&raw_spec).context("Failed to parse initial JSON content")?;
 
+            check_version(&spec_json)?;
+            if is_swagger2(&spec_json) {
+                return from_swagger2(&spec_json);
+            }
+
             // Re-serialize to pretty string for debugging purposes
             let pretty_str = serde_json::to_string_pretty(&spec_json)
                 .context("Failed to normalize JSON structure")?;
 
-            from_json(&pretty_str).context("Failed to parse into OpenAPI Spec object")
+            let spec = from_json(&pretty_str).context("Failed to parse into OpenAPI Spec object")?;
+            Ok(LoadedSpec { spec, document: spec_json })
         }
         Format::Yaml => {
             // Validate YAML with serde_yaml_bw before parsing with oas3
-            let _: serde_yaml_bw::Value = serde_yaml_bw::from_str(&raw_spec)
+            let spec_yaml: serde_yaml_bw::Value = serde_yaml_bw::from_str(&raw_spec)
                 .context("Failed to parse initial YAML content with serde-yaml-bw")?;
 
-            from_yaml(&raw_spec).context("Failed to parse YAML into OpenAPI Spec object")
+            let spec_json = serde_json::to_value(&spec_yaml)
+                .context("Failed to convert YAML content to a JSON document")?;
+
+            check_version(&spec_json)?;
+            if is_swagger2(&spec_json) {
+                return from_swagger2(&spec_json);
+            }
+
+            let spec =
+                from_yaml(&raw_spec).context("Failed to parse YAML into OpenAPI Spec object")?;
+            Ok(LoadedSpec { spec, document: spec_json })
         }
     }
 }
 
+/// Serves the spec from the download cache when fresh, otherwise downloads and caches it.
+///
+/// Returns the body with the URL it was served from, which is `url` for a cache hit.
+fn fetch_cached_spec(url: &str, options: &LoadOptions) -> Result<(String, String)> {
+    let Some(cache) = &options.cache else {
+        return fetch_remote_spec(url, options);
+    };
+
+    if let Some(body) = cache.read_fresh(url, &options.headers) {
+        return Ok((body, url.to_string()));
+    }
+
+    let (body, final_url) = fetch_remote_spec(url, options)?;
+    // The cache is only an optimization, so failing to store never fails the load.
+    cache.store(url, &options.headers, &body).ok();
+    Ok((body, final_url))
+}
+
+/// Downloads the spec body, asking the server for a gzip-encoded response, and returns it
+/// with the URL it was finally served from.
+///
+/// Transient failures are retried up to `options.retries` times with exponential backoff,
+/// and redirects are followed as described in [`follow_redirects`].
+fn fetch_remote_spec(url: &str, options: &LoadOptions) -> Result<(String, String)> {
+    let (response, final_url) = follow_redirects(url, options, |url, headers| {
+        let agent = build_agent(options, url)?;
+        let mut attempt = 0;
+        loop {
+            let mut request = agent.get(url).header("Accept-Encoding", "gzip");
+            for (name, value) in headers {
+                request = request.header(name.as_str(), value.as_str());
+            }
+
+            match request.call() {
+                Ok(response) => return Ok(response),
+                Err(error) if attempt < options.retries && is_transient(&error) => {
+                    thread::sleep(options.retry_backoff * 2u32.saturating_pow(attempt));
+                    attempt += 1;
+                }
+                Err(error) => {
+                    return Err(error).with_context(|| {
+                        format!(
+                            "Failed to make HTTP request (gave up after {} attempt(s))",
+                            attempt + 1
+                        )
+                    });
+                }
+            }
+        }
+    })?;
+
+    let body = response
+        .into_body()
+        .read_to_vec()
+        .context("Failed to read HTTP response body")?;
+
+    Ok((decode_body(body)?, final_url))
+}
+
+/// Sends `request` to `url` and on to every redirect target, returning the first
+/// non-redirect response and the URL that served it.
+///
+/// `request` is given the headers to send: `options.headers` while the chain stays on the
+/// origin (scheme, host and port) of `url`, and none once a redirect leaves it, so
+/// credentials never reach another server. A redirect from `https` to `http` is refused.
+///
+/// At most `options.max_redirects` redirects are followed. Exceeding that, or being sent
+/// back to a URL already visited, is an error naming the whole chain.
+fn follow_redirects(
+    url: &str,
+    options: &LoadOptions,
+    mut request: impl FnMut(&str, &[(String, String)]) -> Result<Response<Body>>,
+) -> Result<(Response<Body>, String)> {
+    let origin = Url::parse(url).map(|url| url.origin()).ok();
+    let mut headers = options.headers.as_slice();
+    let mut chain = vec![url.to_string()];
+    loop {
+        let current = chain.last().expect("chain starts with the requested URL");
+        let response = request(current, headers)?;
+        let Some(location) = redirect_location(&response) else {
+            let final_url = chain.pop().expect("chain starts with the requested URL");
+            return Ok((response, final_url));
+        };
+        let next = resolve_location(current, location)?;
+
+        if current.starts_with("https:") && next.scheme() != "https" {
+            anyhow::bail!(
+                "Refusing to follow redirect from https to {} while fetching spec: {} -> {}",
+                next.scheme(),
+                chain.join(" -> "),
+                next
+            );
+        }
+        if origin.as_ref() != Some(&next.origin()) {
+            headers = &[];
+        }
+
+        let next = next.to_string();
+        if chain.contains(&next) {
+            chain.push(next);
+            anyhow::bail!("Redirect loop while fetching spec: {}", chain.join(" -> "));
+        }
+        if chain.len() > options.max_redirects as usize {
+            chain.push(next);
+            anyhow::bail!(
+                "Too many redirects (more than {}) while fetching spec: {}",
+                options.max_redirects,
+                chain.join(" -> ")
+            );
+        }
+        chain.push(next);
+    }
+}
+
+/// The `Location` of a redirect response, or `None` for any other response.
+fn redirect_location(response: &Response<Body>) -> Option<&str> {
+    if !matches!(response.status().as_u16(), 301 | 302 | 303 | 307 | 308) {
+        return None;
+    }
+    response.headers().get("Location")?.to_str().ok()
+}
+
+/// Resolves a `Location` header against the URL it was received from.
+fn resolve_location(base: &str, location: &str) -> Result<Url> {
+    Url::parse(base)
+        .and_then(|base| base.join(location))
+        .with_context(|| format!("Invalid redirect location '{}' from {}", location, base))
+}
+
+/// Downloads the spec body unless the server reports that `etag` still matches.
+///
+/// Returns `None` for `304 Not Modified`, otherwise the body and its new `ETag`, if any.
+/// Failures are not retried; callers polling for changes simply try again later.
+pub fn fetch_if_modified(
+    url: &str,
+    options: &LoadOptions,
+    etag: Option<&str>,
+) -> Result<Option<(String, Option<String>)>> {
+    let (response, _) = follow_redirects(url, options, |url, headers| {
+        let agent = build_agent(options, url)?;
+        let mut request = agent.get(url).header("Accept-Encoding", "gzip");
+        for (name, value) in headers {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        if let Some(etag) = etag {
+            request = request.header("If-None-Match", etag);
+        }
+
+        request.call().context("Failed to make HTTP request")
+    })?;
+    if response.status() == 304 {
+        return Ok(None);
+    }
+
+    let etag = response
+        .headers()
+        .get("ETag")
+        .and_then(|v| v.to_str().ok())
+        .map(String::from);
+    let body = response
+        .into_body()
+        .read_to_vec()
+        .context("Failed to read HTTP response body")?;
+
+    Ok(Some((decode_body(body)?, etag)))
+}
+
+/// Builds the agent for downloading `url`, going through the proxy `options.proxy` picks
+/// for it. Redirects are left to [`follow_redirects`].
+fn build_agent(options: &LoadOptions, url: &str) -> Result<ureq::Agent> {
+    let agent = ureq::Agent::config_builder()
+        .proxy(options.proxy.proxy_for(url)?)
+        .max_redirects(0)
+        .timeout_connect(options.timeout)
+        .timeout_recv_response(options.timeout)
+        .timeout_recv_body(options.timeout)
+        .build()
+        .into();
+    Ok(agent)
+}
+
+/// Whether a failed request is worth retrying.
+fn is_transient(error: &ureq::Error) -> bool {
+    match error {
+        ureq::Error::StatusCode(status) => *status >= 500,
+        ureq::Error::Io(_) | ureq::Error::Timeout(_) | ureq::Error::ConnectionFailed => true,
+        _ => false,
+    }
+}
+
+/// Decodes a response body into text.
+///
+/// `ureq` already inflates bodies sent with `Content-Encoding: gzip`, but some CDNs serve
+/// a pre-compressed file without that header, so the gzip magic is sniffed as a fallback.
+fn decode_body(body: Vec<u8>) -> Result<String> {
+    if !body.starts_with(&GZIP_MAGIC) {
+        return String::from_utf8(body).context("HTTP response body is not valid UTF-8");
+    }
+
+    let mut decoded = String::new();
+    GzDecoder::new(body.as_slice())
+        .read_to_string(&mut decoded)
+        .context("Failed to decompress gzip-encoded response body")?;
+    Ok(decoded)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
     use std::io::Write;
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    const MINIMAL_JSON_SPEC: &str =
+        r#"{"openapi":"3.1.0","info":{"title":"Gzip API","version":"1.0.0"},"paths":{}}"#;
+
+    /// Serves each canned response to one incoming connection, in order, and records the
+    /// raw request head of every connection it accepted.
+    fn spawn_mock_server(responses: Vec<Vec<u8>>) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&requests);
+
+        thread::spawn(move || {
+            for response in responses {
+                let Ok((mut stream, _)) = listener.accept() else {
+                    return;
+                };
+
+                let mut head = Vec::new();
+                let mut byte = [0u8; 1];
+                while !head.ends_with(b"\r\n\r\n") {
+                    match stream.read(&mut byte) {
+                        Ok(1) => head.push(byte[0]),
+                        _ => break,
+                    }
+                }
+
+                recorded
+                    .lock()
+                    .unwrap()
+                    .push(String::from_utf8_lossy(&head).into_owned());
+                stream.write_all(&response).ok();
+            }
+        });
+
+        (base_url, requests)
+    }
+
+    /// Builds a raw HTTP/1.1 response that closes the connection after the body.
+    fn http_response(status: &str, headers: &[(&str, &str)], body: &[u8]) -> Vec<u8> {
+        let mut head = format!(
+            "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n",
+            status,
+            body.len()
+        );
+        for (name, value) in headers {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        head.push_str("\r\n");
+
+        let mut response = head.into_bytes();
+        response.extend_from_slice(body);
+        response
+    }
+
+    fn gzip(content: &str) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(content.as_bytes()).unwrap();
+        encoder.finish().unwrap()
+    }
 
     //noinspection SpellCheckingInspection
     #[test]
@@ -138,6 +697,36 @@ paths: {}
         fs::remove_file(temp_file).ok();
     }
 
+    #[test]
+    fn test_load_openapi_document_keeps_extensions() {
+        let yaml_content = r#"
+openapi: "3.1.0"
+info:
+  title: Extensions
+  version: "1.0.0"
+  x-ue-module: Players
+paths:
+  /players:
+    get:
+      x-ue-category: Roster
+      responses: {}
+x-generator-hints:
+  blueprint: true
+"#;
+        let temp_file = std::env::temp_dir().join("test_openapi_extensions.yaml");
+        fs::write(&temp_file, yaml_content).unwrap();
+
+        let options = LoadOptions::default();
+        let result = load_openapi_document_with(temp_file.to_str().unwrap(), &options);
+        fs::remove_file(&temp_file).ok();
+
+        let LoadedSpec { spec, document } = result.unwrap();
+        assert_eq!(spec.info.title, "Extensions");
+        assert_eq!(document["info"]["x-ue-module"], "Players");
+        assert_eq!(document["paths"]["/players"]["get"]["x-ue-category"], "Roster");
+        assert_eq!(document["x-generator-hints"]["blueprint"], true);
+    }
+
     #[test]
     fn test_load_openapi_spec_local_json() {
         let json_content = r#"{
@@ -167,6 +756,652 @@ paths: {}
         fs::remove_file(temp_file).ok();
     }
 
+    #[test]
+    fn test_load_openapi_spec_file_uri() {
+        let dir = std::env::temp_dir().join("banette file uri");
+        fs::create_dir_all(&dir).unwrap();
+        let temp_file = dir.join("api.yaml");
+        fs::write(
+            &temp_file,
+            "openapi: \"3.1.0\"\ninfo:\n  title: URI API\n  version: \"1.0.0\"\npaths: {}\n",
+        )
+        .unwrap();
+
+        let path = temp_file.to_str().unwrap();
+        for uri in [
+            format!("file://{}", path),
+            format!("file://localhost{}", path.replace(' ', "%20")),
+            format!("FILE://{}?rev=2", path),
+        ] {
+            let spec = load_openapi_spec(&uri).unwrap();
+            assert_eq!(spec.info.title, "URI API");
+        }
+
+        let result = load_openapi_spec("file:///nonexistent/api.yaml");
+        let err_msg = format!("{:#}", result.unwrap_err());
+        assert!(err_msg.contains("Failed to read local file at: /nonexistent/api.yaml"));
+
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_file_uri_path_unix() {
+        assert_eq!(file_uri_path("file:///abs/path/api.yaml").unwrap(), "/abs/path/api.yaml");
+        assert_eq!(file_uri_path("file://localhost/abs/api.json").unwrap(), "/abs/api.json");
+        assert_eq!(file_uri_path("file:///my%20specs/api.yaml#v1").unwrap(), "/my specs/api.yaml");
+        assert_eq!(
+            file_uri_path("file://server/share/api.yaml").unwrap(),
+            "//server/share/api.yaml"
+        );
+        assert!(file_uri_path("/abs/path/api.yaml").is_none());
+        assert!(file_uri_path("https://example.com/api.yaml").is_none());
+    }
+
+    #[test]
+    fn test_file_uri_path_windows() {
+        assert_eq!(file_uri_path("file:///C:/specs/api.yaml").unwrap(), "C:/specs/api.yaml");
+        assert_eq!(file_uri_path("file:///c|/specs/api.yaml").unwrap(), "c:/specs/api.yaml");
+        assert_eq!(file_uri_path("file://C:/specs/api.yaml").unwrap(), "C:/specs/api.yaml");
+        assert_eq!(
+            file_uri_path("file:///D:/My%20Specs/api.json").unwrap(),
+            "D:/My Specs/api.json"
+        );
+    }
+
+    #[test]
+    fn test_load_openapi_spec_gzip_content_encoding() {
+        let (base_url, requests) = spawn_mock_server(vec![http_response(
+            "200 OK",
+            &[("Content-Encoding", "gzip")],
+            &gzip(MINIMAL_JSON_SPEC),
+        )]);
+
+        let spec = load_openapi_spec(&format!("{}/docs/api.json", base_url)).unwrap();
+        assert_eq!(spec.info.title, "Gzip API");
+
+        let requests = requests.lock().unwrap();
+        assert!(requests[0].to_lowercase().contains("accept-encoding: gzip"));
+    }
+
+    #[test]
+    fn test_load_openapi_spec_gzip_without_content_encoding() {
+        // A pre-compressed file served as-is, without a Content-Encoding header
+        let (base_url, _) = spawn_mock_server(vec![http_response(
+            "200 OK",
+            &[("Content-Type", "application/gzip")],
+            &gzip(MINIMAL_JSON_SPEC),
+        )]);
+
+        let spec = load_openapi_spec(&format!("{}/docs/api.json", base_url)).unwrap();
+        assert_eq!(spec.info.title, "Gzip API");
+    }
+
+    #[test]
+    fn test_load_openapi_spec_uncompressed_http() {
+        let (base_url, _) = spawn_mock_server(vec![http_response(
+            "200 OK",
+            &[("Content-Type", "application/json")],
+            MINIMAL_JSON_SPEC.as_bytes(),
+        )]);
+
+        let spec = load_openapi_spec(&format!("{}/docs/api.json", base_url)).unwrap();
+        assert_eq!(spec.info.version, "1.0.0");
+    }
+
+    #[test]
+    fn test_load_openapi_spec_attaches_headers() {
+        let (base_url, requests) = spawn_mock_server(vec![http_response(
+            "200 OK",
+            &[],
+            MINIMAL_JSON_SPEC.as_bytes(),
+        )]);
+
+        let options = LoadOptions {
+            headers: vec![
+                ("Authorization".to_string(), "Bearer secret-token".to_string()),
+                ("X-Api-Key".to_string(), "abc123".to_string()),
+            ],
+            ..LoadOptions::default()
+        };
+        load_openapi_spec_with(&format!("{}/docs/api.json", base_url), &options).unwrap();
+
+        let requests = requests.lock().unwrap();
+        let head = requests[0].to_lowercase();
+        assert!(head.contains("authorization: bearer secret-token"));
+        assert!(head.contains("x-api-key: abc123"));
+    }
+
+    #[test]
+    fn test_fetch_if_modified() {
+        let (base_url, requests) = spawn_mock_server(vec![
+            http_response("200 OK", &[("ETag", "\"v1\"")], MINIMAL_JSON_SPEC.as_bytes()),
+            http_response("304 Not Modified", &[], b""),
+        ]);
+        let url = format!("{}/docs/api.json", base_url);
+        let options = LoadOptions::default();
+
+        let (body, etag) = fetch_if_modified(&url, &options, None).unwrap().unwrap();
+        assert_eq!(body, MINIMAL_JSON_SPEC);
+        assert_eq!(etag.as_deref(), Some("\"v1\""));
+
+        assert_eq!(fetch_if_modified(&url, &options, etag.as_deref()).unwrap(), None);
+        let requests = requests.lock().unwrap();
+        assert!(requests[1].to_lowercase().contains("if-none-match: \"v1\""));
+    }
+
+    #[test]
+    fn test_load_options_default_has_no_proxy() {
+        // Library callers opt in to proxies, whatever the environment holds
+        assert_eq!(LoadOptions::default().proxy, ProxyOptions::default());
+    }
+
+    #[test]
+    fn test_load_options_debug_redacts_header_values() {
+        let options = LoadOptions {
+            headers: vec![("Authorization".to_string(), "Bearer secret-token".to_string())],
+            ..LoadOptions::default()
+        };
+
+        let debug = format!("{:?}", options);
+        assert!(debug.contains("Authorization"));
+        assert!(!debug.contains("secret-token"));
+    }
+
+    #[test]
+    fn test_load_openapi_spec_retries_transient_failure() {
+        let (base_url, requests) = spawn_mock_server(vec![
+            http_response("503 Service Unavailable", &[], b""),
+            http_response("200 OK", &[], MINIMAL_JSON_SPEC.as_bytes()),
+        ]);
+
+        let options = LoadOptions {
+            retries: 2,
+            retry_backoff: Duration::from_millis(10),
+            ..LoadOptions::default()
+        };
+        let spec = load_openapi_spec_with(&format!("{}/docs/api.json", base_url), &options);
+
+        assert!(spec.is_ok(), "Expected retry to succeed: {:?}", spec.err());
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_load_openapi_spec_exhausted_retries() {
+        let (base_url, requests) = spawn_mock_server(vec![
+            http_response("503 Service Unavailable", &[], b""),
+            http_response("503 Service Unavailable", &[], b""),
+            http_response("503 Service Unavailable", &[], b""),
+        ]);
+
+        let options = LoadOptions {
+            retries: 2,
+            retry_backoff: Duration::from_millis(10),
+            ..LoadOptions::default()
+        };
+        let result = load_openapi_spec_with(&format!("{}/docs/api.json", base_url), &options);
+
+        let err_msg = format!("{:#}", result.unwrap_err());
+        assert!(err_msg.contains("gave up after 3 attempt(s)"), "{}", err_msg);
+        assert!(err_msg.contains("503"), "{}", err_msg);
+        assert_eq!(requests.lock().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_load_openapi_spec_does_not_retry_client_error() {
+        let (base_url, requests) = spawn_mock_server(vec![
+            http_response("404 Not Found", &[], b""),
+            http_response("200 OK", &[], MINIMAL_JSON_SPEC.as_bytes()),
+        ]);
+
+        let options = LoadOptions {
+            retry_backoff: Duration::from_millis(10),
+            ..LoadOptions::default()
+        };
+        let result = load_openapi_spec_with(&format!("{}/docs/api.json", base_url), &options);
+
+        assert!(result.is_err());
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    /// Request lines (`GET /path HTTP/1.1`) the mock server received, in order.
+    fn request_lines(requests: &Mutex<Vec<String>>) -> Vec<String> {
+        let requests = requests.lock().unwrap();
+        requests.iter().map(|head| head.lines().next().unwrap_or_default().to_string()).collect()
+    }
+
+    #[test]
+    fn test_load_openapi_spec_follows_redirect() {
+        let (base_url, requests) = spawn_mock_server(vec![
+            http_response("301 Moved Permanently", &[("Location", "/docs/v2/api.yaml")], b""),
+            http_response(
+                "200 OK",
+                &[],
+                b"openapi: \"3.1.0\"\ninfo:\n  title: Moved API\n  version: \"2.0.0\"\npaths: {}\n",
+            ),
+        ]);
+
+        // The body is YAML, so the final URL's suffix has to win over the requested one
+        let spec = load_openapi_spec(&format!("{}/docs/api.json", base_url)).unwrap();
+
+        assert_eq!(spec.info.title, "Moved API");
+        assert_eq!(
+            request_lines(&requests),
+            ["GET /docs/api.json HTTP/1.1", "GET /docs/v2/api.yaml HTTP/1.1"]
+        );
+    }
+
+    #[test]
+    fn test_load_openapi_spec_follows_redirect_chain() {
+        let (base_url, requests) = spawn_mock_server(vec![
+            http_response("302 Found", &[("Location", "/v1/api.json")], b""),
+            http_response("307 Temporary Redirect", &[("Location", "latest/api.json")], b""),
+            http_response("308 Permanent Redirect", &[("Location", "/v2/api.json?rev=3")], b""),
+            http_response("200 OK", &[], MINIMAL_JSON_SPEC.as_bytes()),
+        ]);
+
+        let options = LoadOptions {
+            max_redirects: 3,
+            ..LoadOptions::default()
+        };
+        let result = load_openapi_spec_with(&format!("{}/docs/api.json", base_url), &options);
+
+        assert!(result.is_ok(), "Expected redirects to be followed: {:?}", result.err());
+        assert_eq!(
+            request_lines(&requests),
+            [
+                "GET /docs/api.json HTTP/1.1",
+                "GET /v1/api.json HTTP/1.1",
+                "GET /v1/latest/api.json HTTP/1.1",
+                "GET /v2/api.json?rev=3 HTTP/1.1",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_load_openapi_spec_too_many_redirects() {
+        let (base_url, requests) = spawn_mock_server(vec![
+            http_response("301 Moved Permanently", &[("Location", "/a.json")], b""),
+            http_response("301 Moved Permanently", &[("Location", "/b.json")], b""),
+            http_response("301 Moved Permanently", &[("Location", "/c.json")], b""),
+            http_response("200 OK", &[], MINIMAL_JSON_SPEC.as_bytes()),
+        ]);
+
+        let options = LoadOptions {
+            max_redirects: 2,
+            ..LoadOptions::default()
+        };
+        let result = load_openapi_spec_with(&format!("{}/api.json", base_url), &options);
+
+        let err_msg = format!("{:#}", result.unwrap_err());
+        assert!(err_msg.contains("Too many redirects (more than 2)"), "{}", err_msg);
+        assert!(err_msg.ends_with(&format!("{}/b.json -> {}/c.json", base_url, base_url)));
+        assert_eq!(requests.lock().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_load_openapi_spec_no_redirects_allowed() {
+        let (base_url, requests) = spawn_mock_server(vec![
+            http_response("301 Moved Permanently", &[("Location", "/a.json")], b""),
+            http_response("200 OK", &[], MINIMAL_JSON_SPEC.as_bytes()),
+        ]);
+
+        let options = LoadOptions {
+            max_redirects: 0,
+            ..LoadOptions::default()
+        };
+        let result = load_openapi_spec_with(&format!("{}/api.json", base_url), &options);
+
+        let err_msg = format!("{:#}", result.unwrap_err());
+        assert!(err_msg.contains("Too many redirects (more than 0)"), "{}", err_msg);
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_load_openapi_spec_redirect_loop() {
+        let (base_url, requests) = spawn_mock_server(vec![
+            http_response("302 Found", &[("Location", "/b.json")], b""),
+            http_response("302 Found", &[("Location", "/api.json")], b""),
+        ]);
+
+        let result = load_openapi_spec(&format!("{}/api.json", base_url));
+
+        let err_msg = format!("{:#}", result.unwrap_err());
+        assert_eq!(
+            err_msg,
+            format!(
+                "Redirect loop while fetching spec: {0}/api.json -> {0}/b.json -> {0}/api.json",
+                base_url
+            )
+        );
+        assert_eq!(requests.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_load_openapi_spec_redirect_keeps_headers_on_same_origin() {
+        let (base_url, requests) = spawn_mock_server(vec![
+            http_response("302 Found", &[("Location", "/v2/api.json")], b""),
+            http_response("200 OK", &[], MINIMAL_JSON_SPEC.as_bytes()),
+        ]);
+
+        let options = LoadOptions {
+            headers: vec![("Authorization".to_string(), "Bearer secret-token".to_string())],
+            ..LoadOptions::default()
+        };
+        load_openapi_spec_with(&format!("{}/api.json", base_url), &options).unwrap();
+
+        let requests = requests.lock().unwrap();
+        assert!(requests[1].to_lowercase().contains("authorization: bearer secret-token"));
+    }
+
+    #[test]
+    fn test_load_openapi_spec_redirect_drops_headers_cross_origin() {
+        let (cdn_url, cdn_requests) = spawn_mock_server(vec![http_response(
+            "200 OK",
+            &[],
+            MINIMAL_JSON_SPEC.as_bytes(),
+        )]);
+        let location = format!("{}/api.json", cdn_url);
+        let (base_url, requests) = spawn_mock_server(vec![http_response(
+            "302 Found",
+            &[("Location", &location)],
+            b"",
+        )]);
+
+        let options = LoadOptions {
+            headers: vec![("Authorization".to_string(), "Bearer secret-token".to_string())],
+            ..LoadOptions::default()
+        };
+        let result = load_openapi_spec_with(&format!("{}/api.json", base_url), &options);
+
+        assert!(result.is_ok(), "Expected redirect to be followed: {:?}", result.err());
+        assert!(requests.lock().unwrap()[0].contains("secret-token"));
+        let cdn_requests = cdn_requests.lock().unwrap();
+        assert_eq!(cdn_requests.len(), 1);
+        assert!(!cdn_requests[0].to_lowercase().contains("authorization"), "{}", cdn_requests[0]);
+    }
+
+    #[test]
+    fn test_follow_redirects_refuses_https_downgrade() {
+        let mut requested = Vec::new();
+        let result = follow_redirects(
+            "https://example.com/api.json",
+            &LoadOptions::default(),
+            |url, _| {
+                requested.push(url.to_string());
+                Ok(Response::builder()
+                    .status(301)
+                    .header("Location", "http://example.com/api.json")
+                    .body(Body::builder().data(""))?)
+            },
+        );
+
+        let err_msg = format!("{:#}", result.unwrap_err());
+        let refused = "Refusing to follow redirect from https to http";
+        assert!(err_msg.starts_with(refused), "{}", err_msg);
+        assert_eq!(requested, ["https://example.com/api.json"]);
+    }
+
+    #[test]
+    fn test_resolve_location() {
+        let base = "https://example.com/docs/v1/api.json?rev=1";
+        let cdn = "https://cdn.example.com/a.json";
+        let resolve = |base, location| resolve_location(base, location).unwrap().to_string();
+
+        assert_eq!(resolve(base, cdn), cdn);
+        assert_eq!(resolve(base, "//cdn.example.com/a.json"), cdn);
+        assert_eq!(resolve(base, "/v2/api.json"), "https://example.com/v2/api.json");
+        assert_eq!(resolve(base, "api.yaml"), "https://example.com/docs/v1/api.yaml");
+        assert_eq!(resolve(base, "../v2/api.yaml"), "https://example.com/docs/v2/api.yaml");
+        assert_eq!(resolve("http://example.com", "a.yaml"), "http://example.com/a.yaml");
+    }
+
+    #[test]
+    fn test_load_openapi_spec_extensionless_json() {
+        let (base_url, _) = spawn_mock_server(vec![http_response(
+            "200 OK",
+            &[("Content-Type", "application/json")],
+            MINIMAL_JSON_SPEC.as_bytes(),
+        )]);
+
+        let spec = load_openapi_spec(&format!("{}/docs/openapi", base_url)).unwrap();
+        assert_eq!(spec.info.title, "Gzip API");
+    }
+
+    #[test]
+    fn test_load_openapi_spec_extensionless_yaml() {
+        let yaml_content = r#"
+openapi: "3.1.0"
+info:
+  title: Sniffed YAML
+  version: "1.0.0"
+paths: {}
+"#;
+        let (base_url, _) = spawn_mock_server(vec![http_response(
+            "200 OK",
+            &[],
+            yaml_content.as_bytes(),
+        )]);
+
+        let spec = load_openapi_spec(&format!("{}/docs/openapi", base_url)).unwrap();
+        assert_eq!(spec.info.title, "Sniffed YAML");
+    }
+
+    #[test]
+    fn test_load_openapi_spec_extensionless_invalid() {
+        let (base_url, _) = spawn_mock_server(vec![http_response(
+            "200 OK",
+            &[("Content-Type", "text/html")],
+            b"<html>Not Found</html>",
+        )]);
+
+        let result = load_openapi_spec(&format!("{}/docs/openapi", base_url));
+        let err_msg = format!("{:#}", result.unwrap_err());
+        assert!(err_msg.contains("Failed to detect OpenAPI format"), "{}", err_msg);
+    }
+
+    #[test]
+    fn test_sniff_format() {
+        assert!(matches!(sniff_format("  {\"openapi\": \"3.1.0\"}"), Ok(Format::Json)));
+        assert!(matches!(sniff_format("\u{feff}{}"), Ok(Format::Json)));
+        assert!(matches!(sniff_format("openapi: 3.1.0\n"), Ok(Format::Yaml)));
+        assert!(sniff_format("just a sentence").is_err());
+        assert!(sniff_format("- a\n- b\n").is_err());
+    }
+
+    #[test]
+    fn test_load_openapi_spec_swagger2_json() {
+        let json_content = r##"{
+  "swagger": "2.0",
+  "info": { "title": "Swagger JSON", "version": "0.9.0" },
+  "host": "api.example.com",
+  "basePath": "/v1",
+  "paths": {
+    "/pets": {
+      "get": {
+        "responses": {
+          "200": { "description": "OK", "schema": { "type": "array", "items": { "$ref": "#/definitions/Pet" } } }
+        }
+      }
+    }
+  },
+  "definitions": {
+    "Pet": { "type": "object", "properties": { "name": { "type": "string" } } }
+  }
+}"##;
+        let temp_file = std::env::temp_dir().join("test_swagger2.json");
+        fs::write(&temp_file, json_content).unwrap();
+
+        let result = load_openapi_spec(temp_file.to_str().unwrap());
+        fs::remove_file(&temp_file).ok();
+
+        let spec = result.unwrap();
+        assert_eq!(spec.info.title, "Swagger JSON");
+        assert!(spec.openapi.starts_with("3."));
+        assert!(spec.paths.as_ref().unwrap().contains_key("/pets"));
+        assert!(spec.components.is_some());
+    }
+
+    #[test]
+    fn test_load_openapi_spec_swagger2_yaml() {
+        let yaml_content = r##"
+swagger: "2.0"
+info:
+  title: Swagger YAML
+  version: "0.1.0"
+paths:
+  /pets:
+    post:
+      parameters:
+        - name: pet
+          in: body
+          schema:
+            $ref: "#/definitions/Pet"
+      responses:
+        201:
+          description: Created
+definitions:
+  Pet:
+    type: object
+"##;
+        let temp_file = std::env::temp_dir().join("test_swagger2.yaml");
+        fs::write(&temp_file, yaml_content).unwrap();
+
+        let result = load_openapi_spec(temp_file.to_str().unwrap());
+        fs::remove_file(&temp_file).ok();
+
+        let spec = result.unwrap();
+        assert_eq!(spec.info.title, "Swagger YAML");
+        assert!(spec.openapi.starts_with("3."));
+    }
+
+    #[test]
+    fn test_load_openapi_spec_cache_hit_skips_network() {
+        let (base_url, requests) = spawn_mock_server(vec![http_response(
+            "200 OK",
+            &[],
+            MINIMAL_JSON_SPEC.as_bytes(),
+        )]);
+        let cache_dir = std::env::temp_dir().join("banette_loader_cache_hit");
+        fs::remove_dir_all(&cache_dir).ok();
+
+        let options = LoadOptions {
+            retries: 0,
+            cache: Some(CacheOptions {
+                dir: cache_dir.clone(),
+                ttl: Duration::from_secs(60),
+            }),
+            ..LoadOptions::default()
+        };
+        let url = format!("{}/docs/api.json", base_url);
+        load_openapi_spec_with(&url, &options).unwrap();
+        let spec = load_openapi_spec_with(&url, &options).unwrap();
+
+        assert_eq!(spec.info.title, "Gzip API");
+        assert_eq!(requests.lock().unwrap().len(), 1);
+
+        fs::remove_dir_all(&cache_dir).ok();
+    }
+
+    #[test]
+    fn test_load_openapi_spec_cache_expired_refetches() {
+        let (base_url, requests) = spawn_mock_server(vec![
+            http_response("200 OK", &[], MINIMAL_JSON_SPEC.as_bytes()),
+            http_response("200 OK", &[], MINIMAL_JSON_SPEC.as_bytes()),
+        ]);
+        let cache_dir = std::env::temp_dir().join("banette_loader_cache_expired");
+        fs::remove_dir_all(&cache_dir).ok();
+
+        let options = LoadOptions {
+            retries: 0,
+            cache: Some(CacheOptions {
+                dir: cache_dir.clone(),
+                ttl: Duration::ZERO,
+            }),
+            ..LoadOptions::default()
+        };
+        let url = format!("{}/docs/api.json", base_url);
+        load_openapi_spec_with(&url, &options).unwrap();
+        load_openapi_spec_with(&url, &options).unwrap();
+
+        assert_eq!(requests.lock().unwrap().len(), 2);
+
+        fs::remove_dir_all(&cache_dir).ok();
+    }
+
+    #[test]
+    fn test_load_openapi_spec_local_file_bypasses_cache() {
+        let temp_file = std::env::temp_dir().join("test_openapi_no_cache.json");
+        fs::write(&temp_file, MINIMAL_JSON_SPEC).unwrap();
+        let cache_dir = std::env::temp_dir().join("banette_loader_cache_local");
+        fs::remove_dir_all(&cache_dir).ok();
+
+        let options = LoadOptions {
+            cache: Some(CacheOptions {
+                dir: cache_dir.clone(),
+                ttl: Duration::from_secs(60),
+            }),
+            ..LoadOptions::default()
+        };
+        load_openapi_spec_with(temp_file.to_str().unwrap(), &options).unwrap();
+
+        assert!(!cache_dir.exists());
+        fs::remove_file(&temp_file).ok();
+    }
+
+    #[test]
+    fn test_check_version_supported() {
+        assert!(check_version(&serde_json::json!({ "openapi": "3.0.3" })).is_ok());
+        assert!(check_version(&serde_json::json!({ "openapi": "3.1.0" })).is_ok());
+        assert!(check_version(&serde_json::json!({ "swagger": "2.0" })).is_ok());
+    }
+
+    #[test]
+    fn test_check_version_unsupported() {
+        let err_msg = check_version(&serde_json::json!({ "openapi": "4.0.0" }))
+            .unwrap_err()
+            .to_string();
+        assert!(err_msg.contains("supports OpenAPI 3.0.x and 3.1.x"));
+        assert!(err_msg.contains("got OpenAPI 4.0.0"));
+
+        let err_msg = check_version(&serde_json::json!({ "swagger": "1.2" }))
+            .unwrap_err()
+            .to_string();
+        assert!(err_msg.contains("got Swagger \"1.2\""));
+
+        let err_msg = check_version(&serde_json::json!({ "openapi": 3.1 }))
+            .unwrap_err()
+            .to_string();
+        assert!(err_msg.contains("must be a string"));
+    }
+
+    #[test]
+    fn test_load_openapi_spec_unsupported_version() {
+        let temp_file = std::env::temp_dir().join("test_openapi_v4.yaml");
+        fs::write(
+            &temp_file,
+            "openapi: \"4.0.0\"\ninfo:\n  title: Future\n  version: \"1\"\npaths: {}\n",
+        )
+        .unwrap();
+
+        let result = load_openapi_spec(temp_file.to_str().unwrap());
+        fs::remove_file(&temp_file).ok();
+
+        let err_msg = format!("{:#}", result.unwrap_err());
+        assert!(err_msg.contains("got OpenAPI 4.0.0"), "{}", err_msg);
+    }
+
+    #[test]
+    fn test_load_openapi_spec_missing_version() {
+        let temp_file = std::env::temp_dir().join("test_openapi_no_version.json");
+        fs::write(&temp_file, r#"{"info": {"title": "T", "version": "1"}, "paths": {}}"#).unwrap();
+
+        let result = load_openapi_spec(temp_file.to_str().unwrap());
+        fs::remove_file(&temp_file).ok();
+
+        let err_msg = format!("{:#}", result.unwrap_err());
+        assert!(err_msg.contains("no `openapi` or `swagger` version field"), "{}", err_msg);
+    }
+
     #[test]
     fn test_infer_format_json() {
         assert!(matches!(
@@ -207,6 +1442,18 @@ paths: {}
         ));
     }
 
+    #[test]
+    fn test_infer_format_file_uri() {
+        assert!(matches!(
+            infer_format("file:///C:/specs/api.yml?rev=2").unwrap(),
+            Format::Yaml
+        ));
+        assert!(matches!(
+            infer_format("https://example.com/openapi.json#/paths").unwrap(),
+            Format::Json
+        ));
+    }
+
     #[test]
     fn test_infer_format_unknown() {
         let result = infer_format("path/to/spec.txt");
@@ -0,0 +1,170 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use super::loader::LoadedSpec;
+use super::output::{compare_output, write_if_changed, WriteOutcome};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Sidecar recording which spec produced a set of generated files, written as
+/// `<file_name_base>.manifest.json` next to them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Spec path or URL as given to the generator.
+    pub source: String,
+    /// Further specs merged into it, in order.
+    pub merged: Vec<String>,
+    /// `sha256:` hash of the loaded (and merged) spec, see [`spec_hash`].
+    pub spec_hash: String,
+    /// Version of the generator that rendered the files.
+    pub generator_version: String,
+    /// When the files were generated, as an RFC 3339 UTC timestamp.
+    pub generated_at: String,
+    /// Names of the generated files.
+    pub files: Vec<String>,
+}
+
+impl Manifest {
+    /// Describes files generated now from `spec`.
+    pub fn new(
+        source: &str,
+        merged: &[String],
+        spec: &LoadedSpec,
+        files: Vec<String>,
+    ) -> Result<Self> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        Ok(Self {
+            source: source.to_string(),
+            merged: merged.to_vec(),
+            spec_hash: spec_hash(&spec.document)?,
+            generator_version: env!("CARGO_PKG_VERSION").to_string(),
+            generated_at: format_utc(now.as_secs()),
+            files,
+        })
+    }
+}
+
+/// Name of the manifest written for `file_name`, e.g. `Pets.manifest.json` for `Pets.h`.
+pub fn manifest_file_name(file_name: &str) -> String {
+    let file_name_base = Path::new(file_name).file_stem().unwrap_or_default();
+    format!("{}.manifest.json", file_name_base.to_string_lossy())
+}
+
+/// Hashes the spec document as loaded, i.e. normalized to JSON, so the hash follows its
+/// content rather than its formatting or whether it was YAML or JSON.
+pub fn spec_hash(document: &Value) -> Result<String> {
+    let document = serde_json::to_vec(document).context("Failed to serialize spec for hashing")?;
+    Ok(format!("sha256:{:x}", Sha256::digest(&document)))
+}
+
+/// Writes `manifest` to `path`, or with `dry_run` only reports what that would do.
+///
+/// An existing manifest that differs only in `generated_at` is left untouched, so the
+/// timestamp keeps recording when the current output was first generated.
+pub fn write_manifest(path: &Path, manifest: &Manifest, dry_run: bool) -> Result<WriteOutcome> {
+    let existing = fs::read_to_string(path)
+        .ok()
+        .and_then(|text| serde_json::from_str::<Manifest>(&text).ok());
+    if let Some(existing) = existing {
+        let restamped = Manifest {
+            generated_at: manifest.generated_at.clone(),
+            ..existing
+        };
+        if &restamped == manifest {
+            return Ok(WriteOutcome::Unchanged);
+        }
+    }
+
+    let mut contents =
+        serde_json::to_string_pretty(manifest).context("Failed to serialize manifest")?;
+    contents.push('\n');
+    if dry_run {
+        compare_output(path, &contents)
+    } else {
+        write_if_changed(path, &contents)
+    }
+}
+
+/// Formats seconds since the Unix epoch as an RFC 3339 UTC timestamp.
+fn format_utc(secs: u64) -> String {
+    let (days, time) = ((secs / 86_400) as i64, secs % 86_400);
+
+    // Civil date from days since the epoch, in 400-year eras starting on March 1st
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        time / 3_600,
+        time % 3_600 / 60,
+        time % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(spec_hash: &str, generated_at: &str) -> Manifest {
+        Manifest {
+            source: "api.json".to_string(),
+            merged: Vec::new(),
+            spec_hash: spec_hash.to_string(),
+            generator_version: "0.1.0".to_string(),
+            generated_at: generated_at.to_string(),
+            files: vec!["Api.h".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_format_utc() {
+        assert_eq!(format_utc(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_utc(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(format_utc(1_700_000_000), "2023-11-14T22:13:20Z");
+        assert_eq!(format_utc(4_102_444_799), "2099-12-31T23:59:59Z");
+    }
+
+    #[test]
+    fn test_manifest_file_name() {
+        assert_eq!(manifest_file_name("Pets.h"), "Pets.manifest.json");
+        assert_eq!(manifest_file_name("Pets"), "Pets.manifest.json");
+    }
+
+    #[test]
+    fn test_write_manifest_ignores_timestamp_only_changes() {
+        let dir = std::env::temp_dir().join("banette_write_manifest");
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("Api.manifest.json");
+
+        let first = manifest("sha256:aa", "2024-01-01T00:00:00Z");
+        assert_eq!(write_manifest(&path, &first, false).unwrap(), WriteOutcome::Created);
+
+        let later = manifest("sha256:aa", "2024-06-01T00:00:00Z");
+        assert_eq!(write_manifest(&path, &later, false).unwrap(), WriteOutcome::Unchanged);
+        assert!(fs::read_to_string(&path).unwrap().contains("2024-01-01T00:00:00Z"));
+
+        let changed = manifest("sha256:bb", "2024-06-01T00:00:00Z");
+        assert_eq!(write_manifest(&path, &changed, true).unwrap(), WriteOutcome::Updated);
+        assert!(fs::read_to_string(&path).unwrap().contains("sha256:aa"));
+        assert_eq!(write_manifest(&path, &changed, false).unwrap(), WriteOutcome::Updated);
+        assert!(fs::read_to_string(&path).unwrap().contains("2024-06-01T00:00:00Z"));
+    }
+}
@@ -0,0 +1,186 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use super::loader::LoadedSpec;
+use anyhow::{bail, Result};
+use serde_json::{Map, Value};
+
+/// Merges `others` into `base`, in order.
+///
+/// `paths` are unioned per operation and each `components` section (`schemas`,
+/// `responses`, ...) per name; `tags` are unioned by name. Identical duplicates are
+/// accepted, while differing definitions under the same key are an error naming that key.
+/// Everything else (`openapi`, `info`, `servers`, ...) comes from `base`. The documents are
+/// merged, so vendor extensions are kept, and the typed spec is parsed again from the result.
+pub fn merge_specs(base: LoadedSpec, others: Vec<LoadedSpec>) -> Result<LoadedSpec> {
+    if others.is_empty() {
+        return Ok(base);
+    }
+
+    let mut merged = base.document;
+    for other in others {
+        merge_spec_values(&mut merged, other.document)?;
+    }
+
+    LoadedSpec::from_document(merged).map_err(|e| e.context("Failed to rebuild merged spec"))
+}
+
+fn merge_spec_values(base: &mut Value, other: Value) -> Result<()> {
+    let (Some(base), Value::Object(mut other)) = (base.as_object_mut(), other) else {
+        bail!("Spec must be an object");
+    };
+
+    if let Some(Value::Object(paths)) = other.remove("paths") {
+        let base_paths = object_entry(base, "paths");
+        for (path, item) in paths {
+            match (base_paths.get_mut(&path), item) {
+                (Some(Value::Object(base_item)), Value::Object(item)) => {
+                    merge_unique(base_item, item, &format!("paths.{}", path))?;
+                }
+                (None, item) => {
+                    base_paths.insert(path, item);
+                }
+                (Some(existing), item) if *existing == item => {}
+                _ => bail!("Conflicting definitions for 'paths.{}'", path),
+            }
+        }
+    }
+
+    if let Some(Value::Object(components)) = other.remove("components") {
+        let base_components = object_entry(base, "components");
+        for (section, entries) in components {
+            let prefix = format!("components.{}", section);
+            match (base_components.get_mut(&section), entries) {
+                (Some(Value::Object(base_entries)), Value::Object(entries)) => {
+                    merge_unique(base_entries, entries, &prefix)?;
+                }
+                (None, entries) => {
+                    base_components.insert(section, entries);
+                }
+                _ => bail!("Conflicting definitions for '{}'", prefix),
+            }
+        }
+    }
+
+    if let Some(Value::Array(tags)) = other.remove("tags") {
+        let Value::Array(base_tags) = base.entry("tags").or_insert_with(|| Value::Array(Vec::new()))
+        else {
+            bail!("Conflicting definitions for 'tags'");
+        };
+        for tag in tags {
+            let name = tag.get("name");
+            match base_tags.iter().find(|t| t.get("name") == name) {
+                Some(existing) if *existing != tag => bail!(
+                    "Conflicting definitions for 'tags.{}'",
+                    name.and_then(Value::as_str).unwrap_or_default()
+                ),
+                Some(_) => {}
+                None => base_tags.push(tag),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn object_entry<'a>(map: &'a mut Map<String, Value>, key: &str) -> &'a mut Map<String, Value> {
+    let entry = map.entry(key).or_insert_with(|| Value::Object(Map::new()));
+    if !entry.is_object() {
+        *entry = Value::Object(Map::new());
+    }
+    entry.as_object_mut().expect("entry was just made an object")
+}
+
+/// Inserts every entry of `other` into `base`, failing if a key already holds a different value.
+fn merge_unique(
+    base: &mut Map<String, Value>,
+    other: Map<String, Value>,
+    prefix: &str,
+) -> Result<()> {
+    for (key, value) in other {
+        match base.get(&key) {
+            Some(existing) if *existing != value => {
+                bail!("Conflicting definitions for '{}.{}'", prefix, key)
+            }
+            Some(_) => {}
+            None => {
+                base.insert(key, value);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn spec(value: Value) -> LoadedSpec {
+        LoadedSpec::from_document(value).unwrap()
+    }
+
+    fn info(title: &str) -> Value {
+        json!({ "title": title, "version": "1.0.0" })
+    }
+
+    #[test]
+    fn test_merge_specs_disjoint() {
+        let public = spec(json!({
+            "openapi": "3.1.0",
+            "info": info("Public"),
+            "paths": { "/users": { "get": { "responses": {} } } },
+            "components": { "schemas": { "User": { "type": "object" } } },
+            "tags": [{ "name": "Users" }],
+            "x-owner": "platform"
+        }));
+        let internal = spec(json!({
+            "openapi": "3.1.0",
+            "info": info("Internal"),
+            "paths": {
+                "/users": { "delete": { "responses": {} } },
+                "/admin": { "get": { "responses": {}, "x-ue-category": "Admin" } }
+            },
+            "components": {
+                "schemas": { "Admin": { "type": "object" }, "User": { "type": "object" } }
+            },
+            "tags": [{ "name": "Admin" }, { "name": "Users" }]
+        }));
+
+        let merged = merge_specs(public, vec![internal]).unwrap();
+        assert_eq!(merged.spec.info.title, "Public");
+        let merged = merged.document;
+
+        assert_eq!(merged["info"]["title"], "Public");
+        assert_eq!(merged["x-owner"], "platform");
+        assert_eq!(merged["paths"]["/admin"]["get"]["x-ue-category"], "Admin");
+        assert_eq!(
+            merged["paths"]["/users"],
+            json!({ "get": { "responses": {} }, "delete": { "responses": {} } })
+        );
+        let schemas: Vec<_> = merged["components"]["schemas"].as_object().unwrap().keys().collect();
+        assert_eq!(schemas, vec!["Admin", "User"]);
+        assert_eq!(merged["tags"], json!([{ "name": "Users" }, { "name": "Admin" }]));
+    }
+
+    #[test]
+    fn test_merge_specs_conflicting_schema() {
+        let first = spec(json!({
+            "openapi": "3.1.0",
+            "info": info("First"),
+            "components": { "schemas": { "User": { "type": "object" } } }
+        }));
+        let second = spec(json!({
+            "openapi": "3.1.0",
+            "info": info("Second"),
+            "components": { "schemas": { "User": { "type": "string" } } }
+        }));
+
+        let error = merge_specs(first, vec![second]).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Conflicting definitions for 'components.schemas.User'"
+        );
+    }
+}
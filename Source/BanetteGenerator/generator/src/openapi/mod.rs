@@ -1,20 +1,51 @@
 /*
  * Copyright 2019-Present tarnishablec. All Rights Reserved.
  */
+pub mod batch;
+pub mod cache;
+pub mod cycles;
+pub mod deref;
+pub mod directions;
+pub mod engine;
+pub mod enums;
+pub mod error;
+pub mod format;
+pub mod formats;
+pub mod lint;
 pub mod loader;
+pub mod manifest;
+pub mod merge;
+pub mod naming;
+pub mod output;
 pub mod parser;
+pub mod proxy;
+pub mod schemas;
+pub mod swagger;
+pub mod template_error;
+pub mod validate;
+pub mod watch;
 
-use crate::filter::register_all_filters;
-use anyhow::anyhow;
-use loader::load_openapi_spec;
+use crate::filter::path_to_func_name::FuncNaming;
+use crate::logging;
+use anyhow::{anyhow, Context};
+use clap::ValueEnum;
+use engine::{GeneratorEngine, RenderTarget};
+use error::{last_error, set_last_error, ErrorKind, STATUS_OK};
+use format::{apply_bom, apply_line_ending, clang_format_or_keep};
+use loader::{load_openapi_document_with, LoadOptions, LoadedSpec};
+use manifest::{manifest_file_name, write_manifest, Manifest};
+use merge::merge_specs;
+use output::{compare_output, write_if_changed, GeneratedFile, Generation};
 use parser::parse_include_headers;
+use std::collections::BTreeMap;
 use std::ffi::{c_char, CStr};
 use std::fs;
-use std::fs::File;
-use std::io::Write;
-use std::path::Path;
-use tera::Tera;
+use std::path::{Path, PathBuf};
 
+/// Generates a header from an OpenAPI spec.
+///
+/// Returns `0` on success, otherwise the [`ErrorKind::code`] of the failure
+/// (`-1` if it could not be categorized). Details are logged through [`logging`].
 #[cbindgen_macro::namespace("banette::ffi::generator::openapi")]
 #[unsafe(no_mangle)]
 pub extern "C" fn generate(
@@ -23,46 +54,385 @@ pub extern "C" fn generate(
     file_name: *const c_char,
     module_name: *const c_char,
     extra_headers: *const c_char,
-) {
-    let result = (|| -> anyhow::Result<()> {
-        let convert_arg = |ptr: *const c_char, param_name: &str| -> anyhow::Result<&str> {
-            if ptr.is_null() {
-                anyhow::bail!("Argument cannot be null (received NULL pointer)",);
-            }
-            // SAFETY: CStr::from_ptr is safe because we check for null.
-            unsafe { CStr::from_ptr(ptr) }
-                .to_str()
-                .map_err(|e| anyhow!("Argument {} contains invalid UTF-8: {}", param_name, e))
-        };
-
-        let openapi_path = convert_arg(openapi_path, "openapi_path")?;
-        let output_dir = convert_arg(output_dir, "output_dir")?;
-        let file_name = convert_arg(file_name, "file_name")?;
-        let module_name = convert_arg(module_name, "module_name")?;
+) -> i32 {
+    ffi_generate(
+        openapi_path,
+        output_dir,
+        file_name,
+        module_name,
+        ffi_include_headers(extra_headers),
+    )
+}
 
-        // Parse extra_headers: can be null (empty) or a C string with concatenated includes
-        let include_headers = if extra_headers.is_null() {
-            Vec::new()
-        } else {
-            // SAFETY: CStr::from_ptr is safe because we check for null above.
-            let headers_str = convert_arg(extra_headers, "extra_headers")?;
-            parse_include_headers(headers_str)
-        };
+/// Same as [`generate`], with the extra headers given as an array of `header_count` C
+/// strings instead of one delimited string. Each entry is parsed like `extra_headers`, so
+/// it may be a bare `Custom.h` or a full `#include <vector>`. A null `headers` or zero
+/// `header_count` means no extra headers.
+///
+/// # Safety
+///
+/// A non-null `headers` must point to at least `header_count` C string pointers.
+#[cbindgen_macro::namespace("banette::ffi::generator::openapi")]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn generate_with_headers(
+    openapi_path: *const c_char,
+    output_dir: *const c_char,
+    file_name: *const c_char,
+    module_name: *const c_char,
+    headers: *const *const c_char,
+    header_count: usize,
+) -> i32 {
+    // SAFETY: forwarded from this function's contract.
+    let headers = unsafe { ffi_include_header_list(headers, header_count) };
+    ffi_generate(openapi_path, output_dir, file_name, module_name, headers)
+}
 
+/// Runs [`generate_safe`] for the FFI entry points and logs the outcome.
+fn ffi_generate(
+    openapi_path: *const c_char,
+    output_dir: *const c_char,
+    file_name: *const c_char,
+    module_name: *const c_char,
+    include_headers: anyhow::Result<Vec<String>>,
+) -> i32 {
+    let result = (|| -> anyhow::Result<Generation> {
         generate_safe(
-            openapi_path,
-            output_dir,
-            file_name,
-            module_name,
-            include_headers,
+            ffi_str(openapi_path, "openapi_path")?,
+            ffi_str(output_dir, "output_dir")?,
+            ffi_str(file_name, "file_name")?,
+            ffi_str(module_name, "module_name")?,
+            include_headers?,
+            &GenerateOptions::default(),
+        )
+    })();
+
+    match result {
+        Ok(generation) => {
+            set_last_error(None);
+            for file in &generation.files {
+                logging::info(&format!("{}: {}", file.path.display(), file.outcome));
+            }
+            for skipped in &generation.skipped {
+                logging::warning(&format!(
+                    "Skipped {} {}: {}",
+                    skipped.method.to_uppercase(),
+                    skipped.path,
+                    skipped.error
+                ));
+            }
+            logging::info("Code generation completed successfully.");
+            STATUS_OK
+        }
+        Err(e) => {
+            record_failure(&e);
+            ErrorKind::of(&e).map_or(-1, ErrorKind::code)
+        }
+    }
+}
+
+/// Renders a header from an OpenAPI spec into `buf` instead of writing a file.
+///
+/// Returns the buffer size needed for the whole header including its NUL terminator,
+/// or `0` on failure (see [`banette_last_error`]). The copy is truncated to fit `len`
+/// and is always NUL-terminated; a null `buf` or zero `len` only reports the required size.
+///
+/// # Safety
+///
+/// A non-null `buf` must point to at least `len` writable bytes.
+#[cbindgen_macro::namespace("banette::ffi::generator::openapi")]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn generate_string(
+    openapi_path: *const c_char,
+    file_name: *const c_char,
+    module_name: *const c_char,
+    extra_headers: *const c_char,
+    buf: *mut c_char,
+    len: usize,
+) -> usize {
+    let result = (|| -> anyhow::Result<String> {
+        generate_to_string(
+            ffi_str(openapi_path, "openapi_path")?,
+            ffi_str(file_name, "file_name")?,
+            ffi_str(module_name, "module_name")?,
+            ffi_include_headers(extra_headers)?,
+            &GenerateOptions::default(),
         )
     })();
 
-    if let Err(e) = result {
-        eprintln!("[Rust] Generation failed: {}", e);
+    match result {
+        Ok(rendered) => {
+            set_last_error(None);
+            // SAFETY: forwarded from this function's contract.
+            unsafe { copy_to_buffer(&rendered, buf, len) }
+        }
+        Err(e) => {
+            record_failure(&e);
+            0
+        }
+    }
+}
+
+/// Copies the message of the last failed generation call on this thread into `buf`.
+///
+/// Returns the buffer size needed for the whole message including its NUL terminator,
+/// or `0` if the last call succeeded. The copy is truncated to fit `len` and is always
+/// NUL-terminated; a null `buf` or zero `len` only reports the required size.
+///
+/// # Safety
+///
+/// A non-null `buf` must point to at least `len` writable bytes.
+#[cbindgen_macro::namespace("banette::ffi::generator::openapi")]
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn banette_last_error(buf: *mut c_char, len: usize) -> usize {
+    match last_error() {
+        // SAFETY: forwarded from this function's contract.
+        Some(message) => unsafe { copy_to_buffer(&message, buf, len) },
+        None => 0,
+    }
+}
+
+fn ffi_str<'a>(ptr: *const c_char, param_name: &str) -> anyhow::Result<&'a str> {
+    if ptr.is_null() {
+        return Err(anyhow!("Argument {} cannot be null", param_name))
+            .context(ErrorKind::NullArgument);
+    }
+    // SAFETY: CStr::from_ptr is safe because we check for null.
+    unsafe { CStr::from_ptr(ptr) }
+        .to_str()
+        .map_err(|e| anyhow!("Argument {} contains invalid UTF-8: {}", param_name, e))
+        .context(ErrorKind::InvalidArgument)
+}
+
+/// Parses `extra_headers`: can be null (empty) or a C string with concatenated includes.
+fn ffi_include_headers(ptr: *const c_char) -> anyhow::Result<Vec<String>> {
+    if ptr.is_null() {
+        Ok(Vec::new())
     } else {
-        println!("[Rust] Code generation completed successfully.");
+        ffi_str(ptr, "extra_headers").map(parse_include_headers)
+    }
+}
+
+/// Parses a `headers` array of `count` C strings, each like `extra_headers`.
+///
+/// # Safety
+///
+/// A non-null `headers` must point to at least `count` C string pointers.
+unsafe fn ffi_include_header_list(
+    headers: *const *const c_char,
+    count: usize,
+) -> anyhow::Result<Vec<String>> {
+    if headers.is_null() || count == 0 {
+        return Ok(Vec::new());
+    }
+    // SAFETY: `headers` is non-null and the caller guarantees `count` entries.
+    let entries = unsafe { std::slice::from_raw_parts(headers, count) };
+
+    let mut include_headers = Vec::new();
+    for (i, &entry) in entries.iter().enumerate() {
+        let entry = ffi_str(entry, &format!("headers[{}]", i))?;
+        include_headers.extend(parse_include_headers(entry));
     }
+    Ok(include_headers)
+}
+
+fn record_failure(error: &anyhow::Error) {
+    let message = format!("{:#}", error);
+    logging::error(&format!("Generation failed: {}", message));
+    set_last_error(Some(message));
+}
+
+/// Copies `text` into a C buffer and returns the size it needs including the NUL terminator.
+///
+/// # Safety
+///
+/// A non-null `buf` must point to at least `len` writable bytes.
+unsafe fn copy_to_buffer(text: &str, buf: *mut c_char, len: usize) -> usize {
+    let bytes = text.as_bytes();
+
+    if !buf.is_null() && len > 0 {
+        let count = bytes.len().min(len - 1);
+        // SAFETY: `buf` is non-null and the caller guarantees `len` writable bytes.
+        unsafe {
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), buf.cast::<u8>(), count);
+            *buf.add(count) = 0;
+        }
+    }
+
+    bytes.len() + 1
+}
+
+/// Optional settings for [`generate_safe`].
+#[derive(Debug, Clone, Default)]
+pub struct GenerateOptions {
+    /// How the spec is fetched when it is an HTTP(S) URL.
+    pub load: LoadOptions,
+    /// Inline internal `$ref`s into their usage sites before rendering
+    /// (see [`deref::dereference`]).
+    pub deref: bool,
+    /// Give schemas with `readOnly` properties a `<Name>Request` variant without them for
+    /// request bodies, and drop `writeOnly` properties from the schemas themselves (see
+    /// [`directions::split_read_write`]).
+    pub split_read_write: bool,
+    /// Also render `<file_name_base>.cpp` holding the function bodies, leaving only
+    /// declarations in the header.
+    pub source: bool,
+    /// Compare the rendered output against the files on disk without writing anything.
+    pub dry_run: bool,
+    /// Header template to render instead of the built-in `openapi.h.tera`.
+    pub template: Option<PathBuf>,
+    /// `.cpp` template to render with `source` instead of the built-in `openapi.cpp.tera`.
+    pub source_template: Option<PathBuf>,
+    /// Extra string variables for the template. Keys may not shadow the spec or the
+    /// generator's own variables such as `module_name`.
+    pub extra_context: BTreeMap<String, String>,
+    /// `clang-format` binary to run the output through before writing. The unformatted
+    /// output is kept if it is missing or fails.
+    pub clang_format: Option<PathBuf>,
+    /// Include guard the header template emits.
+    pub include_guard: IncludeGuard,
+    /// Line ending of the written files (see [`format::apply_line_ending`]).
+    pub line_ending: LineEnding,
+    /// Start the written files with a UTF-8 byte order mark.
+    pub bom: bool,
+    /// Fail on the first operation that cannot be rendered, instead of leaving it out
+    /// (see [`GeneratorEngine::render`]).
+    pub strict: bool,
+    /// Refuse to write output that fails [`validate::validate_cpp`].
+    pub validate: bool,
+    /// Further specs merged into the main one, in order (see [`merge::merge_specs`]).
+    pub merge: Vec<String>,
+    /// Words kept in this exact casing in generated function names, e.g. `API`, `ID`.
+    pub acronyms: Vec<String>,
+    /// Longest allowed function name; longer ones are truncated and suffixed with a hash
+    /// (see [`crate::filter::path_to_func_name::cap_func_name_len`]).
+    pub max_func_name_len: Option<usize>,
+    /// Segment naming operations on the root path `/`, e.g. `Root` for `GET_Root`; without
+    /// it they are named after their method alone.
+    pub root_func_segment: Option<String>,
+    /// Leading path segments left out of function names when they match, e.g. `v1` for
+    /// `GET_Player_Characters` from `/v1/player/characters`.
+    pub strip_path_prefix: Option<String>,
+    /// Also emit a Blueprint async action per operation, with `OnSuccess`/`OnFailure`
+    /// delegates carrying the response and error bodies.
+    pub blueprint_async: bool,
+    /// Also emit a `UDeveloperSettings` class with a `BaseUrl` defaulted from the spec's
+    /// first server, and prepend it to every request URL.
+    pub settings_class: bool,
+    /// Whether struct fields get `Get<Field>`/`Set<Field>` accessors.
+    pub field_accessors: FieldAccessors,
+    /// Also emit an `<Function>_AllPages` helper for paginated list operations, fetching
+    /// pages until one comes back short or empty, or until the next cursor is empty, and
+    /// concatenating them (see [`crate::filter::pagination::pagination_filter`]).
+    pub pagination: bool,
+    /// Query parameter names that mark a page number or cursor; the built-in
+    /// [`DEFAULT_PAGINATION_PARAMS`](crate::filter::pagination::DEFAULT_PAGINATION_PARAMS)
+    /// when empty.
+    pub pagination_params: Vec<String>,
+    /// Also declare an `FOn<Function>Response` dynamic delegate per operation for
+    /// callback-based clients (see
+    /// [`crate::filter::response_delegate::response_delegate_filter`]).
+    pub response_delegates: bool,
+    /// Bundle each operation's parameters and request body into an `F<Function>Request`
+    /// struct and have the function take that instead (see
+    /// [`crate::filter::request_fields::request_fields_filter`]).
+    pub request_structs: bool,
+    /// Also declare a `<Field>Pattern` string constant holding the `pattern` of each string
+    /// property, for runtime validation (see
+    /// [`crate::filter::pattern::pattern_constant_filter`]).
+    pub pattern_constants: bool,
+    /// Generate a `UENUM` with a `ToBool()` helper for two-value string enums such as
+    /// `["on", "off"]` (see [`crate::filter::flag_enum::flag_enum_filter`]). The JSON layer
+    /// reads and writes such enums by enumerator name, not by the spec's literal.
+    pub flag_enums: bool,
+    /// Unreal type for schemas of a given `format`, overriding the built-in mapping, e.g.
+    /// `decimal` to `FDecimal` where it would be an `FString` (see
+    /// [`formats::apply_format_types`]).
+    pub format_types: BTreeMap<String, String>,
+    /// Render straight into the output files instead of holding them in memory, to lower
+    /// peak memory on large specs. Files are then rewritten even when unchanged. Output
+    /// that has to be buffered anyway is (see [`GenerateOptions::streams`]).
+    pub stream: bool,
+    /// Also write `<file_name_base>.manifest.json` recording the spec source and hash, the
+    /// generator version and a timestamp (see [`manifest::Manifest`]).
+    pub manifest: bool,
+}
+
+impl GenerateOptions {
+    /// Whether output is streamed to disk: `stream` is set and nothing needs the whole
+    /// file in memory, i.e. no dry run comparing it, no clang-format, CRLF, BOM, or
+    /// validation pass over it.
+    pub fn streams(&self) -> bool {
+        self.stream
+            && !self.dry_run
+            && self.clang_format.is_none()
+            && self.line_ending == LineEnding::Lf
+            && !self.bom
+            && !self.validate
+    }
+
+    /// The function naming settings, as used by
+    /// [`assign_func_names`](naming::assign_func_names).
+    pub fn func_naming(&self) -> FuncNaming<'_> {
+        FuncNaming {
+            acronyms: &self.acronyms,
+            root: self.root_func_segment.as_deref(),
+            strip_prefix: self.strip_path_prefix.as_deref(),
+        }
+    }
+}
+
+/// Include guard style, exposed to templates as `include_guard_style` together with
+/// `include_guard_macro` (see [`engine::include_guard_macro`]).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum IncludeGuard {
+    /// `#pragma once`
+    #[default]
+    Pragma,
+    /// Classic `#ifndef`/`#define`/`#endif`
+    Ifndef,
+}
+
+impl IncludeGuard {
+    /// Name of the style as seen by templates.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            IncludeGuard::Pragma => "pragma",
+            IncludeGuard::Ifndef => "ifndef",
+        }
+    }
+}
+
+/// Accessors generated for struct fields, exposed to templates as `field_accessors`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum FieldAccessors {
+    /// Public fields only
+    #[default]
+    None,
+    /// Public fields with a getter and setter each
+    Alongside,
+    /// Private fields behind a getter and setter each
+    Instead,
+}
+
+impl FieldAccessors {
+    /// Name of the mode as seen by templates.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FieldAccessors::None => "none",
+            FieldAccessors::Alongside => "alongside",
+            FieldAccessors::Instead => "instead",
+        }
+    }
+}
+
+/// Line ending of the generated files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum LineEnding {
+    /// `\n`, as rendered
+    #[default]
+    Lf,
+    /// `\r\n`
+    Crlf,
 }
 
 /// Generates a safely rendered output file based on an OpenAPI specification and
@@ -74,31 +444,47 @@ pub extern "C" fn generate(
 /// - `file_name`: The desired name for the generated file.
 /// - `module_name`: The module name to be used in the rendered output.
 /// - `include_headers`: A vector of additional `#include` directives to inject into the generated header.
+/// - `options`: Optional settings, such as how the spec is downloaded and whether `$ref`s are inlined.
 ///
 /// # Returns
-/// - `anyhow::Result<()>`: Returns `Ok(())` if the operation completes successfully, or an error
-///   wrapped in `anyhow::Result` if any step of the generation process fails.
+/// - `anyhow::Result<Generation>`: Returns each written file and whether it was created,
+///   updated, or left unchanged, along with the operations left out, or an error wrapped in `anyhow::Result` if any step of the generation process fails.
 ///
 /// # Behavior
-/// 1. Loads the OpenAPI specification from the file located at the provided `path`.
+/// 1. Loads the OpenAPI specification from the file located at the provided `path`, merging in
+///    any `options.merge` specs.
 /// 2. Initializes a Tera template engine instance for rendering templates.
 /// 3. Ensures the existence of the `output_dir`, creating the directory if it is missing.
-/// 4. Registers custom Tera filters that provide specific processing utilities during rendering:
-///    - `to_ue_type`: Converts to an Unreal Engine type.
-///    - `is_required`: Determines if a field is required.
-///    - `path_to_func_name`: Converts a path to a function-friendly name.
-///    - `request_body_schema`: Extracts the request body schema.
-///    - `response_body_schema`: Extracts the response body schema.
-///    - `tags_to_pipe_separated`: Converts tags into a pipe-separated format.
-/// 5. Loads the OpenAPI template:
+/// 4. Registers the generator's `f_`-prefixed Tera filters, such as `f_to_ue_type` and
+///    `f_function_params` (see [`register_all_filters`](crate::filter::register_all_filters)
+///    for the full list).
+/// 5. Loads the OpenAPI template (`options.template` if set, otherwise the built-in one):
 ///    - In debug mode, it reads the template file from the filesystem.
 ///    - In release mode, it embeds the template as a raw string during compilation.
-/// 6. Creates a rendering context using the deserialized data from the OpenAPI spec and additional inputs:
+/// 6. Creates a rendering context from the spec's JSON document, vendor extensions included
+///    (with `$ref`s inlined when `options.deref` is set), and additional inputs:
 ///    - Inserts `module_name`, `file_name`, and `include_headers` into the context for further customization in the template.
-/// 7. Uses the Tera engine to render the template into a file format.
+///    - Inserts each `options.extra_context` entry, rejecting the spec's top-level fields
+///      and [`engine::RESERVED_CONTEXT_KEYS`].
+/// 7. Uses the Tera engine to render the template into a file format. Operations that fail
+///    to render are left out with a comment and reported in [`Generation::skipped`],
+///    unless `options.strict` is set (see [`GeneratorEngine::render`]).
+/// 8. With `options.source`, also renders `<file_name_base>.cpp` from `openapi.cpp.tera`.
+/// 9. With `options.validate`, rejects files that fail [`validate::validate_cpp`]. With
+///    `options.clang_format`, formats each file (see [`format::clang_format`]). Then
+///    applies `options.line_ending` and `options.bom`.
+/// 10. Writes each file only if its contents changed (see [`write_if_changed`]), or with
+///     `options.dry_run` only reports what would change (see [`compare_output`]).
+/// 11. With `options.manifest`, also writes a manifest of the generation, which counts
+///     as changed only when more than its timestamp would (see [`write_manifest`]).
+///
+/// When [`GenerateOptions::streams`], steps 7 to 10 are replaced by rendering each file
+/// straight to disk (see [`GeneratorEngine::render_to_dir`]), falling back to them if an
+/// operation fails to render and `options.strict` is not set.
 ///
 /// # Side Effects
-/// - Writes a generated file to the specified `output_dir` under the provided `file_name`.
+/// - Writes a generated file to the specified `output_dir` under the provided `file_name`, leaving
+///   an identical existing file (and its modification time) untouched.
 ///
 /// # Errors
 /// - Returns an error if:
@@ -119,6 +505,7 @@ pub extern "C" fn generate(
 ///         "generated_file.h",
 ///         "MyModule",
 ///         vec!["#include \"custom.h\";".to_string()],
+///         &GenerateOptions::default(),
 ///     )?;
 ///     Ok(())
 /// }
@@ -135,52 +522,1865 @@ pub fn generate_safe(
     file_name: &str,
     module_name: &str,
     include_headers: Vec<String>,
-) -> anyhow::Result<()> {
-    let spec = load_openapi_spec(path)?;
-    let mut tera = Tera::default();
+    options: &GenerateOptions,
+) -> anyhow::Result<Generation> {
+    let engine = GeneratorEngine::new(options)?;
+    generate_with_engine(&engine, path, output_dir, file_name, module_name, &include_headers)
+}
 
+/// Like [`generate_safe`], but renders with a prebuilt [`GeneratorEngine`] so repeated
+/// calls skip filter registration and template compilation. The engine's options (see
+/// [`GeneratorEngine::options`]) stand in for `options`.
+pub fn generate_with_engine(
+    engine: &GeneratorEngine,
+    path: &str,
+    output_dir: &str,
+    file_name: &str,
+    module_name: &str,
+    include_headers: &[String],
+) -> anyhow::Result<Generation> {
+    let options = engine.options();
+    logging::debug(&format!("Loading {}", path));
+    let spec = load_spec(path, options).context(ErrorKind::Load)?;
     let out_path = Path::new(output_dir);
 
-    if !out_path.exists() {
-        fs::create_dir_all(out_path)?;
+    if !options.dry_run && !out_path.exists() {
+        fs::create_dir_all(out_path).context(ErrorKind::Write)?;
     }
 
-    let file_path = out_path.join(file_name);
-    let file_name_base = file_path.file_stem().unwrap_or_default().to_string_lossy();
+    let target = RenderTarget { file_name, module_name, include_headers, output_dir: out_path };
+    let mut generation = write_outputs(engine, &spec, &target)?;
+
+    if options.manifest {
+        let names = generation
+            .files
+            .iter()
+            .map(|file| file.path.file_name().unwrap_or_default().to_string_lossy().into_owned())
+            .collect();
+        let manifest = Manifest::new(path, &options.merge, &spec, names).context(ErrorKind::Write)?;
+        let manifest_path = out_path.join(manifest_file_name(file_name));
+        let outcome =
+            write_manifest(&manifest_path, &manifest, options.dry_run).context(ErrorKind::Write)?;
+        generation.files.push(GeneratedFile { path: manifest_path, outcome });
+    }
 
-    register_all_filters(&mut tera);
+    Ok(generation)
+}
 
-    #[cfg(debug_assertions)]
-    {
-        let template_path = concat!(env!("CARGO_MANIFEST_DIR"), "/templates/openapi.h.tera");
-        tera.add_template_file(template_path, Some("openapi_template"))?;
+/// Renders the files for `spec` into `target.output_dir`, steps 7 to 10 of
+/// [`generate_safe`].
+fn write_outputs(
+    engine: &GeneratorEngine,
+    spec: &LoadedSpec,
+    target: &RenderTarget,
+) -> anyhow::Result<Generation> {
+    let options = engine.options();
+    if options.streams() {
+        match engine.render_to_dir(spec, target) {
+            Ok(files) => {
+                let files =
+                    files.into_iter().map(|(path, outcome)| GeneratedFile { path, outcome });
+                return Ok(Generation { files: files.collect(), skipped: Vec::new() });
+            }
+            // Finding the operations to skip needs the buffered path
+            Err(e) if !options.strict && ErrorKind::of(&e) == Some(ErrorKind::Render) => {}
+            Err(e) => return Err(e),
+        }
     }
 
-    #[cfg(not(debug_assertions))]
-    {
-        tera.add_raw_template(
-            "openapi_template",
-            include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/templates/openapi.h.tera")),
-        )?;
+    let rendered = engine.render(spec, target)?;
+
+    let files = rendered
+        .files
+        .into_iter()
+        .map(|(name, contents)| {
+            if options.validate {
+                check_rendered(&name, &contents)?;
+            }
+            let path = target.output_dir.join(name);
+            let contents = match &options.clang_format {
+                Some(binary) => clang_format_or_keep(binary, &path, contents),
+                None => contents,
+            };
+            let contents = apply_line_ending(&contents, options.line_ending);
+            let contents = if options.bom { apply_bom(contents) } else { contents };
+            logging::debug(&format!("Rendered {} ({} bytes)", path.display(), contents.len()));
+            let outcome = if options.dry_run {
+                compare_output(&path, &contents)
+            } else {
+                write_if_changed(&path, &contents)
+            }
+            .context(ErrorKind::Write)?;
+            Ok(GeneratedFile { path, outcome })
+        })
+        .collect::<anyhow::Result<_>>()?;
+
+    Ok(Generation { files, skipped: rendered.skipped })
+}
+
+/// Fails with every [`validate::validate_cpp`] issue in the rendered `name`.
+fn check_rendered(name: &str, contents: &str) -> anyhow::Result<()> {
+    let issues = validate::validate_cpp(contents);
+    if issues.is_empty() {
+        return Ok(());
     }
 
-    let mut context = tera::Context::from_serialize(&spec)?;
-    context.insert("module_name", &module_name);
-    context.insert("file_name", &file_name_base);
-    context.insert("include_headers", &include_headers);
+    let issues: Vec<String> = issues.iter().map(|issue| format!("{}: {}", name, issue)).collect();
+    Err(anyhow!("Rendered output looks malformed:\n{}", issues.join("\n")))
+        .context(ErrorKind::Render)
+}
 
-    let rendered = tera.render("openapi_template", &context)?;
+/// Loads the spec at `path` and merges `options.merge` into it.
+fn load_spec(path: &str, options: &GenerateOptions) -> anyhow::Result<LoadedSpec> {
+    let spec = load_openapi_document_with(path, &options.load)?;
+    let others = options
+        .merge
+        .iter()
+        .map(|other| load_openapi_document_with(other, &options.load))
+        .collect::<anyhow::Result<Vec<_>>>()?;
 
-    let mut file = File::create(&file_path)?;
+    merge_specs(spec, others)
+}
 
-    file.write_all(rendered.as_bytes())?;
+/// Loads the spec at `path` (merged with `options.merge`) and reports its generation
+/// hazards (see [`lint::lint_spec`]).
+pub fn lint_spec_at(
+    path: &str,
+    options: &GenerateOptions,
+) -> anyhow::Result<Vec<lint::LintWarning>> {
+    let spec = load_spec(path, options).context(ErrorKind::Load)?;
+    Ok(lint::lint_spec(&spec.document, &options.func_naming()))
+}
 
-    Ok(())
+/// Renders the header [`generate_safe`] would write, without touching the filesystem.
+///
+/// `file_name` only provides the `file_name` template variable (its stem).
+pub fn generate_to_string(
+    path: &str,
+    file_name: &str,
+    module_name: &str,
+    include_headers: Vec<String>,
+    options: &GenerateOptions,
+) -> anyhow::Result<String> {
+    let engine = GeneratorEngine::new(options)?;
+    let spec = load_spec(path, options).context(ErrorKind::Load)?;
+    let target = RenderTarget {
+        file_name,
+        module_name,
+        include_headers: &include_headers,
+        output_dir: Path::new(""),
+    };
+    let mut rendered = engine.render(&spec, &target)?;
+    Ok(rendered.files.swap_remove(0).1)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use output::WriteOutcome;
+    use parser::{
+        parse_context_entry, parse_format_type, parse_include_headers_with, parse_spec_header,
+        IncludeOptions,
+    };
+    use serde_json::{json, Value};
+
+    pub(crate) const SHARED_REF_SPEC: &str = r##"{
+  "openapi": "3.1.0",
+  "info": { "title": "Shared Ref API", "version": "1.0.0" },
+  "paths": {
+    "/users/{id}": {
+      "get": {
+        "tags": ["Users"],
+        "parameters": [
+          { "name": "id", "in": "path", "required": true, "schema": { "type": "integer" } }
+        ],
+        "responses": {
+          "200": {
+            "description": "OK",
+            "content": {
+              "application/json": { "schema": { "$ref": "#/components/schemas/User" } }
+            }
+          }
+        }
+      },
+      "put": {
+        "tags": ["Users"],
+        "requestBody": {
+          "content": {
+            "application/json": { "schema": { "$ref": "#/components/schemas/User" } }
+          }
+        },
+        "responses": { "204": { "description": "Updated" } }
+      }
+    }
+  },
+  "components": {
+    "schemas": {
+      "User": {
+        "type": "object",
+        "required": ["name"],
+        "properties": { "name": { "type": "string" }, "age": { "type": "integer" } }
+      }
+    }
+  }
+}"##;
+
+    /// Writes `spec` to a fresh temp directory and returns `(spec_path, output_dir)`.
+    pub(crate) fn write_temp_spec(dir_name: &str, spec: &str) -> (String, std::path::PathBuf) {
+        let dir = std::env::temp_dir().join(dir_name);
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir).unwrap();
+
+        let spec_path = dir.join("spec.json");
+        fs::write(&spec_path, spec).unwrap();
+
+        (spec_path.to_str().unwrap().to_string(), dir.join("out"))
+    }
+
+    #[test]
+    fn test_generate_safe_deref_keeps_output() {
+        let (spec_path, out_dir) = write_temp_spec("banette_generate_deref", SHARED_REF_SPEC);
+
+        let render = |deref: bool| {
+            let out = out_dir.join(if deref { "deref" } else { "plain" });
+            generate_safe(
+                &spec_path,
+                out.to_str().unwrap(),
+                "SharedApi.h",
+                "TEST_API",
+                Vec::new(),
+                &GenerateOptions {
+                    deref,
+                    ..GenerateOptions::default()
+                },
+            )
+            .unwrap();
+            fs::read_to_string(out.join("SharedApi.h")).unwrap()
+        };
+
+        let plain = render(false);
+        assert!(plain.contains("FUser& ResponseBody"));
+        assert!(plain.contains("const FUser& RequestBody"));
+        // Inlined schemas still name the component they came from
+        assert_eq!(render(true), plain);
+
+        fs::remove_dir_all(out_dir.parent().unwrap()).ok();
+    }
+
+    #[test]
+    fn test_parse_include_headers_newline_separated() {
+        let expected = vec![
+            "#include \"a.h\"".to_string(),
+            "#include \"b.h\"".to_string(),
+            "#include <c>".to_string(),
+        ];
+
+        // Newline-only
+        assert_eq!(parse_include_headers("a.h\nb.h\n<c>\n"), expected);
+
+        // Mixed newlines and semicolons
+        assert_eq!(parse_include_headers("a.h;b.h\n\n<c>;"), expected);
+
+        // CRLF line endings
+        assert_eq!(parse_include_headers("  a.h\r\nb.h\r\n<c>\r\n"), expected);
+
+        // Full format across lines
+        assert_eq!(
+            parse_include_headers("#include \"a.h\"\r\n#include \"b.h\";\n#include <c>\n"),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_parse_include_headers_simplified_brackets() {
+        assert_eq!(
+            parse_include_headers("<vector>;MyHeader.h"),
+            vec![
+                "#include <vector>".to_string(),
+                "#include \"MyHeader.h\"".to_string()
+            ]
+        );
+
+        // Already-quoted entries are not quoted twice
+        assert_eq!(
+            parse_include_headers(" \"Local.h\" ; <string> "),
+            vec![
+                "#include \"Local.h\"".to_string(),
+                "#include <string>".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_include_headers_dedupe() {
+        let options = IncludeOptions {
+            dedupe: true,
+            ..IncludeOptions::default()
+        };
+
+        assert_eq!(
+            parse_include_headers_with("b.h;a.h;b.h;a.h", options),
+            vec!["#include \"b.h\"".to_string(), "#include \"a.h\"".to_string()]
+        );
+
+        // Raw behavior stays the default
+        assert_eq!(parse_include_headers("a.h;a.h").len(), 2);
+    }
+
+    #[test]
+    fn test_parse_include_headers_sort() {
+        let options = IncludeOptions {
+            sort: true,
+            ..IncludeOptions::default()
+        };
+
+        assert_eq!(
+            parse_include_headers_with(
+                "#include \"Zeta.h\";#include <vector>;#include \"Alpha.h\";#include <array>",
+                options
+            ),
+            vec![
+                "#include <array>".to_string(),
+                "#include <vector>".to_string(),
+                "#include \"Alpha.h\"".to_string(),
+                "#include \"Zeta.h\"".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_include_headers_with_empty_input() {
+        let options = IncludeOptions {
+            dedupe: true,
+            sort: true,
+        };
+        assert_eq!(parse_include_headers_with("", options), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_generate_ffi_status_codes() {
+        use std::ffi::CString;
+        use std::ptr;
+
+        let (spec_path, out_dir) = write_temp_spec("banette_generate_ffi", SHARED_REF_SPEC);
+        let out_dir = CString::new(out_dir.to_str().unwrap()).unwrap();
+        let file_name = CString::new("SharedRef.h").unwrap();
+        let module_name = CString::new("TESTMODULE_API").unwrap();
+
+        let status = generate(
+            ptr::null(),
+            out_dir.as_ptr(),
+            file_name.as_ptr(),
+            module_name.as_ptr(),
+            ptr::null(),
+        );
+        assert_eq!(status, ErrorKind::NullArgument.code());
+
+        let missing = CString::new("/nonexistent/banette/spec.json").unwrap();
+        let status = generate(
+            missing.as_ptr(),
+            out_dir.as_ptr(),
+            file_name.as_ptr(),
+            module_name.as_ptr(),
+            ptr::null(),
+        );
+        assert_eq!(status, ErrorKind::Load.code());
+
+        let spec_path = CString::new(spec_path).unwrap();
+        let status = generate(
+            spec_path.as_ptr(),
+            out_dir.as_ptr(),
+            file_name.as_ptr(),
+            module_name.as_ptr(),
+            ptr::null(),
+        );
+        assert_eq!(status, STATUS_OK);
+    }
+
+    #[test]
+    fn test_banette_last_error() {
+        use std::ffi::CString;
+        use std::ptr;
+
+        let (spec_path, out_dir) = write_temp_spec("banette_last_error", SHARED_REF_SPEC);
+        let out_dir = CString::new(out_dir.to_str().unwrap()).unwrap();
+        let file_name = CString::new("SharedRef.h").unwrap();
+        let module_name = CString::new("TESTMODULE_API").unwrap();
+        let missing = CString::new("/nonexistent/banette/spec.json").unwrap();
+
+        let status = generate(
+            missing.as_ptr(),
+            out_dir.as_ptr(),
+            file_name.as_ptr(),
+            module_name.as_ptr(),
+            ptr::null(),
+        );
+        assert_eq!(status, ErrorKind::Load.code());
+
+        let required = unsafe { banette_last_error(ptr::null_mut(), 0) };
+        let mut buf = vec![0 as c_char; required];
+        assert_eq!(unsafe { banette_last_error(buf.as_mut_ptr(), buf.len()) }, required);
+        let message = unsafe { CStr::from_ptr(buf.as_ptr()) }.to_str().unwrap();
+        assert!(message.starts_with("Failed to load OpenAPI spec: "));
+        assert!(message.contains("/nonexistent/banette/spec.json"));
+        assert_eq!(message.len() + 1, required);
+
+        // A short buffer receives a truncated, terminated prefix
+        let mut short = [0x7f as c_char; 7];
+        assert_eq!(unsafe { banette_last_error(short.as_mut_ptr(), short.len()) }, required);
+        let prefix = unsafe { CStr::from_ptr(short.as_ptr()) }.to_str().unwrap();
+        assert_eq!(prefix, "Failed");
+
+        let spec_path = CString::new(spec_path).unwrap();
+        let status = generate(
+            spec_path.as_ptr(),
+            out_dir.as_ptr(),
+            file_name.as_ptr(),
+            module_name.as_ptr(),
+            ptr::null(),
+        );
+        assert_eq!(status, STATUS_OK);
+        assert_eq!(unsafe { banette_last_error(ptr::null_mut(), 0) }, 0);
+    }
+
+    #[test]
+    fn test_generate_ffi_renders_extra_headers() {
+        use std::ffi::CString;
+
+        let (spec_path, out_dir) = write_temp_spec("banette_generate_ffi_headers", SHARED_REF_SPEC);
+        let template_path = out_dir.with_file_name("headers.h.tera");
+        fs::write(&template_path, "{{ extra_headers | join(sep=\"|\") }}").unwrap();
+
+        let options = GenerateOptions {
+            template: Some(template_path),
+            ..Default::default()
+        };
+        let headers = parse_include_headers("Custom.h;<vector>");
+        let rendered =
+            generate_to_string(&spec_path, "SharedRef.h", "TESTMODULE_API", headers, &options)
+                .unwrap();
+        assert_eq!(rendered, "#include \"Custom.h\"|#include <vector>");
+
+        // The delimited string given to the FFI reaches the built-in template
+        let spec_path = CString::new(spec_path).unwrap();
+        let out_dir_c = CString::new(out_dir.to_str().unwrap()).unwrap();
+        let file_name = CString::new("SharedRef.h").unwrap();
+        let module_name = CString::new("TESTMODULE_API").unwrap();
+        let extra_headers = CString::new("Custom.h;<vector>").unwrap();
+        let status = generate(
+            spec_path.as_ptr(),
+            out_dir_c.as_ptr(),
+            file_name.as_ptr(),
+            module_name.as_ptr(),
+            extra_headers.as_ptr(),
+        );
+        assert_eq!(status, STATUS_OK);
+        let written = fs::read_to_string(out_dir.join("SharedRef.h")).unwrap();
+        assert!(written.contains("#include \"Custom.h\"\n#include <vector>"));
+    }
+
+    #[test]
+    fn test_generate_extra_headers_in_order() {
+        use parser::{parse_include_headers_with, IncludeOptions};
+
+        let (spec_path, out_dir) = write_temp_spec("banette_generate_header_order", SHARED_REF_SPEC);
+        let template_path = out_dir.with_file_name("headers.h.tera");
+        fs::write(&template_path, "{% for header in extra_headers %}{{ header }}\n{% endfor %}")
+            .unwrap();
+        let headers = parse_include_headers_with(
+            "B.h;<vector>;B.h;A.h;<vector>",
+            IncludeOptions { dedupe: true, sort: false },
+        );
+
+        let options = GenerateOptions {
+            template: Some(template_path),
+            ..Default::default()
+        };
+        let rendered = generate_to_string(
+            &spec_path,
+            "SharedRef.h",
+            "TESTMODULE_API",
+            headers.clone(),
+            &options,
+        )
+        .unwrap();
+        assert_eq!(rendered, "#include \"B.h\"\n#include <vector>\n#include \"A.h\"\n");
+
+        // The built-in header puts them after its own includes, before the generated one
+        let header = generate_to_string(
+            &spec_path,
+            "SharedRef.h",
+            "TESTMODULE_API",
+            headers,
+            &GenerateOptions::default(),
+        )
+        .unwrap();
+        assert!(header.contains(
+            "#include \"BanetteTransport/Http/JsonLayer.h\"\n#include \"B.h\"\n#include <vector>\n#include \"A.h\"\n#include \"SharedRef.generated.h\""
+        ));
+        assert_eq!(header.matches("#include \"B.h\"").count(), 1);
+    }
+
+    #[test]
+    fn test_generate_with_header_list() {
+        use std::ffi::CString;
+        use std::ptr;
+
+        let (spec_path, out_dir) = write_temp_spec("banette_generate_header_list", SHARED_REF_SPEC);
+        let spec_path = CString::new(spec_path).unwrap();
+        let out_dir_c = CString::new(out_dir.to_str().unwrap()).unwrap();
+        let file_name = CString::new("SharedRef.h").unwrap();
+        let module_name = CString::new("TESTMODULE_API").unwrap();
+        let call = |headers: *const *const c_char, count: usize| {
+            let status = unsafe {
+                generate_with_headers(
+                    spec_path.as_ptr(),
+                    out_dir_c.as_ptr(),
+                    file_name.as_ptr(),
+                    module_name.as_ptr(),
+                    headers,
+                    count,
+                )
+            };
+            (status, fs::read_to_string(out_dir.join("SharedRef.h")).unwrap_or_default())
+        };
+
+        let custom = CString::new("Custom.h").unwrap();
+        let vector = CString::new("#include <vector>").unwrap();
+        let headers = [custom.as_ptr(), vector.as_ptr()];
+        let (status, written) = call(headers.as_ptr(), headers.len());
+        assert_eq!(status, STATUS_OK);
+        assert!(written.contains("#include \"Custom.h\"\n#include <vector>"));
+
+        // Null or empty means no extra headers
+        for (headers, count) in [(ptr::null(), 2), (headers.as_ptr(), 0)] {
+            let (status, written) = call(headers, count);
+            assert_eq!(status, STATUS_OK);
+            assert!(!written.contains("Custom.h"));
+        }
+
+        let with_null = [custom.as_ptr(), ptr::null()];
+        let (status, _) = call(with_null.as_ptr(), with_null.len());
+        assert_eq!(status, ErrorKind::NullArgument.code());
+        let required = unsafe { banette_last_error(ptr::null_mut(), 0) };
+        let mut buf = vec![0 as c_char; required];
+        unsafe { banette_last_error(buf.as_mut_ptr(), buf.len()) };
+        let message = unsafe { CStr::from_ptr(buf.as_ptr()) }.to_str().unwrap();
+        assert!(message.contains("Argument headers[1] cannot be null"));
+    }
+
+    #[test]
+    fn test_generate_routes_log_through_callback() {
+        use crate::logging::{banette_set_log_callback, LogLevel};
+        use std::ffi::CString;
+        use std::ptr;
+        use std::sync::Mutex;
+
+        static MESSAGES: Mutex<Vec<(i32, String)>> = Mutex::new(Vec::new());
+
+        extern "C" fn record(level: i32, msg: *const c_char) {
+            let msg = unsafe { CStr::from_ptr(msg) }.to_string_lossy().into_owned();
+            MESSAGES.lock().unwrap().push((level, msg));
+        }
+
+        let (spec_path, out_dir) = write_temp_spec("banette_log_callback", SHARED_REF_SPEC);
+        let spec_path = CString::new(spec_path).unwrap();
+        let out_dir = CString::new(out_dir.to_str().unwrap()).unwrap();
+        let file_name = CString::new("SharedRef.h").unwrap();
+        let module_name = CString::new("TESTMODULE_API").unwrap();
+
+        banette_set_log_callback(Some(record));
+        let status = generate(
+            spec_path.as_ptr(),
+            out_dir.as_ptr(),
+            file_name.as_ptr(),
+            module_name.as_ptr(),
+            ptr::null(),
+        );
+        banette_set_log_callback(None);
+
+        assert_eq!(status, STATUS_OK);
+        assert!(MESSAGES.lock().unwrap().contains(&(
+            LogLevel::Info as i32,
+            "Code generation completed successfully.".to_string()
+        )));
+    }
+
+    #[test]
+    fn test_generate_to_string_matches_written_file() {
+        use std::ffi::CString;
+        use std::ptr;
+
+        let (spec_path, out_dir) = write_temp_spec("banette_generate_to_string", SHARED_REF_SPEC);
+        let headers = vec!["#include \"Custom.h\"".to_string()];
+        let options = GenerateOptions::default();
+
+        generate_safe(
+            &spec_path,
+            out_dir.to_str().unwrap(),
+            "SharedRef.h",
+            "TESTMODULE_API",
+            headers.clone(),
+            &options,
+        )
+        .unwrap();
+        let written = fs::read_to_string(out_dir.join("SharedRef.h")).unwrap();
+
+        let rendered =
+            generate_to_string(&spec_path, "SharedRef.h", "TESTMODULE_API", headers, &options)
+                .unwrap();
+        assert_eq!(rendered, written);
+
+        // The FFI wrapper reports the size first, then fills the buffer
+        let spec_path = CString::new(spec_path).unwrap();
+        let file_name = CString::new("SharedRef.h").unwrap();
+        let module_name = CString::new("TESTMODULE_API").unwrap();
+        let extra_headers = CString::new("Custom.h").unwrap();
+        let call = |buf: *mut c_char, len: usize| unsafe {
+            generate_string(
+                spec_path.as_ptr(),
+                file_name.as_ptr(),
+                module_name.as_ptr(),
+                extra_headers.as_ptr(),
+                buf,
+                len,
+            )
+        };
+
+        let required = call(ptr::null_mut(), 0);
+        assert_eq!(required, written.len() + 1);
+        let mut buf = vec![0 as c_char; required];
+        assert_eq!(call(buf.as_mut_ptr(), buf.len()), required);
+        assert_eq!(unsafe { CStr::from_ptr(buf.as_ptr()) }.to_str().unwrap(), written);
+    }
+
+    #[test]
+    fn test_generate_safe_skips_unchanged_output() {
+        let (spec_path, out_dir) = write_temp_spec("banette_generate_unchanged", SHARED_REF_SPEC);
+        let generate = |module_name: &str| {
+            generate_safe(
+                &spec_path,
+                out_dir.to_str().unwrap(),
+                "SharedRef.h",
+                module_name,
+                vec![],
+                &GenerateOptions::default(),
+            )
+            .unwrap()
+            .files[0]
+                .outcome
+        };
+
+        assert_eq!(generate("TESTMODULE_API"), WriteOutcome::Created);
+        assert_eq!(generate("TESTMODULE_API"), WriteOutcome::Unchanged);
+        assert_eq!(generate("OTHERMODULE_API"), WriteOutcome::Updated);
+
+        let written = fs::read_to_string(out_dir.join("SharedRef.h")).unwrap();
+        assert!(written.contains("OTHERMODULE_API"));
+    }
+
+    #[test]
+    fn test_generate_safe_emits_source_file() {
+        let (spec_path, out_dir) = write_temp_spec("banette_generate_source", SHARED_REF_SPEC);
+        let options = GenerateOptions {
+            source: true,
+            ..Default::default()
+        };
+
+        let files = generate_safe(
+            &spec_path,
+            out_dir.to_str().unwrap(),
+            "SharedRef.h",
+            "TESTMODULE_API",
+            vec![],
+            &options,
+        )
+        .unwrap()
+        .files;
+        let paths: Vec<_> = files.iter().map(|f| f.path.clone()).collect();
+        assert_eq!(paths, vec![out_dir.join("SharedRef.h"), out_dir.join("SharedRef.cpp")]);
+
+        let header = fs::read_to_string(out_dir.join("SharedRef.h")).unwrap();
+        let source = fs::read_to_string(out_dir.join("SharedRef.cpp")).unwrap();
+
+        assert!(source.contains("#include \"SharedRef.h\""));
+        assert!(!header.contains("co_return"));
+        assert!(header.contains("bool& bSuccess, FLatentActionInfo LatentInfo);"));
+        assert!(source.contains("FVoidCoroutine USharedRefLibrary::"));
+        assert_eq!(source.matches("co_return;").count(), 2);
+    }
+
+    #[test]
+    fn test_generate_safe_writes_manifest() {
+        let (spec_path, out_dir) = write_temp_spec("banette_generate_manifest", SHARED_REF_SPEC);
+        let options = GenerateOptions {
+            manifest: true,
+            ..Default::default()
+        };
+        let generate = |spec_path: &str, out: &str| {
+            let out = out_dir.join(out);
+            let out_str = out.to_str().unwrap();
+            let files = generate_safe(spec_path, out_str, "SharedRef.h", "M", vec![], &options)
+                .unwrap()
+                .files;
+            let manifest_path = out.join("SharedRef.manifest.json");
+            assert_eq!(files.last().unwrap().path, manifest_path);
+            let text = fs::read_to_string(&manifest_path).unwrap();
+            (files.last().unwrap().outcome, serde_json::from_str::<Value>(&text).unwrap())
+        };
+
+        let (outcome, first) = generate(&spec_path, "a");
+        assert_eq!(outcome, WriteOutcome::Created);
+        assert_eq!(first["source"], spec_path.as_str());
+        assert_eq!(first["generator_version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(first["files"], json!(["SharedRef.h"]));
+        assert!(first["spec_hash"].as_str().unwrap().starts_with("sha256:"));
+
+        // Identical inputs hash the same, and a rerun leaves the manifest alone
+        let (outcome, again) = generate(&spec_path, "a");
+        assert_eq!(outcome, WriteOutcome::Unchanged);
+        assert_eq!(again, first);
+        let (_, elsewhere) = generate(&spec_path, "b");
+        assert_eq!(elsewhere["spec_hash"], first["spec_hash"]);
+
+        let (changed_path, _) = write_temp_spec(
+            "banette_generate_manifest_changed",
+            &SHARED_REF_SPEC.replace("Shared Ref API", "Shared Ref API v2"),
+        );
+        let (outcome, changed) = generate(&changed_path, "a");
+        assert_eq!(outcome, WriteOutcome::Updated);
+        assert_ne!(changed["spec_hash"], first["spec_hash"]);
+    }
+
+    #[test]
+    fn test_generate_safe_dry_run() {
+        let (spec_path, out_dir) = write_temp_spec("banette_generate_dry_run", SHARED_REF_SPEC);
+        let generate = |module_name: &str, dry_run: bool| {
+            let options = GenerateOptions {
+                dry_run,
+                ..Default::default()
+            };
+            generate_safe(
+                &spec_path,
+                out_dir.to_str().unwrap(),
+                "SharedRef.h",
+                module_name,
+                vec![],
+                &options,
+            )
+            .unwrap()
+            .files[0]
+                .outcome
+        };
+
+        // Missing target
+        assert_eq!(generate("TESTMODULE_API", true), WriteOutcome::Created);
+        assert!(!out_dir.exists());
+
+        generate("TESTMODULE_API", false);
+        let written = fs::read_to_string(out_dir.join("SharedRef.h")).unwrap();
+
+        assert_eq!(generate("TESTMODULE_API", true), WriteOutcome::Unchanged);
+        assert_eq!(generate("OTHERMODULE_API", true), WriteOutcome::Updated);
+        assert_eq!(fs::read_to_string(out_dir.join("SharedRef.h")).unwrap(), written);
+    }
+
+    #[test]
+    fn test_generate_safe_custom_template() {
+        let (spec_path, out_dir) = write_temp_spec("banette_generate_template", SHARED_REF_SPEC);
+        let template_path = out_dir.with_file_name("custom.h.tera");
+        fs::write(
+            &template_path,
+            "// {{ file_name }} for {{ module_name }}: {{ info.title }}\n\
+             {% for path, item in paths %}{{ path | f_path_to_func_name(method=\"get\") }}{% endfor %}",
+        )
+        .unwrap();
+
+        let options = GenerateOptions {
+            template: Some(template_path),
+            ..Default::default()
+        };
+        let rendered =
+            generate_to_string(&spec_path, "SharedRef.h", "TESTMODULE_API", vec![], &options)
+                .unwrap();
+
+        assert_eq!(
+            rendered,
+            "// SharedRef for TESTMODULE_API: Shared Ref API\nGET_Users_By_Id"
+        );
+    }
+
+    #[test]
+    fn test_generate_safe_custom_source_template() {
+        let (spec_path, out_dir) =
+            write_temp_spec("banette_generate_source_template", SHARED_REF_SPEC);
+        let template_path = out_dir.with_file_name("custom.cpp.tera");
+        fs::write(&template_path, "#include \"{{ file_name }}.h\" // {{ info.title }}").unwrap();
+
+        let options = GenerateOptions {
+            source: true,
+            source_template: Some(template_path),
+            ..Default::default()
+        };
+        generate_safe(
+            &spec_path,
+            out_dir.to_str().unwrap(),
+            "SharedRef.h",
+            "TESTMODULE_API",
+            vec![],
+            &options,
+        )
+        .unwrap();
+
+        let source = fs::read_to_string(out_dir.join("SharedRef.cpp")).unwrap();
+        assert_eq!(source, "#include \"SharedRef.h\" // Shared Ref API");
+        let header = fs::read_to_string(out_dir.join("SharedRef.h")).unwrap();
+        assert!(header.contains("class TESTMODULE_API USharedRefLibrary"));
+    }
+
+    #[test]
+    fn test_generate_api_info_in_context() {
+        let spec = SHARED_REF_SPEC.replace(
+            r#""info": { "title": "Shared Ref API", "version": "1.0.0" }"#,
+            r#""info": { "title": "Players API", "version": "2.4.1-rc.1" }"#,
+        );
+        let (spec_path, out_dir) = write_temp_spec("banette_generate_api_info", &spec);
+        let template_path = out_dir.with_file_name("banner.h.tera");
+        fs::write(&template_path, "// {{ api_title }} v{{ api_version }}").unwrap();
+
+        let options = GenerateOptions {
+            template: Some(template_path),
+            ..Default::default()
+        };
+        let rendered =
+            generate_to_string(&spec_path, "SharedRef.h", "TESTMODULE_API", vec![], &options)
+                .unwrap();
+
+        assert_eq!(rendered, "// Players API v2.4.1-rc.1");
+    }
+
+    #[test]
+    fn test_generate_vendor_extensions_in_context() {
+        let spec = SHARED_REF_SPEC
+            .replace(
+                r#""tags": ["Users"],"#,
+                r#""tags": ["Users"], "x-ue-category": "Players","#,
+            )
+            .replace(
+                r#""age": { "type": "integer" }"#,
+                r#""age": { "type": "integer", "x-ue-editable": false }"#,
+            );
+        let (spec_path, out_dir) = write_temp_spec("banette_generate_extensions", &spec);
+        let template_path = out_dir.with_file_name("extensions.h.tera");
+        fs::write(
+            &template_path,
+            "{% set op = paths[\"/users/{id}\"].get %}\
+             {% set user = components.schemas.User.properties %}\
+             {{ op | f_x_extension(key=\"x-ue-category\") }} \
+             {{ user.age | f_x_extension(key=\"ue-editable\") }} \
+             [{{ user.name | f_x_extension(key=\"x-ue-editable\") }}]",
+        )
+        .unwrap();
+
+        let options = GenerateOptions {
+            template: Some(template_path),
+            ..Default::default()
+        };
+        let rendered =
+            generate_to_string(&spec_path, "SharedRef.h", "TESTMODULE_API", vec![], &options)
+                .unwrap();
+
+        assert_eq!(rendered, "Players false []");
+    }
+
+    #[test]
+    fn test_generate_renders_from_spec_document() {
+        let spec = SHARED_REF_SPEC
+            .replace(
+                r#""version": "1.0.0" }"#,
+                r#""version": "1.0.0", "x-custom": "from info" }"#,
+            )
+            .replace(
+                r#""tags": ["Users"],"#,
+                r#""tags": ["Users"], "x-custom": { "retry": 3 },"#,
+            );
+        let (spec_path, out_dir) = write_temp_spec("banette_generate_document", &spec);
+        let template_path = out_dir.with_file_name("document.h.tera");
+        fs::write(
+            &template_path,
+            "{{ info[\"x-custom\"] }}: {{ paths[\"/users/{id}\"].get[\"x-custom\"].retry }}",
+        )
+        .unwrap();
+
+        let options = GenerateOptions {
+            template: Some(template_path),
+            ..Default::default()
+        };
+        let rendered =
+            generate_to_string(&spec_path, "SharedRef.h", "TESTMODULE_API", vec![], &options)
+                .unwrap();
+
+        assert_eq!(rendered, "from info: 3");
+    }
+
+    #[test]
+    fn test_generate_safe_reports_template_position() {
+        let (spec_path, out_dir) =
+            write_temp_spec("banette_generate_bad_template", SHARED_REF_SPEC);
+        let template_path = out_dir.with_file_name("broken.h.tera");
+        let generate = |template: &str| {
+            fs::write(&template_path, template).unwrap();
+            let options = GenerateOptions {
+                template: Some(template_path.clone()),
+                ..Default::default()
+            };
+            let error = generate_safe(
+                &spec_path,
+                out_dir.to_str().unwrap(),
+                "SharedRef.h",
+                "TESTMODULE_API",
+                vec![],
+                &options,
+            )
+            .unwrap_err();
+            assert_eq!(ErrorKind::of(&error), Some(ErrorKind::Render));
+            format!("{:#}", error)
+        };
+
+        // A syntax error is reported where Tera found it
+        let message = generate("// {{ file_name }}\n{% for path, item in paths %}\n{{ path | }}\n");
+        assert!(
+            message.contains(&format!("Template '{}' at line 3, column ", template_path.display())),
+            "{}",
+            message
+        );
+
+        // A missing variable is named and located by its first use
+        let message = generate("// {{ file_name }}\n\n// {{ info.titel }}\n");
+        assert!(
+            message.contains(
+                "Template 'broken.h.tera' near line 3, column 7 (context key `info.titel`): "
+            ),
+            "{}",
+            message
+        );
+    }
+
+    #[test]
+    fn test_generate_safe_extra_context() {
+        let (spec_path, out_dir) = write_temp_spec("banette_generate_set", SHARED_REF_SPEC);
+        let template_path = out_dir.with_file_name("banner.h.tera");
+        fs::write(&template_path, "// (c) {{ year }} {{ module_name }}").unwrap();
+
+        let render = |entries: &[&str]| {
+            let options = GenerateOptions {
+                template: Some(template_path.clone()),
+                extra_context: entries.iter().map(|e| parse_context_entry(e).unwrap()).collect(),
+                ..Default::default()
+            };
+            generate_to_string(&spec_path, "SharedRef.h", "TESTMODULE_API", vec![], &options)
+        };
+
+        assert_eq!(render(&["year=2026"]).unwrap(), "// (c) 2026 TESTMODULE_API");
+
+        let error = render(&["module_name=OTHER_API"]).unwrap_err();
+        assert_eq!(ErrorKind::of(&error), Some(ErrorKind::InvalidArgument));
+        assert!(format!("{:#}", error).contains("Context key 'module_name' is reserved"));
+
+        // Spec fields are reserved too
+        assert!(render(&["info=x"]).is_err());
+        let error = render(&["flag_enums=true"]).unwrap_err();
+        assert!(format!("{:#}", error).contains("Context key 'flag_enums' is reserved"));
+    }
+
+    #[test]
+    fn test_parse_context_entry() {
+        assert_eq!(
+            parse_context_entry("banner=a=b"),
+            Ok(("banner".to_string(), "a=b".to_string()))
+        );
+        assert_eq!(parse_context_entry("empty="), Ok(("empty".to_string(), String::new())));
+        assert!(parse_context_entry("missing").is_err());
+        assert!(parse_context_entry("=value").is_err());
+    }
+
+    #[test]
+    fn test_parse_format_type() {
+        assert_eq!(
+            parse_format_type(" decimal = FDecimal "),
+            Ok(("decimal".to_string(), "FDecimal".to_string()))
+        );
+        assert_eq!(
+            parse_format_type("decimal"),
+            Err("Invalid format type 'decimal': expected 'format=Type'".to_string())
+        );
+        assert!(parse_format_type("=FDecimal").is_err());
+        assert!(parse_format_type("decimal=").is_err());
+    }
+
+    #[test]
+    fn test_generate_safe_line_ending_and_bom() {
+        let (spec_path, out_dir) = write_temp_spec("banette_generate_crlf", SHARED_REF_SPEC);
+        let header_path = out_dir.join("SharedRef.h");
+        let generate = |options: &GenerateOptions| {
+            generate_safe(
+                &spec_path,
+                out_dir.to_str().unwrap(),
+                "SharedRef.h",
+                "TESTMODULE_API",
+                vec![],
+                options,
+            )
+            .unwrap()
+        };
+
+        // The defaults keep LF endings without a BOM
+        generate(&GenerateOptions::default());
+        let lf = fs::read(&header_path).unwrap();
+        assert!(!lf.contains(&b'\r'));
+        assert_ne!(&lf[..3], &[0xEF, 0xBB, 0xBF]);
+
+        let options = GenerateOptions {
+            line_ending: LineEnding::Crlf,
+            bom: true,
+            ..Default::default()
+        };
+        generate(&options);
+        let crlf = fs::read_to_string(&header_path).unwrap();
+        let body = crlf.strip_prefix('\u{FEFF}').unwrap();
+        assert_eq!(body.replace("\r\n", "\n").as_bytes(), lf.as_slice());
+        assert_eq!(body.matches("\r\n").count(), body.matches('\n').count());
+
+        // Regenerating compares against the converted output
+        assert_eq!(generate(&options).files[0].outcome, WriteOutcome::Unchanged);
+    }
+
+    #[test]
+    fn test_generate_safe_validates_output() {
+        let (spec_path, out_dir) = write_temp_spec("banette_generate_validate", SHARED_REF_SPEC);
+        let generate = |template: Option<PathBuf>| {
+            let options = GenerateOptions {
+                source: true,
+                blueprint_async: true,
+                settings_class: true,
+                validate: true,
+                template,
+                ..Default::default()
+            };
+            generate_safe(
+                &spec_path,
+                out_dir.to_str().unwrap(),
+                "SharedRef.h",
+                "TESTMODULE_API",
+                vec!["#include <vector>".to_string()],
+                &options,
+            )
+        };
+
+        // The built-in templates pass
+        assert_eq!(generate(None).unwrap().files.len(), 2);
+
+        let template_path = out_dir.with_file_name("broken.h.tera");
+        fs::write(
+            &template_path,
+            "#pragma once\n\nstruct F{{ missing | default(value=\"\") }}\n{\n    TArray<> Items;\n",
+        )
+        .unwrap();
+        fs::remove_dir_all(&out_dir).ok();
+
+        let error = generate(Some(template_path)).unwrap_err();
+        assert_eq!(ErrorKind::of(&error), Some(ErrorKind::Render));
+        assert_eq!(
+            format!("{:#}", error),
+            "Failed to render template: Rendered output looks malformed:\n\
+             SharedRef.h: line 3: empty type name 'F'\n\
+             SharedRef.h: line 5: empty template argument list in 'TArray<>'\n\
+             SharedRef.h: line 4: unclosed '{'"
+        );
+        assert!(!out_dir.join("SharedRef.h").exists());
+    }
+
+    #[test]
+    fn test_generate_safe_streamed_matches_buffered() {
+        let paths: Vec<String> = (0..200)
+            .map(|i| {
+                format!(
+                    r##""/items{i}/{{id}}": {{
+                      "get": {{
+                        "tags": ["Items"],
+                        "parameters": [
+                          {{
+                            "name": "id",
+                            "in": "path",
+                            "required": true,
+                            "schema": {{ "type": "integer" }}
+                          }}
+                        ],
+                        "responses": {{ "200": {{ "description": "OK", "content": {{
+                          "application/json": {{
+                            "schema": {{ "$ref": "#/components/schemas/User" }}
+                          }}
+                        }} }} }}
+                      }}
+                    }}"##
+                )
+            })
+            .collect();
+        let spec = SHARED_REF_SPEC.replace(
+            r#""paths": {"#,
+            &format!(r#""paths": {{ {},"#, paths.join(",")),
+        );
+        let (spec_path, out_dir) = write_temp_spec("banette_generate_streamed", &spec);
+        let generate = |stream, dir: &str| {
+            let options = GenerateOptions {
+                source: true,
+                stream,
+                ..Default::default()
+            };
+            let out_dir = out_dir.join(dir);
+            let files = generate_safe(
+                &spec_path,
+                out_dir.to_str().unwrap(),
+                "SharedRef.h",
+                "TESTMODULE_API",
+                vec![],
+                &options,
+            )
+            .unwrap()
+            .files;
+            assert_eq!(files.len(), 2);
+            assert!(files.iter().all(|file| file.outcome == WriteOutcome::Created));
+            ["SharedRef.h", "SharedRef.cpp"].map(|name| fs::read(out_dir.join(name)).unwrap())
+        };
+
+        let buffered = generate(false, "buffered");
+        let streamed = generate(true, "streamed");
+        assert!(buffered[0].len() > 50_000);
+        assert_eq!(streamed, buffered);
+        assert_eq!(fs::read_dir(out_dir.join("streamed")).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn test_generate_safe_skips_broken_operations() {
+        // `options` has no EHttpMethod, so that operation cannot be rendered
+        let spec = SHARED_REF_SPEC.replace(
+            r#""put": {"#,
+            r#""options": { "tags": ["Users"], "responses": { "204": { "description": "OK" } } },
+      "put": {"#,
+        );
+        let (spec_path, out_dir) = write_temp_spec("banette_generate_skip", &spec);
+        let generate = |strict| {
+            let options = GenerateOptions {
+                source: true,
+                strict,
+                ..Default::default()
+            };
+            generate_safe(
+                &spec_path,
+                out_dir.to_str().unwrap(),
+                "SharedRef.h",
+                "TESTMODULE_API",
+                vec![],
+                &options,
+            )
+        };
+
+        let generation = generate(false).unwrap();
+        assert_eq!(generation.files.len(), 2);
+        assert_eq!(generation.skipped.len(), 1);
+        let skipped = &generation.skipped[0];
+        assert_eq!(skipped.path, "/users/{id}");
+        assert_eq!(skipped.method, "options");
+        assert!(skipped.error.contains("Unsupported HTTP method: 'options'"));
+        assert!(!skipped.error.contains('\n'));
+
+        let header = fs::read_to_string(out_dir.join("SharedRef.h")).unwrap();
+        assert!(header.contains("static FVoidCoroutine GET_Users_By_Id("));
+        assert!(header.contains("static FVoidCoroutine PUT_Users_By_Id("));
+        assert!(!header.contains("OPTIONS_Users_By_Id("));
+        assert!(header.contains("    // Skipped OPTIONS /users/{id}: "));
+        let source = fs::read_to_string(out_dir.join("SharedRef.cpp")).unwrap();
+        assert!(source.contains("USharedRefLibrary::GET_Users_By_Id("));
+        assert!(source.contains("// Skipped OPTIONS /users/{id}: "));
+
+        let error = generate(true).unwrap_err();
+        assert_eq!(ErrorKind::of(&error), Some(ErrorKind::Render));
+        assert!(format!("{:#}", error).contains("Unsupported HTTP method: 'options'"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_generate_safe_clang_format() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let (spec_path, out_dir) = write_temp_spec("banette_generate_format", SHARED_REF_SPEC);
+        let binary = out_dir.with_file_name("fake-clang-format");
+        fs::write(&binary, "#!/bin/sh\ncat > /dev/null\necho \"// formatted $1\"\n").unwrap();
+        fs::set_permissions(&binary, fs::Permissions::from_mode(0o755)).unwrap();
+
+        let options = GenerateOptions {
+            clang_format: Some(binary),
+            ..Default::default()
+        };
+        generate_safe(
+            &spec_path,
+            out_dir.to_str().unwrap(),
+            "SharedRef.h",
+            "TESTMODULE_API",
+            vec![],
+            &options,
+        )
+        .unwrap();
+
+        let header_path = out_dir.join("SharedRef.h");
+        assert_eq!(
+            fs::read_to_string(&header_path).unwrap(),
+            format!("// formatted --assume-filename={}\n", header_path.display())
+        );
+    }
+
+    #[test]
+    fn test_generate_marks_deprecated_operations_and_properties() {
+        let spec = SHARED_REF_SPEC
+            .replace(
+                r#""get": {
+        "tags": ["Users"],"#,
+                r#""get": {
+        "deprecated": true,
+        "description": "Use the v2 endpoint.",
+        "tags": ["Users"],"#,
+            )
+            .replace(
+                r#""age": { "type": "integer" }"#,
+                r#""age": { "type": "integer", "deprecated": true }"#,
+            );
+        let (spec_path, _) = write_temp_spec("banette_generate_deprecated", &spec);
+
+        let rendered = generate_to_string(
+            &spec_path,
+            "SharedRef.h",
+            "TESTMODULE_API",
+            vec![],
+            &GenerateOptions::default(),
+        )
+        .unwrap();
+
+        assert!(rendered.contains(
+            "LatentInfo = LatentInfo, DeprecatedFunction, DeprecationMessage=\"Use the v2 endpoint.\"))"
+        ));
+        assert_eq!(rendered.matches("DeprecatedFunction").count(), 1);
+        assert!(rendered.contains(
+            "UPROPERTY(EditAnywhere, BlueprintReadWrite, meta=(DeprecatedProperty, DeprecationMessage=\"Deprecated by the API specification.\"))\n    int32 age"
+        ));
+    }
+
+    #[test]
+    fn test_generate_preserves_json_names() {
+        let spec = SHARED_REF_SPEC.replace(
+            r#""age": { "type": "integer" }"#,
+            r#""age": { "type": "integer" }, "user-id": { "type": "string" }"#,
+        );
+        let (spec_path, _) = write_temp_spec("banette_generate_json_names", &spec);
+
+        let rendered = generate_to_string(
+            &spec_path,
+            "SharedRef.h",
+            "TESTMODULE_API",
+            vec![],
+            &GenerateOptions::default(),
+        )
+        .unwrap();
+
+        assert!(rendered.contains(
+            "UPROPERTY(EditAnywhere, BlueprintReadWrite, meta=(JsonName=\"user-id\"))\n    FString user_id;"
+        ));
+        assert!(rendered.contains("UPROPERTY(EditAnywhere, BlueprintReadWrite)\n    FString name;"));
+        assert_eq!(rendered.matches("JsonName").count(), 1);
+    }
+
+    #[test]
+    fn test_generate_merges_property_meta() {
+        let spec = SHARED_REF_SPEC.replace(
+            r#""age": { "type": "integer" }"#,
+            r#""age": { "type": "integer", "title": "Player Age", "minimum": 0 }"#,
+        )
+        .replace(
+            r#""name": { "type": "string" }"#,
+            r#""name": { "type": "string", "minLength": 1, "maxLength": 64 }"#,
+        );
+        let (spec_path, _) = write_temp_spec("banette_generate_property_meta", &spec);
+
+        let rendered = generate_to_string(
+            &spec_path,
+            "SharedRef.h",
+            "TESTMODULE_API",
+            vec![],
+            &GenerateOptions::default(),
+        )
+        .unwrap();
+
+        assert!(rendered.contains(
+            "UPROPERTY(EditAnywhere, BlueprintReadWrite, meta=(ClampMin=\"0\", DisplayName=\"Player Age\"))\n    int32 age"
+        ));
+        assert_eq!(rendered.matches("ClampMin").count(), 1);
+        assert!(rendered.contains(
+            "UPROPERTY(EditAnywhere, BlueprintReadWrite, meta=(ToolTip=\"Length: 1..64\"))\n    FString name;"
+        ));
+    }
+
+    #[test]
+    fn test_generate_pattern_comment_and_constant() {
+        let spec = SHARED_REF_SPEC.replace(
+            r#""name": { "type": "string" }"#,
+            r#""name": { "type": "string", "pattern": "^[a-z]+\\d*$" }"#,
+        );
+        let (spec_path, _) = write_temp_spec("banette_generate_pattern", &spec);
+        let render = |pattern_constants| {
+            let options = GenerateOptions { pattern_constants, ..Default::default() };
+            generate_to_string(&spec_path, "SharedRef.h", "TESTMODULE_API", vec![], &options)
+                .unwrap()
+        };
+
+        let output = render(true);
+        assert!(output.contains(
+            "    /** pattern: ^[a-z]+\\d*$ */\n    UPROPERTY(EditAnywhere, BlueprintReadWrite)\n    \
+             FString name;\n    static inline const FString NamePattern = TEXT(\"^[a-z]+\\\\d*$\");"
+        ), "{}", output);
+        assert_eq!(output.matches("Pattern = TEXT(").count(), 1);
+
+        let output = render(false);
+        assert!(output.contains("/** pattern: ^[a-z]+\\d*$ */"));
+        assert!(!output.contains("NamePattern"));
+    }
+
+    #[test]
+    fn test_generate_decimal_format_type() {
+        let spec = SHARED_REF_SPEC.replace(
+            r#""age": { "type": "integer" }"#,
+            r#""age": { "type": "integer" }, "balance": { "type": "string", "format": "decimal" }"#,
+        );
+        let (spec_path, _) = write_temp_spec("banette_generate_decimal", &spec);
+        let render = |format_types: &[(&str, &str)]| {
+            let format_types = format_types
+                .iter()
+                .map(|(format, ue_type)| (format.to_string(), ue_type.to_string()))
+                .collect();
+            let options = GenerateOptions { format_types, ..Default::default() };
+            generate_to_string(&spec_path, "SharedRef.h", "TESTMODULE_API", vec![], &options)
+                .unwrap()
+        };
+
+        let output = render(&[]);
+        assert!(output.contains("FString balance;"), "{}", output);
+
+        let output = render(&[("decimal", "FDecimal")]);
+        assert!(output.contains("FDecimal balance;"), "{}", output);
+        assert!(!output.contains("x-banette-ue-type"));
+    }
+
+    #[test]
+    fn test_generate_category_from_first_tag() {
+        let spec = SHARED_REF_SPEC
+            .replacen(r#""tags": ["Users"],"#, r#""tags": ["Users", "Admin"],"#, 1)
+            .replacen(r#""tags": ["Users"],"#, "", 1);
+        let (spec_path, _) = write_temp_spec("banette_generate_category", &spec);
+
+        let rendered = generate_to_string(
+            &spec_path,
+            "SharedRef.h",
+            "TESTMODULE_API",
+            vec![],
+            &GenerateOptions::default(),
+        )
+        .unwrap();
+
+        assert_eq!(rendered.matches("Category=\"SharedRef|Users\"").count(), 1);
+        assert_eq!(rendered.matches("Category=\"SharedRef|Default\"").count(), 1);
+        assert!(!rendered.contains("Admin"));
+    }
+
+    #[test]
+    fn test_generate_field_accessors() {
+        let spec = SHARED_REF_SPEC
+            .replace(
+                r#""age": { "type": "integer" }"#,
+                r##""age": { "type": "integer" },
+                  "profile": { "$ref": "#/components/schemas/Profile" }"##,
+            )
+            .replace(
+                r#""schemas": {"#,
+                r#""schemas": {
+      "Profile": { "type": "object", "properties": { "bio": { "type": "string" } } },"#,
+            );
+        let (spec_path, _) = write_temp_spec("banette_generate_field_accessors", &spec);
+        let render = |field_accessors| {
+            let options = GenerateOptions { field_accessors, ..Default::default() };
+            generate_to_string(&spec_path, "SharedRef.h", "TESTMODULE_API", vec![], &options)
+                .unwrap()
+        };
+
+        let alongside = render(FieldAccessors::Alongside);
+        assert!(alongside.contains("const int32& GetAge() const { return age; }"));
+        assert!(alongside.contains("void SetAge(const int32& InValue) { age = InValue; }"));
+        assert!(alongside.contains("const FProfile& GetProfile() const { return profile; }"));
+        assert!(
+            alongside.contains("void SetProfile(const FProfile& InValue) { profile = InValue; }")
+        );
+        assert!(!alongside.contains("private:"));
+
+        let instead = render(FieldAccessors::Instead);
+        assert!(instead.contains(
+            "private:\n    // age (Required: false)\n    UPROPERTY(EditAnywhere, BlueprintReadWrite, meta=(AllowPrivateAccess=\"true\"))"
+        ));
+        assert!(instead.contains("const FString& GetName() const { return name; }"));
+
+        assert!(!render(FieldAccessors::None).contains("GetAge"));
+    }
+
+    #[test]
+    fn test_generate_pagination_helpers() {
+        let spec = SHARED_REF_SPEC
+            .replace(
+                r#""paths": {"#,
+                r##""paths": {
+    "/users": {
+      "get": {
+        "tags": ["Users"],
+        "parameters": [
+          { "name": "page", "in": "query", "schema": { "type": "integer" } },
+          { "name": "limit", "in": "query", "schema": { "type": "integer" } }
+        ],
+        "responses": {
+          "200": {
+            "description": "OK",
+            "content": {
+              "application/json": {
+                "schema": { "type": "array", "items": { "$ref": "#/components/schemas/User" } }
+              }
+            }
+          }
+        }
+      }
+    },
+    "/feed": {
+      "get": {
+        "tags": ["Users"],
+        "parameters": [{ "name": "cursor", "in": "query", "schema": { "type": "string" } }],
+        "responses": {
+          "200": {
+            "description": "OK",
+            "content": {
+              "application/json": { "schema": { "$ref": "#/components/schemas/UserPage" } }
+            }
+          }
+        }
+      }
+    },"##,
+            )
+            .replace(
+                r#""schemas": {"#,
+                r##""schemas": {
+      "UserPage": {
+        "type": "object",
+        "properties": {
+          "users": { "type": "array", "items": { "$ref": "#/components/schemas/User" } },
+          "nextCursor": { "type": "string" }
+        }
+      },"##,
+            );
+        let (spec_path, _) = write_temp_spec("banette_generate_pagination", &spec);
+        let render = |pagination| {
+            let options = GenerateOptions { pagination, ..Default::default() };
+            generate_to_string(&spec_path, "SharedRef.h", "TESTMODULE_API", vec![], &options)
+                .unwrap()
+        };
+
+        let output = render(true);
+        assert!(output.contains(
+            "static FVoidCoroutine GET_Users_AllPages(int32 limit, TArray<FUser>& ResponseBody, bool& bSuccess, FLatentActionInfo LatentInfo)"
+        ));
+        assert!(output.contains(
+            "for (int32 _PageCount_ = 0; _PageCount_ < 1000; ++_PageCount_, ++page)"
+        ));
+        assert!(output.contains("ResponseBody.Append(MoveTemp(_Page_));"));
+        assert!(output.contains("if (_PageSize_ == 0 || _PageSize_ < limit)"));
+
+        assert!(output.contains(
+            "static FVoidCoroutine GET_Feed_AllPages(TArray<FUser>& ResponseBody, bool& bSuccess, FLatentActionInfo LatentInfo)"
+        ));
+        assert!(output.contains("FUserPage _Page_;"));
+        assert!(output.contains("ResponseBody.Append(MoveTemp(_Page_.users));"));
+        assert!(output.contains("cursor = _Page_.nextCursor;"));
+        assert!(output.contains("if (cursor.IsEmpty())"));
+        assert_eq!(output.matches("_AllPages(").count(), 2);
+
+        assert!(!render(false).contains("_AllPages"));
+    }
+
+    #[test]
+    fn test_generate_response_delegates() {
+        let (spec_path, _) = write_temp_spec("banette_generate_delegates", SHARED_REF_SPEC);
+        let render = |response_delegates| {
+            let options = GenerateOptions { response_delegates, ..Default::default() };
+            generate_to_string(&spec_path, "SharedRef.h", "TESTMODULE_API", vec![], &options)
+                .unwrap()
+        };
+
+        let output = render(true);
+        assert!(output.contains(
+            "\nusing FGET_Users_By_IdResponseType = FUser;\n\
+             DECLARE_DYNAMIC_DELEGATE_OneParam(FOnGET_Users_By_IdResponse, \
+             const FGET_Users_By_IdResponseType&, Response);\n\
+             DECLARE_DYNAMIC_DELEGATE(FOnPUT_Users_By_IdResponse);\n\nUCLASS()"
+        ));
+
+        assert!(!render(false).contains("DECLARE_DYNAMIC_DELEGATE"));
+    }
+
+    #[test]
+    fn test_generate_request_structs() {
+        let spec = r##"{
+  "openapi": "3.0.0",
+  "info": { "title": "Inventory", "version": "1.0.0" },
+  "paths": {
+    "/players/{player-id}/items": {
+      "post": {
+        "parameters": [
+          { "name": "player-id", "in": "path", "required": true, "schema": { "type": "integer" } },
+          { "name": "limit", "in": "query", "schema": { "type": "integer", "default": 20 } },
+          { "name": "verbose", "in": "query", "schema": { "type": "boolean" } },
+          { "name": "class", "in": "query", "required": true, "schema": { "type": "string" } }
+        ],
+        "requestBody": {
+          "content": {
+            "application/json": { "schema": { "$ref": "#/components/schemas/Item" } }
+          }
+        },
+        "responses": { "204": { "description": "Added" } }
+      }
+    },
+    "/health": { "get": { "responses": { "204": { "description": "Healthy" } } } }
+  },
+  "components": {
+    "schemas": {
+      "Item": { "type": "object", "properties": { "name": { "type": "string" } } }
+    }
+  }
+}"##;
+        let (spec_path, _) = write_temp_spec("banette_generate_request_structs", spec);
+        let render = |request_structs| {
+            let options = GenerateOptions { request_structs, ..Default::default() };
+            generate_to_string(&spec_path, "Inventory.h", "TESTMODULE_API", vec![], &options)
+                .unwrap()
+        };
+
+        let output = render(true);
+        assert!(output.contains(
+            "struct TESTMODULE_API FPOST_Players_Items_By_PlayerIdRequest\n{\n    GENERATED_BODY()\n\
+             \x20   // player-id (In: path, Required: true)\n\
+             \x20   UPROPERTY(EditAnywhere, BlueprintReadWrite)\n    int32 player_id = 0;\n\
+             \x20   // class (In: query, Required: true)\n\
+             \x20   UPROPERTY(EditAnywhere, BlueprintReadWrite)\n    FString class_;\n\
+             \x20   // limit (In: query, Required: false)\n\
+             \x20   UPROPERTY(EditAnywhere, BlueprintReadWrite)\n    int32 limit = 20;\n\
+             \x20   // verbose (In: query, Required: false)\n\
+             \x20   UPROPERTY(EditAnywhere, BlueprintReadWrite)\n    bool verbose = false;\n\
+             \x20   // RequestBody (In: body, Required: false)\n\
+             \x20   UPROPERTY(EditAnywhere, BlueprintReadWrite)\n    FItem RequestBody;\n};"
+        ), "{}", output);
+        assert!(output.contains(
+            "POST_Players_Items_By_PlayerId(const FPOST_Players_Items_By_PlayerIdRequest& Request, \
+             bool& bSuccess, FLatentActionInfo LatentInfo)"
+        ));
+        assert!(output.contains("{\"player-id\", Request.player_id}, {\"limit\", Request.limit}"));
+        assert!(output.contains(".With_Body(ToBytes(Request.RequestBody))"));
+        // An operation without parameters or body keeps its signature and gets no struct
+        assert!(output.contains("GET_Health(bool& bSuccess, FLatentActionInfo LatentInfo)"));
+        assert!(!output.contains("FGET_HealthRequest"));
+
+        let output = render(false);
+        assert!(!output.contains("FPOST_Players_Items_By_PlayerIdRequest"));
+        assert!(output.contains("POST_Players_Items_By_PlayerId(int32 player_id, "));
+    }
+
+    #[test]
+    fn test_generate_split_read_write() {
+        let spec = SHARED_REF_SPEC.replace(
+            r#""properties": { "name": { "type": "string" }, "age": { "type": "integer" } }"#,
+            r#""properties": {
+          "id": { "type": "integer", "readOnly": true },
+          "name": { "type": "string" },
+          "age": { "type": "integer" }
+        }"#,
+        );
+        let (spec_path, _) = write_temp_spec("banette_generate_read_write", &spec);
+        let render = |split_read_write| {
+            let options = GenerateOptions { split_read_write, ..Default::default() };
+            generate_to_string(&spec_path, "SharedRef.h", "TESTMODULE_API", vec![], &options)
+                .unwrap()
+        };
+        let struct_body = |output: &str, name: &str| {
+            let start = output.find(&format!("struct TESTMODULE_API {}\n", name)).unwrap();
+            output[start..start + output[start..].find("};").unwrap()].to_string()
+        };
+
+        let output = render(true);
+        let response = struct_body(&output, "FUser");
+        assert!(response.contains("int32 id = 0;"), "{}", response);
+        assert!(response.contains("FString name;"));
+        let request = struct_body(&output, "FUserRequest");
+        assert!(!request.contains(" id"), "{}", request);
+        assert!(request.contains("FString name;"));
+        assert!(request.contains("int32 age = 0;"));
+        assert!(output.contains("GET_Users_By_Id(int32 id, FUser& ResponseBody, "));
+        assert!(output.contains("PUT_Users_By_Id(const FUserRequest& RequestBody, "));
+
+        let output = render(false);
+        assert!(!output.contains("FUserRequest"));
+        assert!(struct_body(&output, "FUser").contains("int32 id = 0;"));
+    }
+
+    #[test]
+    fn test_generate_blueprint_async_actions() {
+        let spec = SHARED_REF_SPEC.replace(
+            r#""responses": { "204": { "description": "Updated" } }"#,
+            r##""responses": {
+          "204": { "description": "Updated" },
+          "422": {
+            "description": "Invalid",
+            "content": {
+              "application/json": { "schema": { "$ref": "#/components/schemas/Problem" } }
+            }
+          }
+        }"##,
+        )
+        .replace(
+            r#""schemas": {"#,
+            r#""schemas": {
+      "Problem": { "type": "object", "properties": { "detail": { "type": "string" } } },"#,
+        );
+        let (spec_path, _) = write_temp_spec("banette_generate_blueprint_async", &spec);
+        let render = |blueprint_async| {
+            let options = GenerateOptions {
+                blueprint_async,
+                ..Default::default()
+            };
+            generate_to_string(&spec_path, "SharedRef.h", "TESTMODULE_API", vec![], &options)
+                .unwrap()
+        };
+
+        let rendered = render(true);
+        assert!(rendered.contains("#include \"Kismet/BlueprintAsyncActionBase.h\""));
+        assert_eq!(rendered.matches(": public UBlueprintAsyncActionBase").count(), 2);
+
+        // GET: typed success, no error response declared
+        assert!(rendered.contains(
+            "using FGET_Users_By_IdResponseType = FUser;\n\
+             DECLARE_DYNAMIC_MULTICAST_DELEGATE_OneParam(FGET_Users_By_IdSuccess, \
+             const FGET_Users_By_IdResponseType&, ResponseBody);"
+        ));
+        assert!(rendered.contains("DECLARE_DYNAMIC_MULTICAST_DELEGATE(FGET_Users_By_IdFailure);"));
+        assert!(rendered.contains(
+            "class TESTMODULE_API UGET_Users_By_IdAsyncAction : public UBlueprintAsyncActionBase"
+        ));
+        assert!(rendered.contains(
+            "static UGET_Users_By_IdAsyncAction* GET_Users_By_IdAsync(\
+             UObject* WorldContextObject, int32 id)"
+        ));
+
+        // PUT: no response body, typed error
+        assert!(rendered.contains("DECLARE_DYNAMIC_MULTICAST_DELEGATE(FPUT_Users_By_IdSuccess);"));
+        assert!(rendered.contains(
+            "using FPUT_Users_By_IdErrorType = FProblem;\n\
+             DECLARE_DYNAMIC_MULTICAST_DELEGATE_OneParam(FPUT_Users_By_IdFailure, \
+             const FPUT_Users_By_IdErrorType&, ErrorBody);"
+        ));
+        assert!(rendered.contains(
+            "static UPUT_Users_By_IdAsyncAction* PUT_Users_By_IdAsync(\
+             UObject* WorldContextObject, const FUser& RequestBody)"
+        ));
+        assert!(rendered.contains("OnFailure.Broadcast(ErrorBody);"));
+
+        let plain = render(false);
+        assert!(!plain.contains("AsyncAction"));
+        assert!(!plain.contains("DECLARE_DYNAMIC_MULTICAST_DELEGATE"));
+    }
+
+    #[test]
+    fn test_generate_blueprint_async_comma_type() {
+        // A type with a comma would split into two macro arguments without the alias
+        let spec = SHARED_REF_SPEC.replace(
+            r##""schema": { "$ref": "#/components/schemas/User" } }
+            }"##,
+            r#""schema": { "type": "object", "format": "score-map" } }
+            }"#,
+        );
+        let (spec_path, _) = write_temp_spec("banette_generate_async_comma", &spec);
+        let options = GenerateOptions {
+            blueprint_async: true,
+            format_types: BTreeMap::from([(
+                "score-map".to_string(),
+                "TMap<FString, int32>".to_string(),
+            )]),
+            ..Default::default()
+        };
+        let rendered =
+            generate_to_string(&spec_path, "SharedRef.h", "TESTMODULE_API", vec![], &options)
+                .unwrap();
+
+        assert!(rendered.contains(
+            "using FGET_Users_By_IdResponseType = TMap<FString, int32>;\n\
+             DECLARE_DYNAMIC_MULTICAST_DELEGATE_OneParam(FGET_Users_By_IdSuccess, \
+             const FGET_Users_By_IdResponseType&, ResponseBody);"
+        ));
+        assert!(rendered.contains("FGET_Users_By_IdResponseType ResponseBody;"));
+    }
+
+    #[test]
+    fn test_generate_sanitizes_param_names() {
+        let spec = SHARED_REF_SPEC.replace(
+            r#"{ "name": "id", "in": "path", "required": true, "schema": { "type": "integer" } }"#,
+            r#"{ "name": "id", "in": "path", "required": true, "schema": { "type": "integer" } },
+          { "name": "class", "in": "query", "schema": { "type": "string" } }"#,
+        );
+        let (spec_path, _) = write_temp_spec("banette_generate_keyword_param", &spec);
+        let options = GenerateOptions {
+            blueprint_async: true,
+            ..Default::default()
+        };
+        let rendered =
+            generate_to_string(&spec_path, "SharedRef.h", "TESTMODULE_API", vec![], &options)
+                .unwrap();
+
+        assert!(rendered.contains("GET_Users_By_Id(int32 id, FString class_, FUser& ResponseBody"));
+        assert!(rendered.contains("UObject* WorldContextObject, int32 id, FString class_)"));
+        assert!(rendered.contains("Action->class_ = class_;"));
+        assert!(rendered.contains("    FString class_;\n"));
+        // The query keeps the original name
+        assert!(rendered.contains(r#"{"id", id}, {"class", class_}"#));
+        assert!(!rendered.contains(" class;"));
+    }
+
+    #[test]
+    fn test_generate_param_default_arguments() {
+        let spec = SHARED_REF_SPEC.replace(
+            r#"{ "name": "id", "in": "path", "required": true, "schema": { "type": "integer" } }"#,
+            r#"{ "name": "shard", "in": "query",
+            "schema": { "type": "string", "default": "CN-1" } },
+          { "name": "id", "in": "path", "required": true, "schema": { "type": "integer" } }"#,
+        );
+        let (spec_path, out_dir) = write_temp_spec("banette_generate_param_default", &spec);
+        let options = GenerateOptions {
+            source: true,
+            blueprint_async: true,
+            ..Default::default()
+        };
+        generate_safe(&spec_path, out_dir.to_str().unwrap(), "Api.h", "", vec![], &options)
+            .unwrap();
+        let header = fs::read_to_string(out_dir.join("Api.h")).unwrap();
+        let source = fs::read_to_string(out_dir.join("Api.cpp")).unwrap();
+
+        // The default argument follows the out parameters, which may not come after it
+        assert!(header.contains(
+            "GET_Users_By_Id(int32 id, FUser& ResponseBody, bool& bSuccess, \
+             FLatentActionInfo LatentInfo, FString shard = TEXT(\"CN-1\"));"
+        ));
+        assert!(header.contains(
+            "GET_Users_By_IdAsync(UObject* WorldContextObject, int32 id, \
+             FString shard = TEXT(\"CN-1\"))"
+        ));
+        // The definition does not repeat it
+        assert!(source.contains("FLatentActionInfo LatentInfo, FString shard)"));
+        assert!(!source.contains("CN-1"));
+
+        fs::remove_dir_all(out_dir.parent().unwrap()).ok();
+    }
+
+    #[test]
+    fn test_generate_settings_class() {
+        let spec = SHARED_REF_SPEC.replace(
+            r#""paths": {"#,
+            r#""servers": [
+    { "url": "https://api.example.com/v1/" },
+    { "url": "https://staging.example.com/v1" }
+  ],
+  "paths": {"#,
+        );
+        let (spec_path, _) = write_temp_spec("banette_generate_settings_class", &spec);
+        let render = |settings_class| {
+            let options = GenerateOptions {
+                settings_class,
+                ..Default::default()
+            };
+            generate_to_string(&spec_path, "SharedRef.h", "TESTMODULE_API", vec![], &options)
+                .unwrap()
+        };
+
+        let rendered = render(true);
+        assert!(rendered.contains("#include \"Engine/DeveloperSettings.h\""));
+        assert!(rendered.contains(
+            "class TESTMODULE_API USharedRefSettings : public UDeveloperSettings"
+        ));
+        assert!(rendered.contains("FString BaseUrl = TEXT(\"https://api.example.com/v1\");"));
+        assert!(rendered.contains(
+            ".With_Url(GetDefault<USharedRefSettings>()->BaseUrl + FString::Format(\
+             TEXT(\"/users/{id}\")"
+        ));
+
+        let plain = render(false);
+        assert!(!plain.contains("UDeveloperSettings"));
+        assert!(!plain.contains("BaseUrl"));
+
+        // Without servers the base URL defaults to empty, keeping paths relative
+        let (spec_path, _) = write_temp_spec("banette_generate_settings_class", SHARED_REF_SPEC);
+        let options = GenerateOptions {
+            settings_class: true,
+            ..Default::default()
+        };
+        let rendered =
+            generate_to_string(&spec_path, "SharedRef.h", "TESTMODULE_API", vec![], &options)
+                .unwrap();
+        assert!(rendered.contains("FString BaseUrl = TEXT(\"\");"));
+
+        // Server variables take their defaults, and the URL is escaped for the literal
+        let spec = SHARED_REF_SPEC.replace(
+            r#""paths": {"#,
+            r#""servers": [
+    {
+      "url": "https://{region}.example.com/{base}/\"v1\"/",
+      "variables": {
+        "region": { "default": "eu", "enum": ["eu", "us"] },
+        "base": { "default": "api" }
+      }
+    }
+  ],
+  "paths": {"#,
+        );
+        let (spec_path, _) = write_temp_spec("banette_generate_settings_class", &spec);
+        let rendered =
+            generate_to_string(&spec_path, "SharedRef.h", "TESTMODULE_API", vec![], &options)
+                .unwrap();
+        assert!(rendered.contains(
+            "FString BaseUrl = TEXT(\"https://eu.example.com/api/\\\"v1\\\"\");"
+        ));
+    }
+
+    #[test]
+    fn test_parse_spec_header() {
+        assert_eq!(
+            parse_spec_header("Authorization: Bearer abc"),
+            Ok(("Authorization".to_string(), "Bearer abc".to_string()))
+        );
+
+        // Only the first colon separates the name from the value
+        assert_eq!(
+            parse_spec_header("X-Origin:https://example.com"),
+            Ok(("X-Origin".to_string(), "https://example.com".to_string()))
+        );
+
+        assert!(parse_spec_header("Authorization").is_err());
+        assert!(parse_spec_header(": value").is_err());
+    }
 
     #[test]
     fn test_parse_include_headers() {
@@ -0,0 +1,195 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use super::GenerateOptions;
+use crate::filter::path_to_func_name::{cap_func_name_len, func_name};
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// Operation field holding the function name chosen by [`assign_func_names`].
+pub const FUNC_NAME_KEY: &str = "x-banette-func-name";
+
+/// Path item keys that hold operations.
+pub(crate) const HTTP_METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Stores a unique function name on every operation under `paths`, as [`FUNC_NAME_KEY`].
+///
+/// Names are derived as by `f_path_to_func_name`, with the
+/// [`func_naming`](GenerateOptions::func_naming) settings and `options.max_func_name_len`
+/// applied. Operations are visited in path and then method
+/// order, and when a name is already taken the later operation gets the first free `_2`,
+/// `_3`, ... suffix, so the same spec always yields the same names. The length cap is
+/// applied after the suffix.
+pub fn assign_func_names(document: &mut Value, options: &GenerateOptions) {
+    let Some(paths) = document.get_mut("paths").and_then(Value::as_object_mut) else {
+        return;
+    };
+
+    let mut taken = HashSet::new();
+    let mut operations: Vec<(&String, &String, &mut Value)> = Vec::new();
+    for (path, path_item) in paths.iter_mut() {
+        let Some(path_item) = path_item.as_object_mut() else {
+            continue;
+        };
+        for (method, operation) in path_item.iter_mut() {
+            if HTTP_METHODS.contains(&method.as_str()) && operation.is_object() {
+                operations.push((path, method, operation));
+            }
+        }
+    }
+    operations.sort_by(|a, b| (a.0, a.1).cmp(&(b.0, b.1)));
+
+    for (path, method, operation) in operations {
+        let operation_id = operation.get("operationId").and_then(Value::as_str);
+        let base = func_name(path, method, operation_id, &options.func_naming());
+        let cap = |name: String| match options.max_func_name_len {
+            Some(max_len) => cap_func_name_len(&name, max_len),
+            None => name,
+        };
+
+        let mut name = cap(base.clone());
+        let mut suffix = 2;
+        while !taken.insert(name.clone()) {
+            name = cap(format!("{}_{}", base, suffix));
+            suffix += 1;
+        }
+
+        operation[FUNC_NAME_KEY] = Value::String(name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn names(document: &Value) -> Vec<(&str, &str)> {
+        let mut names = Vec::new();
+        for (path, path_item) in document["paths"].as_object().unwrap() {
+            for operation in path_item.as_object().unwrap().values() {
+                names.push((path.as_str(), operation[FUNC_NAME_KEY].as_str().unwrap()));
+            }
+        }
+        names
+    }
+
+    #[test]
+    fn test_assign_func_names_disambiguates_collisions() {
+        let mut document = json!({
+            "paths": {
+                "/api/resource": { "get": {} },
+                "/api//resource": { "get": {} },
+                "/items": { "get": { "operationId": "GET_Api_Resource_2" } }
+            }
+        });
+        assign_func_names(&mut document, &GenerateOptions::default());
+
+        assert_eq!(
+            names(&document),
+            vec![
+                ("/api//resource", "GET_Api_Resource"),
+                ("/api/resource", "GET_Api_Resource_2"),
+                ("/items", "GETApiResource2"),
+            ]
+        );
+
+        // The outcome does not depend on the order paths were declared in
+        let mut reordered = json!({
+            "paths": {
+                "/api//resource": { "get": {} },
+                "/api/resource": { "get": {} }
+            }
+        });
+        assign_func_names(&mut reordered, &GenerateOptions::default());
+        assert_eq!(names(&reordered), names(&document)[..2].to_vec());
+    }
+
+    #[test]
+    fn test_assign_func_names_caps_length() {
+        let mut document = json!({
+            "paths": {
+                "/organizations/{organization_id}/projects": { "get": {} },
+                "/organizations//projects/{organization_id}": { "get": {} }
+            }
+        });
+        let options = GenerateOptions {
+            max_func_name_len: Some(20),
+            ..Default::default()
+        };
+        assign_func_names(&mut document, &options);
+
+        let names = names(&document);
+        assert!(names.iter().all(|(_, name)| name.len() <= 20));
+        assert_ne!(names[0].1, names[1].1);
+    }
+
+    #[test]
+    fn test_assign_func_names_root_segment() {
+        let document = json!({
+            "paths": {
+                "/": { "get": {}, "post": {} },
+                "/root": { "get": {} }
+            }
+        });
+
+        let mut unnamed = document.clone();
+        assign_func_names(&mut unnamed, &GenerateOptions::default());
+        assert_eq!(unnamed["paths"]["/"]["get"][FUNC_NAME_KEY], "GET");
+        assert_eq!(unnamed["paths"]["/"]["post"][FUNC_NAME_KEY], "POST");
+
+        let mut named = document;
+        let options = GenerateOptions {
+            root_func_segment: Some("Root".to_string()),
+            ..Default::default()
+        };
+        assign_func_names(&mut named, &options);
+        assert_eq!(named["paths"]["/"]["get"][FUNC_NAME_KEY], "GET_Root");
+        assert_eq!(named["paths"]["/"]["post"][FUNC_NAME_KEY], "POST_Root");
+        // A real `/root` path still gets a distinct name
+        assert_eq!(named["paths"]["/root"]["get"][FUNC_NAME_KEY], "GET_Root_2");
+    }
+
+    #[test]
+    fn test_assign_func_names_strip_path_prefix() {
+        let mut document = json!({
+            "paths": {
+                "/v1/players": { "get": {} },
+                "/v2/players": { "get": {} },
+                "/v1/players/{id}": { "get": { "operationId": "getPlayer" } }
+            }
+        });
+        let options = GenerateOptions {
+            strip_path_prefix: Some("v1".to_string()),
+            ..Default::default()
+        };
+        assign_func_names(&mut document, &options);
+
+        let paths = &document["paths"];
+        assert_eq!(paths["/v1/players"]["get"][FUNC_NAME_KEY], "GET_Players");
+        assert_eq!(paths["/v2/players"]["get"][FUNC_NAME_KEY], "GET_V2_Players");
+        assert_eq!(paths["/v1/players/{id}"]["get"][FUNC_NAME_KEY], "GetPlayer");
+    }
+
+    #[test]
+    fn test_assign_func_names_skips_non_operations() {
+        let mut document = json!({
+            "paths": {
+                "/items": {
+                    "parameters": [],
+                    "summary": "Items",
+                    "get": {},
+                    "post": { "operationId": "createItem" }
+                }
+            }
+        });
+        assign_func_names(&mut document, &GenerateOptions::default());
+
+        let path_item = &document["paths"]["/items"];
+        assert_eq!(path_item["get"][FUNC_NAME_KEY], "GET_Items");
+        assert_eq!(path_item["post"][FUNC_NAME_KEY], "CreateItem");
+        assert_eq!(path_item["parameters"], json!([]));
+    }
+}
@@ -0,0 +1,201 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{BufWriter, ErrorKind as IoErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+/// What a generation produced: the files it wrote (or, in a dry run, checked) and the
+/// operations it left out of them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Generation {
+    pub files: Vec<GeneratedFile>,
+    /// Operations left out of the files because they failed to render.
+    pub skipped: Vec<SkippedOperation>,
+}
+
+/// A file written (or, in a dry run, checked) by generation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GeneratedFile {
+    pub path: PathBuf,
+    pub outcome: WriteOutcome,
+}
+
+/// An operation left out of the generated files, seen by templates as an entry of
+/// `skipped_operations`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SkippedOperation {
+    pub path: String,
+    pub method: String,
+    /// Why it failed to render, on one line.
+    pub error: String,
+}
+
+/// What writing a generated file did, or in a dry run would do, to the file on disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteOutcome {
+    /// The file did not exist and was written.
+    Created,
+    /// The file existed with different contents and was replaced.
+    Updated,
+    /// The file already held the rendered contents and was left untouched.
+    Unchanged,
+}
+
+impl fmt::Display for WriteOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            WriteOutcome::Created => "created",
+            WriteOutcome::Updated => "updated",
+            WriteOutcome::Unchanged => "unchanged",
+        })
+    }
+}
+
+/// Writes `contents` to `path` unless the file already holds exactly that, so an
+/// unchanged output keeps its mtime and does not trigger a rebuild.
+///
+/// The contents are written to a sibling temp file and renamed into place, so a
+/// failed write never leaves a truncated file behind.
+pub fn write_if_changed(path: &Path, contents: &str) -> Result<WriteOutcome> {
+    let outcome = compare_output(path, contents)?;
+    if outcome == WriteOutcome::Unchanged {
+        return Ok(outcome);
+    }
+
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let temp_path = path.with_file_name(format!(".{}.tmp", file_name));
+
+    let result = fs::write(&temp_path, contents)
+        .and_then(|()| fs::rename(&temp_path, path))
+        .with_context(|| format!("Failed to write {}", path.display()));
+    if result.is_err() {
+        fs::remove_file(&temp_path).ok();
+    }
+
+    result.map(|()| outcome)
+}
+
+/// Writes what `write` produces straight to `path` through a buffered writer, for output
+/// too large to hold in memory first.
+///
+/// The file is always rewritten, as its contents are not known up front to compare with
+/// the existing one, so the outcome is never [`WriteOutcome::Unchanged`]. Like
+/// [`write_if_changed`] it goes through a sibling temp file, so an error from `write`
+/// leaves the existing file untouched.
+pub fn write_streamed(
+    path: &Path,
+    write: impl FnOnce(&mut dyn Write) -> Result<()>,
+) -> Result<WriteOutcome> {
+    let outcome = if path.exists() {
+        WriteOutcome::Updated
+    } else {
+        WriteOutcome::Created
+    };
+
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let temp_path = path.with_file_name(format!(".{}.tmp", file_name));
+
+    let result = File::create(&temp_path)
+        .with_context(|| format!("Failed to write {}", path.display()))
+        .and_then(|file| {
+            let mut writer = BufWriter::new(file);
+            write(&mut writer)?;
+            writer
+                .flush()
+                .with_context(|| format!("Failed to write {}", path.display()))
+        })
+        .and_then(|()| {
+            fs::rename(&temp_path, path)
+                .with_context(|| format!("Failed to write {}", path.display()))
+        });
+    if result.is_err() {
+        fs::remove_file(&temp_path).ok();
+    }
+
+    result.map(|()| outcome)
+}
+
+/// Reports what [`write_if_changed`] would do, without writing anything.
+pub fn compare_output(path: &Path, contents: &str) -> Result<WriteOutcome> {
+    match fs::read(path) {
+        Ok(existing) if existing == contents.as_bytes() => Ok(WriteOutcome::Unchanged),
+        Ok(_) => Ok(WriteOutcome::Updated),
+        Err(e) if e.kind() == IoErrorKind::NotFound => Ok(WriteOutcome::Created),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_if_changed() {
+        let dir = std::env::temp_dir().join("banette_write_if_changed");
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("Api.h");
+
+        assert_eq!(write_if_changed(&path, "a").unwrap(), WriteOutcome::Created);
+        let modified = fs::metadata(&path).unwrap().modified().unwrap();
+
+        assert_eq!(write_if_changed(&path, "a").unwrap(), WriteOutcome::Unchanged);
+        assert_eq!(fs::metadata(&path).unwrap().modified().unwrap(), modified);
+
+        assert_eq!(write_if_changed(&path, "b").unwrap(), WriteOutcome::Updated);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "b");
+
+        // No temp file is left behind
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_compare_output() {
+        let dir = std::env::temp_dir().join("banette_compare_output");
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("Api.h");
+
+        assert_eq!(compare_output(&path, "a").unwrap(), WriteOutcome::Created);
+        assert!(!path.exists());
+
+        fs::write(&path, "a").unwrap();
+        assert_eq!(compare_output(&path, "a").unwrap(), WriteOutcome::Unchanged);
+        assert_eq!(compare_output(&path, "b").unwrap(), WriteOutcome::Updated);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "a");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_write_streamed() {
+        let dir = std::env::temp_dir().join("banette_write_streamed");
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("Api.h");
+
+        let outcome = write_streamed(&path, |writer| Ok(writer.write_all(b"a")?)).unwrap();
+        assert_eq!(outcome, WriteOutcome::Created);
+        let outcome = write_streamed(&path, |writer| Ok(writer.write_all(b"b")?)).unwrap();
+        assert_eq!(outcome, WriteOutcome::Updated);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "b");
+
+        // A failed write keeps the existing file and leaves no temp file behind
+        let result = write_streamed(&path, |writer| {
+            writer.write_all(b"partial")?;
+            Err(anyhow::anyhow!("render failed"))
+        });
+        assert_eq!(result.unwrap_err().to_string(), "render failed");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "b");
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
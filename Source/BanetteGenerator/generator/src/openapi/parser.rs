@@ -6,7 +6,10 @@
 ///
 /// Supports two formats:
 /// 1. Full format: `#include "a.h";#include "b.h";` or `#include <vector>;`
-/// 2. Simplified format: `a.h;b.h` (will be converted to `#include "a.h"` format)
+/// 2. Simplified format: `a.h;b.h` (will be converted to `#include "a.h"` format).
+///    Newlines (LF or CRLF) separate entries just like semicolons.
+///    Entries already wrapped in `<...>` or `"..."` keep their brackets, so
+///    `<vector>;a.h` yields `#include <vector>` and `#include "a.h"`.
 ///
 /// # Arguments
 /// * `input` - A string that may contain multiple header includes in either format.
@@ -41,11 +44,14 @@ pub fn parse_include_headers(input: &str) -> Vec<String> {
     } else {
         // Simplified format: a.h;b.h -> #include "a.h", #include "b.h"
         input
-            .split(';')
+            .split([';', '\n'])
             .filter_map(|part| {
                 let trimmed = part.trim();
                 if trimmed.is_empty() {
                     None
+                } else if is_bracketed(trimmed) {
+                    // Already <system> or "local": keep the caller's choice
+                    Some(format!("#include {}", trimmed))
                 } else {
                     // Wrap in #include "..." format
                     Some(format!("#include \"{}\"", trimmed))
@@ -54,3 +60,89 @@ pub fn parse_include_headers(input: &str) -> Vec<String> {
             .collect()
     }
 }
+
+/// Returns `true` if the include target is already wrapped in `<...>` or `"..."`.
+fn is_bracketed(target: &str) -> bool {
+    target.len() >= 2
+        && ((target.starts_with('<') && target.ends_with('>'))
+            || (target.starts_with('"') && target.ends_with('"')))
+}
+
+/// Post-processing applied by [`parse_include_headers_with`].
+///
+/// Both default to off, so the parsed includes are returned exactly as given.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IncludeOptions {
+    /// Drop repeated directives, keeping the first occurrence.
+    pub dedupe: bool,
+    /// Order system `<...>` includes first, then local `"..."` includes, each alphabetically.
+    pub sort: bool,
+}
+
+/// Parses include directives like [`parse_include_headers`], then applies `options`.
+pub fn parse_include_headers_with(input: &str, options: IncludeOptions) -> Vec<String> {
+    let mut headers = parse_include_headers(input);
+
+    if options.dedupe {
+        let mut seen = std::collections::HashSet::new();
+        headers.retain(|header| seen.insert(header.clone()));
+    }
+
+    if options.sort {
+        headers.sort_by_cached_key(|header| {
+            let target = header.trim_start_matches("#include").trim();
+            (!target.starts_with('<'), target.to_string())
+        });
+    }
+
+    headers
+}
+
+/// Parses a `Name: Value` request header as passed to `--spec-header`.
+///
+/// The name is trimmed, and only the first `:` separates name from value, so values
+/// such as URLs may contain further colons.
+pub fn parse_spec_header(input: &str) -> Result<(String, String), String> {
+    let (name, value) = input
+        .split_once(':')
+        .ok_or_else(|| format!("Invalid header '{}': expected 'Name: Value'", input))?;
+
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Invalid header: name cannot be empty".to_string());
+    }
+
+    Ok((name.to_string(), value.trim().to_string()))
+}
+
+/// Parses a `key=value` template variable as passed to `--set`.
+///
+/// Only the first `=` separates key from value; the key must be non-empty.
+pub fn parse_context_entry(input: &str) -> Result<(String, String), String> {
+    let (key, value) = input
+        .split_once('=')
+        .ok_or_else(|| format!("Invalid entry '{}': expected 'key=value'", input))?;
+
+    let key = key.trim();
+    if key.is_empty() {
+        return Err("Invalid entry: key cannot be empty".to_string());
+    }
+
+    Ok((key.to_string(), value.to_string()))
+}
+
+/// Parses a `format=Type` mapping as passed to `--format-type`.
+///
+/// Both sides are trimmed and must be non-empty.
+pub fn parse_format_type(input: &str) -> Result<(String, String), String> {
+    let (format, ue_type) = input
+        .split_once('=')
+        .ok_or_else(|| format!("Invalid format type '{}': expected 'format=Type'", input))?;
+
+    let (format, ue_type) = (format.trim(), ue_type.trim());
+    if format.is_empty() || ue_type.is_empty() {
+        return Err(format!("Invalid format type '{}': format and type cannot be empty", input));
+    }
+
+    Ok((format.to_string(), ue_type.to_string()))
+}
@@ -0,0 +1,198 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use super::naming::HTTP_METHODS;
+use serde_json::Value;
+
+/// JSON pointers (without the leading `#`) of every schema in an OpenAPI 3 document.
+///
+/// Only schema positions are visited: `components.schemas`, the `schema` of parameters,
+/// headers and media types (under `paths`, `webhooks` and `components`), and within a
+/// schema its `properties`, `patternProperties`, `items`, `prefixItems`,
+/// `additionalProperties`, `not`, and `allOf`/`oneOf`/`anyOf` members. So `example`,
+/// `examples`, `default`, `enum` and `x-*` values are never taken for schemas, however
+/// they look.
+pub fn schema_pointers(document: &Value) -> Vec<String> {
+    let mut pointers = Vec::new();
+    let components = document.get("components");
+
+    for (name, schema) in entries(components.and_then(|c| c.get("schemas"))) {
+        let pointer = format!("/components/schemas/{}", escape_pointer(name));
+        walk_schema(schema, pointer, &mut pointers);
+    }
+    for (name, parameter) in entries(components.and_then(|c| c.get("parameters"))) {
+        let pointer = format!("/components/parameters/{}", escape_pointer(name));
+        walk_parameter(parameter, pointer, &mut pointers);
+    }
+    for (name, header) in entries(components.and_then(|c| c.get("headers"))) {
+        let pointer = format!("/components/headers/{}", escape_pointer(name));
+        walk_parameter(header, pointer, &mut pointers);
+    }
+    for (name, body) in entries(components.and_then(|c| c.get("requestBodies"))) {
+        let pointer = format!("/components/requestBodies/{}", escape_pointer(name));
+        walk_content(body, &pointer, &mut pointers);
+    }
+    for (name, response) in entries(components.and_then(|c| c.get("responses"))) {
+        let pointer = format!("/components/responses/{}", escape_pointer(name));
+        walk_response(response, &pointer, &mut pointers);
+    }
+    for root in ["paths", "webhooks"] {
+        for (path, path_item) in entries(document.get(root)) {
+            let pointer = format!("/{}/{}", root, escape_pointer(path));
+            walk_path_item(path_item, &pointer, &mut pointers);
+        }
+    }
+    pointers
+}
+
+/// Escapes `key` for use as one JSON pointer segment.
+fn escape_pointer(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// The entries of `value` if it is an object.
+fn entries(value: Option<&Value>) -> impl Iterator<Item = (&String, &Value)> {
+    value.and_then(Value::as_object).into_iter().flatten()
+}
+
+/// The items of `value` if it is an array, with their indexes.
+fn items(value: Option<&Value>) -> impl Iterator<Item = (usize, &Value)> {
+    value.and_then(Value::as_array).into_iter().flatten().enumerate()
+}
+
+/// The schemas of a path item's shared parameters and of its operations.
+fn walk_path_item(path_item: &Value, pointer: &str, pointers: &mut Vec<String>) {
+    for (index, parameter) in items(path_item.get("parameters")) {
+        walk_parameter(parameter, format!("{}/parameters/{}", pointer, index), pointers);
+    }
+    for (method, operation) in entries(Some(path_item)) {
+        if !HTTP_METHODS.contains(&method.as_str()) {
+            continue;
+        }
+        let pointer = format!("{}/{}", pointer, method);
+        for (index, parameter) in items(operation.get("parameters")) {
+            walk_parameter(parameter, format!("{}/parameters/{}", pointer, index), pointers);
+        }
+        if let Some(body) = operation.get("requestBody") {
+            walk_content(body, &format!("{}/requestBody", pointer), pointers);
+        }
+        for (status, response) in entries(operation.get("responses")) {
+            let pointer = format!("{}/responses/{}", pointer, escape_pointer(status));
+            walk_response(response, &pointer, pointers);
+        }
+    }
+}
+
+/// The schemas of a response's media types and headers.
+fn walk_response(response: &Value, pointer: &str, pointers: &mut Vec<String>) {
+    walk_content(response, pointer, pointers);
+    for (name, header) in entries(response.get("headers")) {
+        let pointer = format!("{}/headers/{}", pointer, escape_pointer(name));
+        walk_parameter(header, pointer, pointers);
+    }
+}
+
+/// The schema of a parameter or header, given directly or through its media types.
+fn walk_parameter(parameter: &Value, pointer: String, pointers: &mut Vec<String>) {
+    if let Some(schema) = parameter.get("schema") {
+        walk_schema(schema, format!("{}/schema", pointer), pointers);
+    }
+    walk_content(parameter, &pointer, pointers);
+}
+
+/// The schemas of the media types under `content` of `value`.
+fn walk_content(value: &Value, pointer: &str, pointers: &mut Vec<String>) {
+    for (media_type, media) in entries(value.get("content")) {
+        if let Some(schema) = media.get("schema") {
+            let pointer = format!("{}/content/{}/schema", pointer, escape_pointer(media_type));
+            walk_schema(schema, pointer, pointers);
+        }
+    }
+}
+
+/// `schema` itself, if it is an object, and the schemas it contains.
+fn walk_schema(schema: &Value, pointer: String, pointers: &mut Vec<String>) {
+    let Some(map) = schema.as_object() else {
+        return;
+    };
+    for (key, child) in map {
+        match key.as_str() {
+            "properties" | "patternProperties" => {
+                for (name, property) in entries(Some(child)) {
+                    let pointer = format!("{}/{}/{}", pointer, key, escape_pointer(name));
+                    walk_schema(property, pointer, pointers);
+                }
+            }
+            "items" | "additionalProperties" | "not" => {
+                walk_schema(child, format!("{}/{}", pointer, key), pointers);
+            }
+            "allOf" | "oneOf" | "anyOf" | "prefixItems" => {
+                for (index, member) in items(Some(child)) {
+                    walk_schema(member, format!("{}/{}/{}", pointer, key, index), pointers);
+                }
+            }
+            _ => {}
+        }
+    }
+    pointers.push(pointer);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_schema_pointers() {
+        let document = json!({
+            "paths": {
+                "/users/{id}": {
+                    "parameters": [
+                        { "name": "id", "in": "path", "schema": { "type": "integer" } }
+                    ],
+                    "get": {
+                        "responses": {
+                            "200": {
+                                "content": {
+                                    "application/json": {
+                                        "schema": {
+                                            "type": "array",
+                                            "items": { "type": "string" }
+                                        },
+                                        "example": { "format": "not-a-schema" }
+                                    }
+                                }
+                            }
+                        }
+                    },
+                    "x-internal": { "type": "object" }
+                }
+            },
+            "components": {
+                "schemas": {
+                    "User": {
+                        "type": "object",
+                        "properties": { "pets": { "oneOf": [{ "type": "string" }] } },
+                        "example": { "type": "object" },
+                        "x-meta": { "properties": { "a": {} } }
+                    }
+                }
+            }
+        });
+
+        let mut pointers = schema_pointers(&document);
+        pointers.sort();
+        assert_eq!(
+            pointers,
+            [
+                "/components/schemas/User",
+                "/components/schemas/User/properties/pets",
+                "/components/schemas/User/properties/pets/oneOf/0",
+                "/paths/~1users~1{id}/get/responses/200/content/application~1json/schema",
+                "/paths/~1users~1{id}/get/responses/200/content/application~1json/schema/items",
+                "/paths/~1users~1{id}/parameters/0/schema",
+            ]
+        );
+    }
+}
@@ -0,0 +1,731 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use anyhow::Result;
+use serde_json::{json, Map, Value};
+
+/// HTTP methods a Swagger 2.0 path item may define.
+const METHODS: &[&str] = &["get", "put", "post", "delete", "options", "head", "patch"];
+
+/// Parameter fields that move under `schema` in OpenAPI 3.x.
+const SCHEMA_FIELDS: &[&str] = &[
+    "type",
+    "format",
+    "items",
+    "enum",
+    "default",
+    "minimum",
+    "maximum",
+    "exclusiveMinimum",
+    "exclusiveMaximum",
+    "minLength",
+    "maxLength",
+    "pattern",
+    "minItems",
+    "maxItems",
+    "uniqueItems",
+    "multipleOf",
+];
+
+/// Returns `true` if the document declares `swagger: "2.0"` at its root.
+pub fn is_swagger2(document: &Value) -> bool {
+    document
+        .get("swagger")
+        .and_then(|v| v.as_str())
+        .is_some_and(|v| v.starts_with("2."))
+}
+
+/// Converts a Swagger 2.0 document into an equivalent OpenAPI 3.0 document.
+///
+/// Covers what code generation relies on: `info`, `host`/`basePath`/`schemes` into
+/// `servers`, `definitions` into `components/schemas`, body and form parameters into
+/// `requestBody`, response schemas into `content`, and `$ref` targets. Anything
+/// else Swagger-specific is dropped rather than guessed at.
+pub fn convert_swagger2(document: &Value) -> Result<Value> {
+    let root = document
+        .as_object()
+        .ok_or_else(|| anyhow::anyhow!("Swagger 2.0 document must be an object"))?;
+
+    let consumes = media_types(root.get("consumes"));
+    let produces = media_types(root.get("produces"));
+
+    let mut converted = Map::new();
+    converted.insert("openapi".to_string(), json!("3.0.3"));
+
+    for key in ["info", "tags", "externalDocs", "security"] {
+        if let Some(value) = root.get(key) {
+            converted.insert(key.to_string(), value.clone());
+        }
+    }
+    copy_extensions(root, &mut converted);
+
+    let servers = convert_servers(root);
+    if !servers.is_empty() {
+        converted.insert("servers".to_string(), Value::Array(servers));
+    }
+
+    let mut paths = Map::new();
+    if let Some(source_paths) = root.get("paths").and_then(|v| v.as_object()) {
+        for (path, path_item) in source_paths {
+            paths.insert(
+                path.clone(),
+                convert_path_item(root, path_item, &consumes, &produces),
+            );
+        }
+    }
+    converted.insert("paths".to_string(), Value::Object(paths));
+
+    let components = convert_components(root, &produces);
+    if !components.is_empty() {
+        converted.insert("components".to_string(), Value::Object(components));
+    }
+
+    let mut converted = Value::Object(converted);
+    rewrite_refs(&mut converted);
+    Ok(converted)
+}
+
+/// Reads a `consumes`/`produces` list, defaulting to `application/json`.
+fn media_types(value: Option<&Value>) -> Vec<String> {
+    let types: Vec<String> = value
+        .and_then(|v| v.as_array())
+        .map(|list| {
+            list.iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if types.is_empty() {
+        vec!["application/json".to_string()]
+    } else {
+        types
+    }
+}
+
+fn copy_extensions(source: &Map<String, Value>, target: &mut Map<String, Value>) {
+    for (key, value) in source {
+        if key.starts_with("x-") {
+            target.insert(key.clone(), value.clone());
+        }
+    }
+}
+
+/// Builds `servers` from `host`, `basePath`, and `schemes`.
+fn convert_servers(root: &Map<String, Value>) -> Vec<Value> {
+    let base_path = root.get("basePath").and_then(|v| v.as_str()).unwrap_or("");
+
+    let Some(host) = root.get("host").and_then(|v| v.as_str()) else {
+        return if base_path.is_empty() {
+            Vec::new()
+        } else {
+            vec![json!({ "url": base_path })]
+        };
+    };
+
+    let schemes: Vec<&str> = root
+        .get("schemes")
+        .and_then(|v| v.as_array())
+        .map(|list| list.iter().filter_map(|v| v.as_str()).collect())
+        .filter(|list: &Vec<&str>| !list.is_empty())
+        .unwrap_or_else(|| vec!["https"]);
+
+    schemes
+        .iter()
+        .map(|scheme| json!({ "url": format!("{}://{}{}", scheme, host, base_path) }))
+        .collect()
+}
+
+fn convert_path_item(
+    root: &Map<String, Value>,
+    path_item: &Value,
+    consumes: &[String],
+    produces: &[String],
+) -> Value {
+    let Some(item) = path_item.as_object() else {
+        return path_item.clone();
+    };
+
+    let shared_params: Vec<Value> = item
+        .get("parameters")
+        .and_then(|v| v.as_array())
+        .map(|params| params.iter().map(|p| resolve_parameter(root, p)).collect())
+        .unwrap_or_default();
+
+    let mut converted = Map::new();
+
+    // Body and form parameters cannot live on the path item in 3.x, so only the
+    // plain ones stay here; the rest are pushed down into each operation.
+    let plain_shared: Vec<Value> = shared_params
+        .iter()
+        .filter(|p| !is_body_or_form(p))
+        .map(convert_parameter)
+        .collect();
+    if !plain_shared.is_empty() {
+        converted.insert("parameters".to_string(), Value::Array(plain_shared));
+    }
+    let inherited: Vec<Value> = shared_params
+        .into_iter()
+        .filter(is_body_or_form)
+        .collect();
+
+    for (key, value) in item {
+        if METHODS.contains(&key.as_str()) {
+            converted.insert(
+                key.clone(),
+                convert_operation(root, value, &inherited, consumes, produces),
+            );
+        } else if key.starts_with("x-") || key == "$ref" {
+            converted.insert(key.clone(), value.clone());
+        }
+    }
+
+    Value::Object(converted)
+}
+
+fn convert_operation(
+    root: &Map<String, Value>,
+    operation: &Value,
+    inherited: &[Value],
+    consumes: &[String],
+    produces: &[String],
+) -> Value {
+    let Some(op) = operation.as_object() else {
+        return operation.clone();
+    };
+
+    let consumes = op
+        .get("consumes")
+        .map(|v| media_types(Some(v)))
+        .unwrap_or_else(|| consumes.to_vec());
+    let produces = op
+        .get("produces")
+        .map(|v| media_types(Some(v)))
+        .unwrap_or_else(|| produces.to_vec());
+
+    let mut converted = Map::new();
+    for key in [
+        "tags",
+        "summary",
+        "description",
+        "externalDocs",
+        "operationId",
+        "deprecated",
+        "security",
+    ] {
+        if let Some(value) = op.get(key) {
+            converted.insert(key.to_string(), value.clone());
+        }
+    }
+    copy_extensions(op, &mut converted);
+
+    let params: Vec<Value> = inherited
+        .iter()
+        .cloned()
+        .chain(
+            op.get("parameters")
+                .and_then(|v| v.as_array())
+                .into_iter()
+                .flatten()
+                .map(|p| resolve_parameter(root, p)),
+        )
+        .collect();
+
+    let plain: Vec<Value> = params
+        .iter()
+        .filter(|p| !is_body_or_form(p))
+        .map(convert_parameter)
+        .collect();
+    if !plain.is_empty() {
+        converted.insert("parameters".to_string(), Value::Array(plain));
+    }
+
+    if let Some(request_body) = convert_request_body(&params, &consumes) {
+        converted.insert("requestBody".to_string(), request_body);
+    }
+
+    let mut responses = Map::new();
+    if let Some(source) = op.get("responses").and_then(|v| v.as_object()) {
+        for (status, response) in source {
+            responses.insert(status.clone(), convert_response(response, &produces));
+        }
+    }
+    converted.insert("responses".to_string(), Value::Object(responses));
+
+    Value::Object(converted)
+}
+
+/// Inlines a `#/parameters/...` reference when it points at a body or form
+/// parameter, since those become part of `requestBody` rather than `parameters`.
+fn resolve_parameter(root: &Map<String, Value>, param: &Value) -> Value {
+    let target = param
+        .get("$ref")
+        .and_then(|v| v.as_str())
+        .and_then(|r| r.strip_prefix("#/parameters/"))
+        .and_then(|name| root.get("parameters")?.get(name));
+
+    match target {
+        Some(target) if is_body_or_form(target) => target.clone(),
+        _ => param.clone(),
+    }
+}
+
+fn is_body_or_form(param: &Value) -> bool {
+    matches!(
+        param.get("in").and_then(|v| v.as_str()),
+        Some("body") | Some("formData")
+    )
+}
+
+/// Converts a non-body parameter, moving its type information under `schema`.
+fn convert_parameter(param: &Value) -> Value {
+    let Some(source) = param.as_object() else {
+        return param.clone();
+    };
+    if source.contains_key("$ref") {
+        return param.clone();
+    }
+
+    let mut converted = Map::new();
+    let mut schema = Map::new();
+
+    for (key, value) in source {
+        if SCHEMA_FIELDS.contains(&key.as_str()) {
+            schema.insert(key.clone(), value.clone());
+        } else if key == "collectionFormat" {
+            match value.as_str() {
+                Some("multi") => {
+                    converted.insert("style".to_string(), json!("form"));
+                    converted.insert("explode".to_string(), json!(true));
+                }
+                Some("csv") => {
+                    converted.insert("explode".to_string(), json!(false));
+                }
+                _ => {}
+            }
+        } else {
+            converted.insert(key.clone(), value.clone());
+        }
+    }
+
+    if !schema.is_empty() {
+        converted.insert("schema".to_string(), convert_schema(Value::Object(schema)));
+    }
+
+    Value::Object(converted)
+}
+
+/// Folds `in: body` or `in: formData` parameters into a 3.x `requestBody`.
+fn convert_request_body(params: &[Value], consumes: &[String]) -> Option<Value> {
+    if let Some(body) = params
+        .iter()
+        .find(|p| p.get("in").and_then(|v| v.as_str()) == Some("body"))
+    {
+        let schema = convert_schema(body.get("schema").cloned().unwrap_or(json!({})));
+        let content: Map<String, Value> = consumes
+            .iter()
+            .map(|media| (media.clone(), json!({ "schema": schema })))
+            .collect();
+
+        let mut request_body = Map::new();
+        if let Some(description) = body.get("description") {
+            request_body.insert("description".to_string(), description.clone());
+        }
+        request_body.insert("content".to_string(), Value::Object(content));
+        if let Some(required) = body.get("required") {
+            request_body.insert("required".to_string(), required.clone());
+        }
+        return Some(Value::Object(request_body));
+    }
+
+    let form_params: Vec<&Value> = params
+        .iter()
+        .filter(|p| p.get("in").and_then(|v| v.as_str()) == Some("formData"))
+        .collect();
+    if form_params.is_empty() {
+        return None;
+    }
+
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    let mut has_file = false;
+    for param in &form_params {
+        let Some(name) = param.get("name").and_then(|v| v.as_str()) else {
+            continue;
+        };
+        has_file |= param.get("type").and_then(|v| v.as_str()) == Some("file");
+        if param.get("required").and_then(|v| v.as_bool()) == Some(true) {
+            required.push(json!(name));
+        }
+
+        let mut schema = Map::new();
+        for key in SCHEMA_FIELDS.iter().chain(["description"].iter()) {
+            if let Some(value) = param.get(*key) {
+                schema.insert(key.to_string(), value.clone());
+            }
+        }
+        properties.insert(name.to_string(), convert_schema(Value::Object(schema)));
+    }
+
+    let mut schema = json!({ "type": "object", "properties": properties });
+    if !required.is_empty() {
+        schema["required"] = Value::Array(required);
+    }
+
+    let media_type = if has_file || consumes.iter().any(|c| c == "multipart/form-data") {
+        "multipart/form-data"
+    } else {
+        "application/x-www-form-urlencoded"
+    };
+
+    Some(json!({ "content": { media_type: { "schema": schema } } }))
+}
+
+fn convert_response(response: &Value, produces: &[String]) -> Value {
+    let Some(source) = response.as_object() else {
+        return response.clone();
+    };
+    if source.contains_key("$ref") {
+        return response.clone();
+    }
+
+    let mut converted = Map::new();
+    converted.insert(
+        "description".to_string(),
+        source.get("description").cloned().unwrap_or(json!("")),
+    );
+    copy_extensions(source, &mut converted);
+
+    if let Some(schema) = source.get("schema") {
+        let schema = convert_schema(schema.clone());
+        let content: Map<String, Value> = produces
+            .iter()
+            .map(|media| {
+                let mut media_type = json!({ "schema": schema });
+                if let Some(example) = source.get("examples").and_then(|e| e.get(media)) {
+                    media_type["example"] = example.clone();
+                }
+                (media.clone(), media_type)
+            })
+            .collect();
+        converted.insert("content".to_string(), Value::Object(content));
+    }
+
+    if let Some(headers) = source.get("headers").and_then(|v| v.as_object()) {
+        let headers: Map<String, Value> = headers
+            .iter()
+            .map(|(name, header)| {
+                let mut schema = Map::new();
+                let mut converted_header = Map::new();
+                for (key, value) in header.as_object().into_iter().flatten() {
+                    if SCHEMA_FIELDS.contains(&key.as_str()) {
+                        schema.insert(key.clone(), value.clone());
+                    } else if key != "collectionFormat" {
+                        converted_header.insert(key.clone(), value.clone());
+                    }
+                }
+                converted_header.insert("schema".to_string(), Value::Object(schema));
+                (name.clone(), Value::Object(converted_header))
+            })
+            .collect();
+        converted.insert("headers".to_string(), Value::Object(headers));
+    }
+
+    Value::Object(converted)
+}
+
+/// Moves root-level reusable objects under `components`.
+fn convert_components(root: &Map<String, Value>, produces: &[String]) -> Map<String, Value> {
+    let mut components = Map::new();
+
+    if let Some(definitions) = root.get("definitions").and_then(|v| v.as_object()) {
+        let schemas: Map<String, Value> = definitions
+            .iter()
+            .map(|(name, schema)| (name.clone(), convert_schema(schema.clone())))
+            .collect();
+        components.insert("schemas".to_string(), Value::Object(schemas));
+    }
+
+    if let Some(parameters) = root.get("parameters").and_then(|v| v.as_object()) {
+        let parameters: Map<String, Value> = parameters
+            .iter()
+            .filter(|(_, param)| !is_body_or_form(param))
+            .map(|(name, param)| (name.clone(), convert_parameter(param)))
+            .collect();
+        if !parameters.is_empty() {
+            components.insert("parameters".to_string(), Value::Object(parameters));
+        }
+    }
+
+    if let Some(responses) = root.get("responses").and_then(|v| v.as_object()) {
+        let responses: Map<String, Value> = responses
+            .iter()
+            .map(|(name, response)| (name.clone(), convert_response(response, produces)))
+            .collect();
+        components.insert("responses".to_string(), Value::Object(responses));
+    }
+
+    if let Some(schemes) = root.get("securityDefinitions").and_then(|v| v.as_object()) {
+        let schemes: Map<String, Value> = schemes
+            .iter()
+            .map(|(name, scheme)| (name.clone(), convert_security_scheme(scheme)))
+            .collect();
+        components.insert("securitySchemes".to_string(), Value::Object(schemes));
+    }
+
+    components
+}
+
+fn convert_security_scheme(scheme: &Value) -> Value {
+    match scheme.get("type").and_then(|v| v.as_str()) {
+        Some("basic") => json!({ "type": "http", "scheme": "basic" }),
+        Some("oauth2") => {
+            let flow = match scheme.get("flow").and_then(|v| v.as_str()) {
+                Some("implicit") => "implicit",
+                Some("password") => "password",
+                Some("application") => "clientCredentials",
+                _ => "authorizationCode",
+            };
+
+            let mut flow_object = Map::new();
+            for key in ["authorizationUrl", "tokenUrl"] {
+                if let Some(value) = scheme.get(key) {
+                    flow_object.insert(key.to_string(), value.clone());
+                }
+            }
+            flow_object.insert(
+                "scopes".to_string(),
+                scheme.get("scopes").cloned().unwrap_or(json!({})),
+            );
+
+            json!({ "type": "oauth2", "flows": { flow: flow_object } })
+        }
+        _ => scheme.clone(),
+    }
+}
+
+/// Applies the schema-level differences between Swagger 2.0 and OpenAPI 3.0.
+fn convert_schema(mut schema: Value) -> Value {
+    match &mut schema {
+        Value::Object(map) => {
+            if map.get("type").and_then(|v| v.as_str()) == Some("file") {
+                map.insert("type".to_string(), json!("string"));
+                map.insert("format".to_string(), json!("binary"));
+            }
+            if let Some(nullable) = map.remove("x-nullable") {
+                map.insert("nullable".to_string(), nullable);
+            }
+            if let Some(Value::String(property)) = map.get("discriminator") {
+                let property = property.clone();
+                map.insert(
+                    "discriminator".to_string(),
+                    json!({ "propertyName": property }),
+                );
+            }
+
+            for (key, value) in map.iter_mut() {
+                // Property maps hold schemas keyed by name; everything else that can
+                // contain schemas is either a schema or an array of schemas.
+                match key.as_str() {
+                    "properties" | "patternProperties" => {
+                        if let Some(props) = value.as_object_mut() {
+                            for prop in props.values_mut() {
+                                *prop = convert_schema(prop.take());
+                            }
+                        }
+                    }
+                    "items" | "additionalProperties" | "not" => {
+                        *value = convert_schema(value.take());
+                    }
+                    "allOf" | "anyOf" | "oneOf" => {
+                        if let Some(list) = value.as_array_mut() {
+                            for item in list.iter_mut() {
+                                *item = convert_schema(item.take());
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            schema
+        }
+        _ => schema,
+    }
+}
+
+/// Points every Swagger 2.0 `$ref` at its new location under `components`.
+fn rewrite_refs(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                if key == "$ref"
+                    && let Value::String(reference) = child
+                {
+                    for (from, to) in [
+                        ("#/definitions/", "#/components/schemas/"),
+                        ("#/parameters/", "#/components/parameters/"),
+                        ("#/responses/", "#/components/responses/"),
+                    ] {
+                        if let Some(name) = reference.strip_prefix(from) {
+                            *reference = format!("{}{}", to, name);
+                            break;
+                        }
+                    }
+                } else {
+                    rewrite_refs(child);
+                }
+            }
+        }
+        Value::Array(list) => list.iter_mut().for_each(rewrite_refs),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn minimal_swagger2() -> Value {
+        json!({
+            "swagger": "2.0",
+            "info": { "title": "Legacy API", "version": "1.2.3" },
+            "host": "api.example.com",
+            "basePath": "/v1",
+            "schemes": ["https"],
+            "paths": {
+                "/pets/{id}": {
+                    "parameters": [
+                        { "name": "id", "in": "path", "required": true, "type": "integer", "format": "int64" }
+                    ],
+                    "get": {
+                        "operationId": "getPet",
+                        "parameters": [
+                            { "name": "verbose", "in": "query", "type": "boolean", "default": false }
+                        ],
+                        "responses": {
+                            "200": { "description": "OK", "schema": { "$ref": "#/definitions/Pet" } },
+                            "404": { "description": "Not found" }
+                        }
+                    },
+                    "put": {
+                        "parameters": [
+                            { "name": "pet", "in": "body", "required": true, "schema": { "$ref": "#/definitions/Pet" } }
+                        ],
+                        "responses": { "204": { "description": "Updated" } }
+                    }
+                }
+            },
+            "definitions": {
+                "Pet": {
+                    "type": "object",
+                    "required": ["name"],
+                    "properties": {
+                        "name": { "type": "string" },
+                        "owner": { "$ref": "#/definitions/Owner" },
+                        "nickname": { "type": "string", "x-nullable": true }
+                    }
+                },
+                "Owner": { "type": "object", "properties": { "id": { "type": "integer" } } }
+            }
+        })
+    }
+
+    #[test]
+    fn test_is_swagger2() {
+        assert!(is_swagger2(&json!({ "swagger": "2.0" })));
+        assert!(!is_swagger2(&json!({ "openapi": "3.1.0" })));
+        assert!(!is_swagger2(&json!({})));
+    }
+
+    #[test]
+    fn test_convert_swagger2_root() {
+        let converted = convert_swagger2(&minimal_swagger2()).unwrap();
+
+        assert_eq!(converted["openapi"], "3.0.3");
+        assert_eq!(converted["info"]["title"], "Legacy API");
+        assert_eq!(converted["servers"][0]["url"], "https://api.example.com/v1");
+        assert!(converted.get("swagger").is_none());
+        assert!(converted.get("definitions").is_none());
+    }
+
+    #[test]
+    fn test_convert_swagger2_definitions_to_components() {
+        let converted = convert_swagger2(&minimal_swagger2()).unwrap();
+        let pet = &converted["components"]["schemas"]["Pet"];
+
+        assert_eq!(
+            pet["properties"]["owner"]["$ref"],
+            "#/components/schemas/Owner"
+        );
+        assert_eq!(pet["properties"]["nickname"]["nullable"], true);
+        assert!(converted["components"]["schemas"]["Owner"].is_object());
+    }
+
+    #[test]
+    fn test_convert_swagger2_parameters_and_body() {
+        let converted = convert_swagger2(&minimal_swagger2()).unwrap();
+        let path_item = &converted["paths"]["/pets/{id}"];
+
+        // Path-level parameter keeps its place, with type info moved under schema
+        assert_eq!(path_item["parameters"][0]["schema"]["type"], "integer");
+        assert_eq!(path_item["parameters"][0]["schema"]["format"], "int64");
+
+        let get = &path_item["get"];
+        assert_eq!(get["parameters"][0]["name"], "verbose");
+        assert_eq!(get["parameters"][0]["schema"]["default"], false);
+        assert_eq!(
+            get["responses"]["200"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/Pet"
+        );
+        assert!(get["responses"]["404"].get("content").is_none());
+
+        let put = &path_item["put"];
+        assert!(put.get("parameters").is_none());
+        assert_eq!(put["requestBody"]["required"], true);
+        assert_eq!(
+            put["requestBody"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/Pet"
+        );
+    }
+
+    #[test]
+    fn test_convert_swagger2_form_data() {
+        let document = json!({
+            "swagger": "2.0",
+            "info": { "title": "Upload", "version": "1" },
+            "paths": {
+                "/upload": {
+                    "post": {
+                        "parameters": [
+                            { "name": "file", "in": "formData", "type": "file", "required": true },
+                            { "name": "note", "in": "formData", "type": "string" }
+                        ],
+                        "responses": { "200": { "description": "OK" } }
+                    }
+                }
+            }
+        });
+
+        let converted = convert_swagger2(&document).unwrap();
+        let schema =
+            &converted["paths"]["/upload"]["post"]["requestBody"]["content"]["multipart/form-data"]["schema"];
+
+        assert_eq!(schema["properties"]["file"]["type"], "string");
+        assert_eq!(schema["properties"]["file"]["format"], "binary");
+        assert_eq!(schema["required"], json!(["file"]));
+    }
+
+    #[test]
+    fn test_convert_swagger2_servers_without_host() {
+        let document = json!({
+            "swagger": "2.0",
+            "info": { "title": "T", "version": "1" },
+            "basePath": "/api",
+            "paths": {}
+        });
+
+        let converted = convert_swagger2(&document).unwrap();
+        assert_eq!(converted["servers"], json!([{ "url": "/api" }]));
+    }
+}
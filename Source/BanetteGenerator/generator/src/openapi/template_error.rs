@@ -0,0 +1,105 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use std::error::Error;
+
+/// Describes a Tera error on `template` with everything its error chain knows.
+///
+/// The message names the template and, for syntax errors, the line and column Tera
+/// reports. A missing context variable is named as well, and when the template `source`
+/// is given it is located by its first use in the template.
+pub fn describe_template_error(
+    error: &tera::Error,
+    template: &str,
+    source: Option<&str>,
+) -> String {
+    let mut causes = Vec::new();
+    let mut cause: Option<&dyn Error> = Some(error);
+    while let Some(e) = cause {
+        causes.push(e.to_string());
+        cause = e.source();
+    }
+    let key = causes.iter().find_map(|cause| missing_variable(cause));
+
+    let position = match causes.iter().find_map(|cause| parse_position(cause)) {
+        Some((line, column)) => format!(" at line {}, column {}", line, column),
+        None => match source.zip(key).and_then(|(source, key)| find_position(source, key)) {
+            Some((line, column)) => format!(" near line {}, column {}", line, column),
+            None => String::new(),
+        },
+    };
+    let key = key.map(|key| format!(" (context key `{}`)", key)).unwrap_or_default();
+
+    // The first cause only repeats the template name when there are more specific ones
+    let details = if causes.len() > 1 { &causes[1..] } else { &causes[..] };
+    format!("Template '{}'{}{}: {}", template, position, key, details.join(": "))
+}
+
+/// Extracts the variable from Tera's "Variable `x` not found in context" message.
+fn missing_variable(message: &str) -> Option<&str> {
+    let rest = message.strip_prefix("Variable `")?;
+    let (key, rest) = rest.split_once('`')?;
+    rest.starts_with(" not found in context").then_some(key)
+}
+
+/// Extracts `(line, column)` from the ` --> 3:8` marker of a Tera syntax error.
+fn parse_position(message: &str) -> Option<(usize, usize)> {
+    let (_, rest) = message.split_once("--> ")?;
+    let position = rest.split_whitespace().next()?;
+    let (line, column) = position.split_once(':')?;
+    Some((line.parse().ok()?, column.parse().ok()?))
+}
+
+/// Returns the 1-based `(line, column)` of the first occurrence of `needle` in `source`.
+fn find_position(source: &str, needle: &str) -> Option<(usize, usize)> {
+    let offset = source.find(needle)?;
+    let before = &source[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    Some((line, before[line_start..].chars().count() + 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tera::{Context, Tera};
+
+    #[test]
+    fn test_describe_syntax_error() {
+        let source = "// header\n{% for item in items %}\n{{ item | }}\n{% endfor %}";
+        let error = Tera::default().add_raw_template("custom.h.tera", source).unwrap_err();
+        let message = describe_template_error(&error, "custom.h.tera", Some(source));
+
+        assert!(message.starts_with("Template 'custom.h.tera' at line 3, column "), "{}", message);
+    }
+
+    #[test]
+    fn test_describe_missing_variable() {
+        let source = "// header\n#include \"{{ file_name }}.h\"\n{{ info.titel }}\n";
+        let mut tera = Tera::default();
+        tera.add_raw_template("custom.h.tera", source).unwrap();
+        let error = tera.render("custom.h.tera", &Context::new()).unwrap_err();
+        let message = describe_template_error(&error, "custom.h.tera", Some(source));
+
+        assert_eq!(
+            message,
+            "Template 'custom.h.tera' near line 2, column 14 (context key `file_name`): \
+             Variable `file_name` not found in context while rendering 'custom.h.tera'"
+        );
+
+        // Without the source the key is still named
+        let message = describe_template_error(&error, "custom.h.tera", None);
+        assert!(message.starts_with("Template 'custom.h.tera' (context key `file_name`): "));
+    }
+
+    #[test]
+    fn test_describe_filter_error() {
+        let mut tera = Tera::default();
+        tera.add_raw_template("custom.h.tera", "{{ 1 | upper }}").unwrap();
+        let error = tera.render("custom.h.tera", &Context::new()).unwrap_err();
+        let message = describe_template_error(&error, "custom.h.tera", None);
+
+        assert!(message.starts_with("Template 'custom.h.tera': Filter call 'upper' failed"));
+    }
+}
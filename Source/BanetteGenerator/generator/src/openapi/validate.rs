@@ -0,0 +1,249 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use std::fmt;
+
+/// Something in rendered C++ that is almost certainly a template bug.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationIssue {
+    /// 1-based line the issue was found on.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// Sanity-checks rendered C++ without parsing it.
+///
+/// Reports `{}`, `()`, and template `<>` that are not balanced, empty template argument
+/// lists such as `TArray<>`, and a bare `F` left where a type name was expected. Comments,
+/// string and character literals, and preprocessor lines are ignored. A `<` only opens a
+/// template argument list when it directly follows a name, so `a < b` is not flagged.
+pub fn validate_cpp(contents: &str) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    let mut report = |line: usize, message: String| issues.push(ValidationIssue { line, message });
+
+    let chars: Vec<char> = contents.chars().collect();
+    let mut stack: Vec<(char, usize)> = Vec::new();
+    let mut identifier = String::new();
+    let mut line = 1;
+    let mut at_line_start = true;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        if c.is_alphanumeric() || c == '_' {
+            identifier.push(c);
+            at_line_start = false;
+            i += 1;
+            continue;
+        }
+        let previous = std::mem::take(&mut identifier);
+        if previous == "F" {
+            report(line, "empty type name 'F'".to_string());
+        }
+
+        match c {
+            '\n' => {
+                line += 1;
+                at_line_start = true;
+                i += 1;
+                continue;
+            }
+            c if c.is_whitespace() => {
+                i += 1;
+                continue;
+            }
+            '#' if at_line_start => {
+                i = skip_until(&chars, i, &mut line, |chars, j| chars[j] == '\n');
+                continue;
+            }
+            '/' if next == Some('/') => {
+                i = skip_until(&chars, i, &mut line, |chars, j| chars[j] == '\n');
+                continue;
+            }
+            '/' if next == Some('*') => {
+                let end = skip_until(&chars, i + 2, &mut line, |chars, j| {
+                    chars[j] == '*' && chars.get(j + 1) == Some(&'/')
+                });
+                i = end + 2;
+                continue;
+            }
+            '"' | '\'' => {
+                i = skip_literal(&chars, i) + 1;
+                at_line_start = false;
+                continue;
+            }
+            '{' | '(' => stack.push((c, line)),
+            '<' if previous.starts_with(|c: char| c.is_alphabetic() || c == '_') => {
+                if next == Some('>') {
+                    report(line, format!("empty template argument list in '{}<>'", previous));
+                }
+                stack.push(('<', line));
+            }
+            '>' if i > 0 && chars[i - 1] == '-' => {}
+            '>' => {
+                if matches!(stack.last(), Some(('<', _))) {
+                    stack.pop();
+                }
+            }
+            '}' | ')' => {
+                let open = if c == '}' { '{' } else { '(' };
+                while let Some(&('<', opened)) = stack.last() {
+                    report(line, format!("unclosed '<' opened on line {}", opened));
+                    stack.pop();
+                }
+                match stack.pop() {
+                    Some((top, _)) if top == open => {}
+                    Some((top, opened)) => report(
+                        line,
+                        format!("'{}' closes '{}' opened on line {}", c, top, opened),
+                    ),
+                    None => report(line, format!("unmatched '{}'", c)),
+                }
+            }
+            ';' => {
+                while let Some(&('<', opened)) = stack.last() {
+                    report(line, format!("unclosed '<' opened on line {}", opened));
+                    stack.pop();
+                }
+            }
+            _ => {}
+        }
+
+        at_line_start = false;
+        i += 1;
+    }
+
+    if identifier == "F" {
+        report(line, "empty type name 'F'".to_string());
+    }
+    for (open, opened) in stack {
+        report(opened, format!("unclosed '{}'", open));
+    }
+
+    issues
+}
+
+/// Advances from `start` to the first index where `stop` holds (or the end), counting
+/// the newlines passed.
+fn skip_until(
+    chars: &[char],
+    start: usize,
+    line: &mut usize,
+    stop: impl Fn(&[char], usize) -> bool,
+) -> usize {
+    let mut j = start;
+    while j < chars.len() && !stop(chars, j) {
+        if chars[j] == '\n' {
+            *line += 1;
+        }
+        j += 1;
+    }
+    j
+}
+
+/// Returns the index of the quote closing the literal opened at `start`, honoring
+/// backslash escapes. An unterminated literal ends at the end of its line.
+fn skip_literal(chars: &[char], start: usize) -> usize {
+    let quote = chars[start];
+    let mut j = start + 1;
+    while j < chars.len() {
+        match chars[j] {
+            '\\' => j += 1,
+            '\n' => return j - 1,
+            c if c == quote => return j,
+            _ => {}
+        }
+        j += 1;
+    }
+    j
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(contents: &str) -> Vec<String> {
+        validate_cpp(contents).iter().map(|issue| issue.to_string()).collect()
+    }
+
+    #[test]
+    fn test_validate_cpp_accepts_generated_code() {
+        let contents = r#"#pragma once
+#include <vector>
+#include "Api.generated.h"
+
+/**
+ * Summary: Don't { break (the <checker>
+ */
+USTRUCT(BlueprintType)
+struct FUser
+{
+    GENERATED_BODY()
+    // name (Required: true) }
+    UPROPERTY(EditAnywhere, meta=(ClampMin="0"))
+    TMap<FString, TArray<int32>> Scores;
+    FString Brace = TEXT("}{)(<");
+    TCHAR Quote = '\'';
+};
+
+FVoidCoroutine Call()
+{
+    const auto Url = GetDefault<USettings>()->BaseUrl + TEXT("/users");
+    if (Count < Limit && Count > 0)
+    {
+        co_return;
+    }
+}
+"#;
+        assert_eq!(messages(contents), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_validate_cpp_unbalanced_braces() {
+        assert_eq!(messages("struct FUser\n{\n    int32 Age;\n"), vec!["line 2: unclosed '{'"]);
+        assert_eq!(messages("void Call()\n{\n}\n}\n"), vec!["line 4: unmatched '}'"]);
+        assert_eq!(messages("void Call(\n{\n}\n"), vec!["line 1: unclosed '('"]);
+        assert_eq!(
+            messages("void Call(\n{\n)\n"),
+            vec!["line 3: ')' closes '{' opened on line 2", "line 1: unclosed '('"]
+        );
+    }
+
+    #[test]
+    fn test_validate_cpp_unbalanced_angle_brackets() {
+        assert_eq!(
+            messages("struct FUser\n{\n    TArray<FString Names;\n};\n"),
+            vec!["line 3: unclosed '<' opened on line 3"]
+        );
+        assert_eq!(
+            messages("void Call(TMap<FString, int32 Map)\n{\n}\n"),
+            vec!["line 1: unclosed '<' opened on line 1"]
+        );
+    }
+
+    #[test]
+    fn test_validate_cpp_suspicious_tokens() {
+        assert_eq!(
+            messages("struct FUser\n{\n    TArray<> Items;\n    F Owner;\n};\n"),
+            vec![
+                "line 3: empty template argument list in 'TArray<>'",
+                "line 4: empty type name 'F'"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_cpp_ignores_comments_and_literals() {
+        let contents = "// TArray<> {\n/* F ( */\nconst TCHAR* S = TEXT(\"F <> {\");\n";
+        assert_eq!(messages(contents), Vec::<String>::new());
+    }
+}
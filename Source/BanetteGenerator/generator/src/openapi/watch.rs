@@ -0,0 +1,218 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use super::loader::{fetch_if_modified, file_uri_path, LoadOptions};
+use crate::logging;
+use anyhow::{Context, Result};
+use notify::{RecursiveMode, Watcher};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Quiet period after the last change before regenerating.
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(300);
+/// How often a spec URL is polled for changes.
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// How often `stop` is checked while waiting.
+const STOP_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone)]
+pub struct WatchOptions {
+    pub debounce: Duration,
+    pub poll_interval: Duration,
+}
+
+impl Default for WatchOptions {
+    fn default() -> Self {
+        Self {
+            debounce: DEFAULT_DEBOUNCE,
+            poll_interval: DEFAULT_POLL_INTERVAL,
+        }
+    }
+}
+
+/// Calls `on_change` each time the spec at `path` changes, until `stop` is set.
+///
+/// Local files, given as paths or `file://` URIs, are watched through filesystem events,
+/// and a burst of events is reported once `options.debounce` has passed without another.
+/// URLs are polled every `options.poll_interval` with `If-None-Match`; fetch errors are
+/// logged and retried on the next poll. A changed download is stored in the spec cache
+/// (if any) so that `on_change` loads it without downloading it again.
+pub fn watch(
+    path: &str,
+    load: &LoadOptions,
+    options: &WatchOptions,
+    on_change: impl FnMut(),
+    stop: &AtomicBool,
+) -> Result<()> {
+    if path.starts_with("http://") || path.starts_with("https://") {
+        poll_url(path, load, options, on_change, stop);
+        Ok(())
+    } else if let Some(file_path) = file_uri_path(path) {
+        watch_file(Path::new(&file_path), options, on_change, stop)
+    } else {
+        watch_file(Path::new(path), options, on_change, stop)
+    }
+}
+
+fn watch_file(
+    path: &Path,
+    options: &WatchOptions,
+    mut on_change: impl FnMut(),
+    stop: &AtomicBool,
+) -> Result<()> {
+    let path = path
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {}", path.display()))?;
+    let (sender, receiver) = mpsc::channel();
+
+    // Editors often save by replacing the file, so the directory is watched rather
+    // than the file itself.
+    let mut watcher = notify::recommended_watcher(move |event| {
+        sender.send(event).ok();
+    })
+    .context("Failed to create file watcher")?;
+    let dir = path.parent().unwrap_or(Path::new("."));
+    watcher
+        .watch(dir, RecursiveMode::NonRecursive)
+        .with_context(|| format!("Failed to watch {}", dir.display()))?;
+
+    let touches_spec = |event: &notify::Result<notify::Event>| match event {
+        Ok(event) => event.paths.iter().any(|p| same_file(p, &path)),
+        Err(_) => false,
+    };
+
+    let mut pending_since: Option<Instant> = None;
+    while !stop.load(Ordering::Relaxed) {
+        let timeout = match pending_since {
+            Some(last) => options.debounce.saturating_sub(last.elapsed()),
+            None => STOP_CHECK_INTERVAL,
+        };
+
+        match receiver.recv_timeout(timeout.min(STOP_CHECK_INTERVAL)) {
+            Ok(event) if touches_spec(&event) => pending_since = Some(Instant::now()),
+            Ok(_) => {}
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                if pending_since.is_some_and(|last| last.elapsed() >= options.debounce) {
+                    pending_since = None;
+                    on_change();
+                }
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+        }
+    }
+
+    Ok(())
+}
+
+fn same_file(candidate: &Path, spec: &Path) -> bool {
+    candidate == spec
+        || candidate
+            .canonicalize()
+            .map(|c| c == spec)
+            .unwrap_or_else(|_| candidate.file_name() == spec.file_name())
+}
+
+fn poll_url(
+    url: &str,
+    load: &LoadOptions,
+    options: &WatchOptions,
+    mut on_change: impl FnMut(),
+    stop: &AtomicBool,
+) {
+    let mut etag: Option<String> = None;
+    let mut last_body: Option<String> = None;
+
+    while !stop.load(Ordering::Relaxed) {
+        match fetch_if_modified(url, load, etag.as_deref()) {
+            Ok(Some((body, new_etag))) => {
+                etag = new_etag;
+                // The first download only records the baseline; servers without ETags
+                // are compared by body.
+                if last_body.as_ref().is_some_and(|last| *last != body) {
+                    if let Some(cache) = &load.cache {
+                        cache.store(url, &load.headers, &body).ok();
+                    }
+                    last_body = Some(body);
+                    on_change();
+                } else {
+                    last_body = Some(body);
+                }
+            }
+            Ok(None) => {}
+            Err(e) => logging::warning(&format!("Failed to poll {}: {:#}", url, e)),
+        }
+
+        sleep_unless_stopped(options.poll_interval, stop);
+    }
+}
+
+fn sleep_unless_stopped(duration: Duration, stop: &AtomicBool) {
+    let deadline = Instant::now() + duration;
+    while !stop.load(Ordering::Relaxed) {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            break;
+        }
+        thread::sleep(remaining.min(STOP_CHECK_INTERVAL));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_watch_file_debounces_changes() {
+        let dir = std::env::temp_dir().join("banette_watch_file");
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir).unwrap();
+        let spec_path = dir.join("spec.json");
+        fs::write(&spec_path, "{}").unwrap();
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let changes = Arc::new(AtomicUsize::new(0));
+        let options = WatchOptions {
+            debounce: Duration::from_millis(200),
+            ..Default::default()
+        };
+
+        let watcher = {
+            let (stop, changes) = (Arc::clone(&stop), Arc::clone(&changes));
+            let path = spec_path.to_str().unwrap().to_string();
+            thread::spawn(move || {
+                watch(
+                    &path,
+                    &LoadOptions::default(),
+                    &options,
+                    || {
+                        changes.fetch_add(1, Ordering::SeqCst);
+                    },
+                    &stop,
+                )
+            })
+        };
+        thread::sleep(Duration::from_millis(300));
+
+        for i in 0..3 {
+            fs::write(&spec_path, format!("{{\"v\": {}}}", i)).unwrap();
+            thread::sleep(Duration::from_millis(20));
+        }
+        // Unrelated files in the same directory are ignored
+        fs::write(dir.join("other.json"), "{}").unwrap();
+        thread::sleep(Duration::from_millis(800));
+
+        stop.store(true, Ordering::Relaxed);
+        watcher.join().unwrap().unwrap();
+        assert_eq!(changes.load(Ordering::SeqCst), 1);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
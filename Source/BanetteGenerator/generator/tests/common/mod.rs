@@ -0,0 +1,39 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+pub const SPEC: &str = r#"{
+  "openapi": "3.0.0",
+  "info": { "title": "Exit Codes", "version": "1.0.0" },
+  "paths": {
+    "/health": {
+      "get": {
+        "operationId": "getHealth",
+        "responses": { "200": { "description": "OK" } }
+      }
+    }
+  },
+  "components": { "schemas": {} }
+}"#;
+
+/// Writes `spec` as `spec.json` to a fresh temp directory and returns the directory.
+pub fn spec_dir(dir_name: &str, spec: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(dir_name);
+    fs::remove_dir_all(&dir).ok();
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("spec.json"), spec).unwrap();
+    dir
+}
+
+/// Runs the generator binary in `dir` with `args`.
+pub fn run_generator(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_generator"))
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .unwrap()
+}
@@ -0,0 +1,48 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+mod common;
+
+use common::{run_generator, spec_dir, SPEC};
+use std::path::Path;
+
+/// Runs the generator on `spec` into `dir/out` and returns its exit code.
+fn exit_code(dir: &Path, spec: &str, extra_args: &[&str]) -> i32 {
+    let mut args = vec!["--path", spec, "--output-dir", "out", "--file-name", "Api.h"];
+    args.extend(["--module-name", "TESTMODULE_API", "--quiet"]);
+    args.extend(extra_args);
+    run_generator(dir, &args).status.code().unwrap()
+}
+
+#[test]
+fn test_exit_code_success() {
+    let dir = spec_dir("banette_exit_code_success", SPEC);
+
+    assert_eq!(exit_code(&dir, "spec.json", &[]), 0);
+    assert_eq!(exit_code(&dir, "spec.json", &["--dry-run"]), 0);
+}
+
+#[test]
+fn test_exit_code_missing_spec() {
+    let dir = spec_dir("banette_exit_code_missing_spec", SPEC);
+
+    assert_eq!(exit_code(&dir, "missing.json", &[]), 3);
+}
+
+#[test]
+fn test_exit_code_dry_run_diff() {
+    let dir = spec_dir("banette_exit_code_dry_run", SPEC);
+
+    assert_eq!(exit_code(&dir, "spec.json", &["--dry-run"]), 6);
+    assert!(!dir.join("out/Api.h").exists());
+}
+
+#[test]
+fn test_exit_code_glob_keeps_failure_kind() {
+    let dir = spec_dir("banette_exit_code_glob", SPEC);
+    std::fs::write(dir.join("broken.json"), "{ not json").unwrap();
+
+    assert_eq!(exit_code(&dir, "*.json", &[]), 3);
+    assert!(dir.join("out/Spec.h").exists());
+}
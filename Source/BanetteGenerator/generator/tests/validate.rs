@@ -0,0 +1,44 @@
+/*
+ * Copyright 2019-Present tarnishablec. All Rights Reserved.
+ */
+
+mod common;
+
+use common::{run_generator, spec_dir, SPEC};
+use std::process::Output;
+
+fn validate(dir_name: &str, spec: &str) -> Output {
+    let dir = spec_dir(dir_name, spec);
+    let output = run_generator(&dir, &["validate", "--path", "spec.json"]);
+    // Validating never writes output files
+    assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+    output
+}
+
+#[test]
+fn test_validate_valid_spec() {
+    let output = validate("banette_validate_valid", SPEC);
+
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "spec.json: OK\n");
+}
+
+#[test]
+fn test_validate_bad_version() {
+    let output = validate("banette_validate_bad_version", &SPEC.replace("3.0.0", "4.0.0"));
+
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("got OpenAPI 4.0.0"), "{}", stderr);
+}
+
+#[test]
+fn test_validate_generation_hazard() {
+    let output =
+        validate("banette_validate_hazard", &SPEC.replace("\"operationId\": \"getHealth\",", ""));
+
+    assert_eq!(output.status.code(), Some(7));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("[missing-operation-id] GET /health"), "{}", stderr);
+    assert!(stderr.contains("Lint found generation hazards"), "{}", stderr);
+}